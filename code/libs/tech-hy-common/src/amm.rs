@@ -0,0 +1,85 @@
+//! Интерфейс AMM-адаптера: инструкции, которые программы экосистемы вызывают у AMM.
+//! Адаптер - любая программа, реализующая эти инструкции с указанными
+//! дискриминаторами и порядком аккаунтов (адаптер Raydium в сети, mock-amm в тестах).
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+/// Дискриминатор `collect_fees`: sha256("global:collect_fees")[..8]
+pub const AMM_COLLECT_FEES_DISCRIMINATOR: [u8; 8] = [164, 152, 207, 99, 30, 186, 19, 182];
+
+/// Аккаунты `collect_fees`: накопленные комиссии позиции переводятся на `destination`.
+/// Владелец позиции подписывает вызов (для PDA - через invoke_signed).
+pub struct AmmCollectFeesAccounts {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub position_owner: Pubkey,
+    pub fee_vault: Pubkey,
+    pub fee_mint: Pubkey,
+    pub destination: Pubkey,
+    pub token_program: Pubkey,
+}
+
+/// Инструкция `collect_fees` адаптера (без аргументов)
+pub fn amm_collect_fees_instruction(amm_program: Pubkey, accounts: &AmmCollectFeesAccounts) -> Instruction {
+    Instruction {
+        program_id: amm_program,
+        accounts: vec![
+            AccountMeta::new(accounts.pool, false),
+            AccountMeta::new(accounts.position, false),
+            AccountMeta::new_readonly(accounts.position_owner, true),
+            AccountMeta::new(accounts.fee_vault, false),
+            AccountMeta::new_readonly(accounts.fee_mint, false),
+            AccountMeta::new(accounts.destination, false),
+            AccountMeta::new_readonly(accounts.token_program, false),
+        ],
+        data: AMM_COLLECT_FEES_DISCRIMINATOR.to_vec(),
+    }
+}
+
+/// Дискриминатор `swap`: sha256("global:swap")[..8]
+pub const AMM_SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+
+/// Аккаунты `swap`: `amount_in` токенов `source_mint` обмениваются на не менее
+/// `min_amount_out` токенов `destination_mint`. Владелец токен-аккаунтов подписывает вызов.
+/// Аккаунты Transfer Hook (если нужны) добавляются вызывающим в конец списка.
+pub struct AmmSwapAccounts {
+    pub pool: Pubkey,
+    pub user_authority: Pubkey,
+    pub user_source: Pubkey,
+    pub user_destination: Pubkey,
+    pub pool_source_vault: Pubkey,
+    pub pool_destination_vault: Pubkey,
+    pub source_mint: Pubkey,
+    pub destination_mint: Pubkey,
+    pub source_token_program: Pubkey,
+    pub destination_token_program: Pubkey,
+}
+
+/// Инструкция `swap(amount_in: u64, min_amount_out: u64)` адаптера
+pub fn amm_swap_instruction(
+    amm_program: Pubkey,
+    accounts: &AmmSwapAccounts,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Instruction {
+    let mut data = AMM_SWAP_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&min_amount_out.to_le_bytes());
+    Instruction {
+        program_id: amm_program,
+        accounts: vec![
+            AccountMeta::new(accounts.pool, false),
+            AccountMeta::new_readonly(accounts.user_authority, true),
+            AccountMeta::new(accounts.user_source, false),
+            AccountMeta::new(accounts.user_destination, false),
+            AccountMeta::new(accounts.pool_source_vault, false),
+            AccountMeta::new(accounts.pool_destination_vault, false),
+            AccountMeta::new_readonly(accounts.source_mint, false),
+            AccountMeta::new_readonly(accounts.destination_mint, false),
+            AccountMeta::new_readonly(accounts.source_token_program, false),
+            AccountMeta::new_readonly(accounts.destination_token_program, false),
+        ],
+        data,
+    }
+}
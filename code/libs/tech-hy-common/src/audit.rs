@@ -0,0 +1,70 @@
+//! Отчет аудита минта, который vc-token и vg-token возвращают через return data.
+
+use anchor_lang::prelude::*;
+
+/// Результат проверки инвариантов минта: фактические значения и флаги каждой проверки.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintAuditReport {
+    /// Адрес минта
+    pub mint: Pubkey,
+    /// Программа-владелец аккаунта минта
+    pub owner_program: Pubkey,
+    /// Текущая эмиссия
+    pub supply: u64,
+    /// Десятичные знаки
+    pub decimals: u8,
+    /// Эмиссия равна полной эмиссии токена
+    pub supply_ok: bool,
+    /// Mint authority отозвана
+    pub mint_authority_revoked: bool,
+    /// Freeze authority отсутствует
+    pub freeze_authority_revoked: bool,
+    /// Десятичные знаки совпадают с ожидаемыми
+    pub decimals_ok: bool,
+    /// Минт принадлежит ожидаемой токен-программе
+    pub owner_program_ok: bool,
+    /// Все проверки пройдены
+    pub passed: bool,
+}
+
+/// Ожидаемые параметры минта
+pub struct MintAuditExpectation {
+    pub total_supply: u64,
+    pub decimals: u8,
+    pub owner_program: Pubkey,
+}
+
+impl MintAuditReport {
+    /// Сравнивает фактическое состояние минта с ожидаемым
+    pub fn build(
+        mint: Pubkey,
+        owner_program: Pubkey,
+        supply: u64,
+        decimals: u8,
+        mint_authority: Option<Pubkey>,
+        freeze_authority: Option<Pubkey>,
+        expected: &MintAuditExpectation,
+    ) -> Self {
+        let supply_ok = supply == expected.total_supply;
+        let mint_authority_revoked = mint_authority.is_none();
+        let freeze_authority_revoked = freeze_authority.is_none();
+        let decimals_ok = decimals == expected.decimals;
+        let owner_program_ok = owner_program == expected.owner_program;
+        Self {
+            mint,
+            owner_program,
+            supply,
+            decimals,
+            supply_ok,
+            mint_authority_revoked,
+            freeze_authority_revoked,
+            decimals_ok,
+            owner_program_ok,
+            passed: supply_ok
+                && mint_authority_revoked
+                && freeze_authority_revoked
+                && decimals_ok
+                && owner_program_ok,
+        }
+    }
+}
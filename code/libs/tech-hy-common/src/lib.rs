@@ -0,0 +1,35 @@
+//! Общие константы и утилиты экосистемы TECH-HY.
+//!
+//! Используется всеми программами (vc-token, vg-token, burn-and-earn, вестинг, казна, airdrop, реестр)
+//! и офчейн-клиентами, чтобы сиды PDA, десятичные знаки, расчеты в базисных
+//! пунктах, уровни NFT/DAO, интерфейс AMM-адаптера и merkle-дерево airdrop не
+//! дублировались литералами.
+
+pub mod amm;
+pub mod audit;
+pub mod math;
+pub mod merkle;
+pub mod migration;
+pub mod pda;
+pub mod seeds;
+pub mod tiers;
+pub mod token_metadata;
+
+pub use amm::*;
+pub use audit::*;
+pub use math::*;
+pub use merkle::*;
+pub use pda::*;
+pub use seeds::*;
+pub use tiers::*;
+pub use token_metadata::*;
+
+/// Десятичные знаки VC и VG токенов
+pub const TOKEN_DECIMALS: u8 = 9;
+/// Один целый токен в минимальных единицах
+pub const ONE_TOKEN: u64 = 10u64.pow(TOKEN_DECIMALS as u32);
+
+/// Полная эмиссия VC: 5 миллиардов токенов
+pub const VC_TOTAL_SUPPLY: u64 = 5_000_000_000 * ONE_TOKEN;
+/// Полная эмиссия VG: 1 миллиард токенов
+pub const VG_TOTAL_SUPPLY: u64 = 1_000_000_000 * ONE_TOKEN;
@@ -0,0 +1,86 @@
+//! Проверяемая арифметика в базисных пунктах.
+//! Все функции возвращают `None` при переполнении или неверных bps,
+//! программы превращают это в собственный `MathOverflow`.
+
+/// 100% в базисных пунктах
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Проверяет, что значение не превышает 100%
+pub fn is_valid_bps(bps: u16) -> bool {
+    bps as u64 <= BPS_DENOMINATOR
+}
+
+/// Доля `amount` в `bps` базисных пунктах с округлением вниз
+pub fn bps_of(amount: u64, bps: u16) -> Option<u64> {
+    if !is_valid_bps(bps) {
+        return None;
+    }
+    let result = (amount as u128)
+        .checked_mul(bps as u128)?
+        .checked_div(BPS_DENOMINATOR as u128)?;
+    u64::try_from(result).ok()
+}
+
+/// Делит `amount` на долю в `bps` и остаток: `(part, amount - part)`
+pub fn split_bps(amount: u64, bps: u16) -> Option<(u64, u64)> {
+    let part = bps_of(amount, bps)?;
+    Some((part, amount.checked_sub(part)?))
+}
+
+/// `amount * numerator / denominator` без промежуточного переполнения
+pub fn mul_div(amount: u64, numerator: u64, denominator: u64) -> Option<u64> {
+    if denominator == 0 {
+        return None;
+    }
+    let result = (amount as u128)
+        .checked_mul(numerator as u128)?
+        .checked_div(denominator as u128)?;
+    u64::try_from(result).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bps_of_rounds_down() {
+        assert_eq!(bps_of(10_000, 1_000), Some(1_000));
+        assert_eq!(bps_of(9_999, 1_000), Some(999));
+        assert_eq!(bps_of(9, 1_000), Some(0));
+        assert_eq!(bps_of(1, 9_999), Some(0));
+    }
+
+    #[test]
+    fn bps_of_bounds() {
+        assert_eq!(bps_of(12_345, 0), Some(0));
+        assert_eq!(bps_of(12_345, 10_000), Some(12_345));
+        assert_eq!(bps_of(u64::MAX, 10_000), Some(u64::MAX));
+        assert_eq!(bps_of(u64::MAX, 5_000), Some(u64::MAX / 2));
+        assert_eq!(bps_of(1, 10_001), None);
+        assert_eq!(bps_of(0, u16::MAX), None);
+    }
+
+    #[test]
+    fn split_bps_keeps_remainder() {
+        assert_eq!(split_bps(1_001, 5_000), Some((500, 501)));
+        assert_eq!(split_bps(7, 0), Some((0, 7)));
+        assert_eq!(split_bps(7, 10_000), Some((7, 0)));
+        let (part, rest) = split_bps(u64::MAX, 3_333).unwrap();
+        assert_eq!(part + rest, u64::MAX);
+        assert_eq!(split_bps(100, 10_001), None);
+    }
+
+    #[test]
+    fn mul_div_avoids_intermediate_overflow() {
+        assert_eq!(mul_div(u64::MAX, u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(mul_div(u64::MAX, 3, 4), Some(13_835_058_055_282_163_711));
+        assert_eq!(mul_div(10, 1, 3), Some(3));
+        assert_eq!(mul_div(0, u64::MAX, 1), Some(0));
+    }
+
+    #[test]
+    fn mul_div_rejects_overflow_and_zero_denominator() {
+        assert_eq!(mul_div(u64::MAX, 2, 1), None);
+        assert_eq!(mul_div(1, 1, 0), None);
+    }
+}
@@ -0,0 +1,139 @@
+//! Merkle-дерево airdrop программы tech-hy-distributor.
+//! Лист - sha256(0x00 || index || claimant || amount), узел - sha256(0x01 || min || max):
+//! префиксы разделяют листья и узлы, а упорядоченная пара избавляет доказательство
+//! от флагов направления. Построение дерева используется офчейн-утилитой,
+//! проверка доказательства - программой.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Максимум получателей одной раздачи: битовая карта дистрибьютора создается
+/// одной инструкцией и не должна превышать лимит размера аккаунта при init (10 КБ)
+pub const AIRDROP_MAX_CLAIMANTS: u64 = 64_000;
+
+/// Лист получателя с номером `index` в таблице airdrop
+pub fn airdrop_leaf(index: u64, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, &index.to_le_bytes(), claimant.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+/// Родительский узел двух узлов (порядок аргументов не важен)
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, first, second]).to_bytes()
+}
+
+/// Проверяет доказательство листа относительно корня
+pub fn verify_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof.iter().fold(leaf, |node, sibling| hash_pair(&node, sibling)) == *root
+}
+
+/// Дерево по всем листьям: слои от листьев к корню. Узел без пары
+/// переносится в следующий слой без изменений.
+pub struct MerkleTree {
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// Строит дерево; `None` для пустого списка листьев
+    pub fn new(leaves: Vec<[u8; 32]>) -> Option<Self> {
+        if leaves.is_empty() {
+            return None;
+        }
+        let mut layers = vec![leaves];
+        while layers.last()?.len() > 1 {
+            let next = layers
+                .last()?
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Some(Self { layers })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.layers[self.layers.len() - 1][0]
+    }
+
+    /// Доказательство листа с номером `index`; `None`, если такого листа нет
+    pub fn proof(&self, index: usize) -> Option<Vec<[u8; 32]>> {
+        if index >= self.layers[0].len() {
+            return None;
+        }
+        let mut proof = Vec::new();
+        let mut position = index;
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(position ^ 1) {
+                proof.push(*sibling);
+            }
+            position /= 2;
+        }
+        Some(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u64) -> Vec<[u8; 32]> {
+        (0..count)
+            .map(|index| airdrop_leaf(index, &Pubkey::new_from_array([index as u8 + 1; 32]), index + 1))
+            .collect()
+    }
+
+    fn assert_all_proofs(count: u64) {
+        let leaves = leaves(count);
+        let tree = MerkleTree::new(leaves.clone()).unwrap();
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert!(verify_proof(&proof, &tree.root(), *leaf), "лист {} из {}", index, count);
+        }
+        assert_eq!(tree.proof(leaves.len()), None);
+    }
+
+    #[test]
+    fn empty_tree_is_rejected() {
+        assert!(MerkleTree::new(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn single_leaf_is_root() {
+        let leaf = leaves(1)[0];
+        let tree = MerkleTree::new(vec![leaf]).unwrap();
+        assert_eq!(tree.root(), leaf);
+        assert_eq!(tree.proof(0), Some(Vec::new()));
+        assert!(verify_proof(&[], &tree.root(), leaf));
+    }
+
+    #[test]
+    fn proofs_verify_for_odd_and_even_counts() {
+        for count in [2, 3, 5, 6, 7, 8, 9, 33] {
+            assert_all_proofs(count);
+        }
+    }
+
+    #[test]
+    fn proof_rejects_other_leaf() {
+        let leaves = leaves(5);
+        let tree = MerkleTree::new(leaves.clone()).unwrap();
+        let proof = tree.proof(4).unwrap();
+        assert!(!verify_proof(&proof, &tree.root(), leaves[3]));
+        let forged = airdrop_leaf(4, &Pubkey::new_from_array([5; 32]), 6);
+        assert!(!verify_proof(&proof, &tree.root(), forged));
+    }
+
+    #[test]
+    fn hash_pair_is_order_independent() {
+        let [a, b] = [leaves(2)[0], leaves(2)[1]];
+        assert_eq!(hash_pair(&a, &b), hash_pair(&b, &a));
+        assert_ne!(hash_pair(&a, &b), a);
+    }
+}
@@ -0,0 +1,53 @@
+//! Вспомогательные функции для инструкций `migrate_*`.
+//!
+//! Каждый аккаунт экосистемы начинается с байта версии сразу после
+//! дискриминатора Anchor. Аккаунты, созданные до появления версий, не
+//! имеют этого байта и распознаются по своей фиксированной длине.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+/// Смещение байта версии в данных аккаунта (после 8 байт дискриминатора)
+pub const VERSION_OFFSET: usize = 8;
+
+/// Проверяет дискриминатор и возвращает байт версии аккаунта
+pub fn read_version(data: &[u8], discriminator: &[u8]) -> Option<u8> {
+    if data.len() <= VERSION_OFFSET || !data.starts_with(discriminator) {
+        return None;
+    }
+    Some(data[VERSION_OFFSET])
+}
+
+/// Меняет размер аккаунта программы до `new_len`.
+/// Недостающую ренту доплачивает `payer`, излишек при уменьшении возвращается ему же.
+pub fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let required = rent.minimum_balance(new_len);
+    let current = account.lamports();
+
+    if required > current {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            required - current,
+        )?;
+    } else if current > required {
+        // Аккаунт принадлежит программе, поэтому излишек можно списать напрямую
+        let excess = current - required;
+        **account.try_borrow_mut_lamports()? -= excess;
+        **payer.try_borrow_mut_lamports()? += excess;
+    }
+
+    account.resize(new_len)?;
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! Вычисление адресов PDA. ID программы передается явно, чтобы хелперы
+//! работали на любом кластере и не создавали циклических зависимостей.
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::seeds::*;
+
+/// PDA минта VC токена
+pub fn find_vc_mint_address(vc_token_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VC_TOKEN_MINT_SEED], vc_token_program)
+}
+
+/// PDA минта VG токена
+pub fn find_vg_mint_address(vg_token_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VG_TOKEN_MINT_SEED], vg_token_program)
+}
+
+/// PDA конфигурации налога VG
+pub fn find_tax_config_address(vg_token_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TAX_CONFIG_SEED], vg_token_program)
+}
+
+/// PDA глобального состояния Burn and Earn
+pub fn find_burn_and_earn_state_address(burn_and_earn_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BURN_AND_EARN_STATE_SEED], burn_and_earn_program)
+}
+
+/// PDA эскроу Burn and Earn (авторитет ATA с нераспределенными VG)
+pub fn find_burn_and_earn_escrow_address(burn_and_earn_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BURN_AND_EARN_ESCROW_SEED], burn_and_earn_program)
+}
+
+/// PDA PermanentLockVault - владельца позиции заблокированной ликвидности
+pub fn find_permanent_lock_vault_address(burn_and_earn_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PERMANENT_LOCK_VAULT_SEED], burn_and_earn_program)
+}
+
+/// PDA настроек реферальной программы Burn and Earn
+pub fn find_referral_config_address(burn_and_earn_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REFERRAL_CONFIG_SEED], burn_and_earn_program)
+}
+
+/// PDA связи пользователя с реферером
+pub fn find_referral_link_address(burn_and_earn_program: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REFERRAL_LINK_SEED, user.as_ref()], burn_and_earn_program)
+}
+
+/// PDA статистики реферера
+pub fn find_referrer_stats_address(burn_and_earn_program: &Pubkey, referrer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REFERRER_STATS_SEED, referrer.as_ref()], burn_and_earn_program)
+}
+
+/// PDA распределителя комиссий LP
+pub fn find_fee_distributor_address(burn_and_earn_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_DISTRIBUTOR_SEED], burn_and_earn_program)
+}
+
+/// PDA лимитов скорости Burn and Earn
+pub fn find_rate_limit_config_address(burn_and_earn_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RATE_LIMIT_CONFIG_SEED], burn_and_earn_program)
+}
+
+/// PDA записи пользователя о заблокированных LP
+pub fn find_user_lp_record_address(burn_and_earn_program: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_LP_RECORD_SEED, user.as_ref()], burn_and_earn_program)
+}
+
+/// PDA реестра экосистемы
+pub fn find_registry_address(registry_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ECOSYSTEM_REGISTRY_SEED], registry_program)
+}
+
+/// PDA аварийного совета
+pub fn find_emergency_council_address(registry_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EMERGENCY_COUNCIL_SEED], registry_program)
+}
+
+/// PDA счетчика графиков вестинга бенефициара
+pub fn find_vesting_index_address(vesting_program: &Pubkey, beneficiary: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VESTING_INDEX_SEED, beneficiary.as_ref()], vesting_program)
+}
+
+/// PDA графика вестинга бенефициара с порядковым номером `index`
+pub fn find_vesting_schedule_address(
+    vesting_program: &Pubkey,
+    beneficiary: &Pubkey,
+    index: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VESTING_SCHEDULE_SEED, beneficiary.as_ref(), &index.to_le_bytes()],
+        vesting_program,
+    )
+}
+
+/// PDA аккаунта метаданных Metaplex для минта
+pub fn find_metadata_address(metadata_program: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[METADATA_SEED, metadata_program.as_ref(), mint.as_ref()],
+        metadata_program,
+    )
+}
+
+/// PDA казны DAO (владелец токен-аккаунтов казны)
+pub fn find_treasury_address(treasury_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED], treasury_program)
+}
+
+/// PDA состояния казны DAO
+pub fn find_treasury_state_address(treasury_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_STATE_SEED], treasury_program)
+}
+
+/// PDA бюджета категории казны для актива (`Pubkey::default()` - SOL)
+pub fn find_treasury_budget_address(treasury_program: &Pubkey, category: u8, asset: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_BUDGET_SEED, &[category], asset.as_ref()], treasury_program)
+}
+
+/// PDA merkle-дистрибьютора airdrop для минта и номера раздачи
+pub fn find_merkle_distributor_address(distributor_program: &Pubkey, mint: &Pubkey, distribution_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MERKLE_DISTRIBUTOR_SEED, mint.as_ref(), &distribution_id.to_le_bytes()],
+        distributor_program,
+    )
+}
+
+/// PDA битовой карты полученных airdrop дистрибьютора
+pub fn find_claim_bitmap_address(distributor_program: &Pubkey, distributor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CLAIM_BITMAP_SEED, distributor.as_ref()], distributor_program)
+}
@@ -0,0 +1,50 @@
+//! Сиды PDA всех программ экосистемы.
+
+/// Минт VC токена (программа vc-token)
+pub const VC_TOKEN_MINT_SEED: &[u8] = b"vc_token_mint";
+/// Минт VG токена (программа vg-token)
+pub const VG_TOKEN_MINT_SEED: &[u8] = b"vg_token_mint";
+/// Конфигурация VC токена (программа vc-token)
+pub const VC_CONFIG_SEED: &[u8] = b"vc_config";
+/// Конфигурация налога VG (программа vg-token)
+pub const TAX_CONFIG_SEED: &[u8] = b"tax_config_seed";
+/// Глобальное состояние Burn and Earn
+pub const BURN_AND_EARN_STATE_SEED: &[u8] = b"burn_and_earn_state";
+/// Эскроу Burn and Earn: авторитет токен-аккаунта с полной эмиссией VG
+pub const BURN_AND_EARN_ESCROW_SEED: &[u8] = b"burn_and_earn_escrow";
+/// PermanentLockVault: владелец позиции заблокированной ликвидности (программа burn-and-earn)
+pub const PERMANENT_LOCK_VAULT_SEED: &[u8] = b"permanent_lock_vault";
+/// Распределитель комиссий LP держателям NFT Fee Key (программа burn-and-earn)
+pub const FEE_DISTRIBUTOR_SEED: &[u8] = b"fee_distributor";
+/// Лимиты скорости burn_and_lock (программа burn-and-earn)
+pub const RATE_LIMIT_CONFIG_SEED: &[u8] = b"rate_limit_config";
+/// Настройки реферальной программы burn_and_lock (программа burn-and-earn)
+pub const REFERRAL_CONFIG_SEED: &[u8] = b"referral_config";
+/// Связь пользователя с реферером, записывается один раз (программа burn-and-earn)
+pub const REFERRAL_LINK_SEED: &[u8] = b"referral_link";
+/// Статистика реферера (программа burn-and-earn)
+pub const REFERRER_STATS_SEED: &[u8] = b"referrer_stats";
+/// Запись пользователя о заблокированных LP (программа burn-and-earn)
+pub const USER_LP_RECORD_SEED: &[u8] = b"user_lp_record";
+/// Казна DAO: владелец токен-аккаунтов казны (программа treasury)
+pub const TREASURY_SEED: &[u8] = b"treasury";
+/// Состояние казны DAO (программа treasury)
+pub const TREASURY_STATE_SEED: &[u8] = b"treasury_state";
+/// Маршрут выкупа VC через AMM (программа treasury)
+pub const TREASURY_BUYBACK_ROUTE_SEED: &[u8] = b"treasury_buyback_route";
+/// Бюджет категории расходов казны по активу (программа treasury)
+pub const TREASURY_BUDGET_SEED: &[u8] = b"treasury_budget";
+/// Merkle-дистрибьютор airdrop (программа tech-hy-distributor)
+pub const MERKLE_DISTRIBUTOR_SEED: &[u8] = b"merkle_distributor";
+/// Битовая карта полученных airdrop (программа tech-hy-distributor)
+pub const CLAIM_BITMAP_SEED: &[u8] = b"claim_bitmap";
+/// Реестр экосистемы (программа tech_hy_contracts)
+pub const ECOSYSTEM_REGISTRY_SEED: &[u8] = b"ecosystem_registry";
+/// Аварийный совет (программа tech_hy_contracts)
+pub const EMERGENCY_COUNCIL_SEED: &[u8] = b"emergency_council";
+/// График вестинга (программа tech-hy-vesting)
+pub const VESTING_SCHEDULE_SEED: &[u8] = b"vesting_schedule";
+/// Счетчик графиков вестинга бенефициара (программа tech-hy-vesting)
+pub const VESTING_INDEX_SEED: &[u8] = b"vesting_index";
+/// Аккаунт метаданных Metaplex
+pub const METADATA_SEED: &[u8] = b"metadata";
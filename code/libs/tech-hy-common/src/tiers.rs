@@ -0,0 +1,307 @@
+//! Уровни NFT Fee Key, NFT "Investor's Hand" и уровни участия в DAO.
+
+use anchor_lang::prelude::*;
+
+use crate::ONE_TOKEN;
+
+/// Шаблон URI метаданных NFT Fee Key; `{tier}` и `{id}` подставляются при минте
+pub const FEE_KEY_METADATA_URI_TEMPLATE: &str = "https://token-cdn.tech-hy.com/fee-key/{tier}/{id}.json";
+
+/// Уровень NFT Fee Key, определяемый объемом заблокированных LP.
+/// Сериализуется в один байт (0 = нет NFT, 1 = Bronze, ... 4 = Platinum).
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FeeKeyTier {
+    /// Нет NFT
+    #[default]
+    None,
+    /// От 1,000 LP
+    Bronze,
+    /// От 10,000 LP
+    Silver,
+    /// От 100,000 LP
+    Gold,
+    /// От 1,000,000 LP
+    Platinum,
+}
+
+impl FeeKeyTier {
+    /// Все уровни от высшего к низшему
+    pub const DESCENDING: [FeeKeyTier; 4] = [
+        FeeKeyTier::Platinum,
+        FeeKeyTier::Gold,
+        FeeKeyTier::Silver,
+        FeeKeyTier::Bronze,
+    ];
+
+    /// Минимальное количество заблокированных LP для уровня
+    pub const fn threshold(self) -> u64 {
+        match self {
+            FeeKeyTier::None => 0,
+            FeeKeyTier::Bronze => 1_000 * ONE_TOKEN,
+            FeeKeyTier::Silver => 10_000 * ONE_TOKEN,
+            FeeKeyTier::Gold => 100_000 * ONE_TOKEN,
+            FeeKeyTier::Platinum => 1_000_000 * ONE_TOKEN,
+        }
+    }
+
+    /// Определяет уровень по количеству заблокированных LP
+    pub fn from_locked_lp(locked_lp: u64) -> Self {
+        Self::DESCENDING
+            .into_iter()
+            .find(|tier| locked_lp >= tier.threshold())
+            .unwrap_or(FeeKeyTier::None)
+    }
+
+    /// Уровень по его числовому значению (для записей, хранивших уровень как `u8`)
+    pub const fn from_level(level: u8) -> Option<Self> {
+        match level {
+            0 => Some(FeeKeyTier::None),
+            1 => Some(FeeKeyTier::Bronze),
+            2 => Some(FeeKeyTier::Silver),
+            3 => Some(FeeKeyTier::Gold),
+            4 => Some(FeeKeyTier::Platinum),
+            _ => None,
+        }
+    }
+
+    /// Отображаемое название уровня
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            FeeKeyTier::None => "None",
+            FeeKeyTier::Bronze => "Bronze",
+            FeeKeyTier::Silver => "Silver",
+            FeeKeyTier::Gold => "Gold",
+            FeeKeyTier::Platinum => "Platinum",
+        }
+    }
+
+    /// Сегмент пути уровня в шаблоне URI метаданных
+    pub const fn uri_slug(self) -> &'static str {
+        match self {
+            FeeKeyTier::None => "none",
+            FeeKeyTier::Bronze => "bronze",
+            FeeKeyTier::Silver => "silver",
+            FeeKeyTier::Gold => "gold",
+            FeeKeyTier::Platinum => "platinum",
+        }
+    }
+
+    /// Шаблон URI метаданных для NFT этого уровня (`{id}` остается для подстановки)
+    pub fn metadata_uri_template(self) -> String {
+        FEE_KEY_METADATA_URI_TEMPLATE.replace("{tier}", self.uri_slug())
+    }
+
+    /// URI метаданных конкретного NFT Fee Key
+    pub fn metadata_uri(self, id: u64) -> String {
+        self.metadata_uri_template().replace("{id}", &id.to_string())
+    }
+
+    /// Множитель доли в комиссиях в базисных пунктах (10000 = 1.0x)
+    pub const fn fee_multiplier_bps(self) -> u16 {
+        match self {
+            FeeKeyTier::None => 0,
+            FeeKeyTier::Bronze => 10_000,
+            FeeKeyTier::Silver => 12_000,
+            FeeKeyTier::Gold => 15_000,
+            FeeKeyTier::Platinum => 20_000,
+        }
+    }
+}
+
+/// Уровень NFT коллекции "Investor's Hand"
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandLevel {
+    Paper,
+    Wooden,
+    Steel,
+    Titanium,
+    Diamond,
+}
+
+impl HandLevel {
+    /// Множитель доходности стейкинга в базисных пунктах (11000 = 1.1x)
+    pub const fn multiplier_bps(self) -> u16 {
+        match self {
+            HandLevel::Paper => 11_000,
+            HandLevel::Wooden => 12_500,
+            HandLevel::Steel => 15_000,
+            HandLevel::Titanium => 17_500,
+            HandLevel::Diamond => 20_000,
+        }
+    }
+
+    /// Дополнительные VC, которые нужно застейкать для получения уровня
+    pub const fn required_vc(self) -> u64 {
+        match self {
+            HandLevel::Paper => 1_000_000 * ONE_TOKEN,
+            HandLevel::Wooden => 1_500_000 * ONE_TOKEN,
+            HandLevel::Steel => 2_500_000 * ONE_TOKEN,
+            HandLevel::Titanium => 4_000_000 * ONE_TOKEN,
+            HandLevel::Diamond => 6_000_000 * ONE_TOKEN,
+        }
+    }
+
+    /// Минимальный период стейкинга в днях
+    pub const fn staking_days(self) -> u16 {
+        match self {
+            HandLevel::Paper => 30,
+            HandLevel::Wooden => 60,
+            HandLevel::Steel => 90,
+            HandLevel::Titanium => 180,
+            HandLevel::Diamond => 365,
+        }
+    }
+
+    /// Следующий уровень в цепочке Paper → Wooden → Steel → Titanium → Diamond
+    pub const fn next(self) -> Option<HandLevel> {
+        match self {
+            HandLevel::Paper => Some(HandLevel::Wooden),
+            HandLevel::Wooden => Some(HandLevel::Steel),
+            HandLevel::Steel => Some(HandLevel::Titanium),
+            HandLevel::Titanium => Some(HandLevel::Diamond),
+            HandLevel::Diamond => None,
+        }
+    }
+}
+
+/// Уровень участия в DAO
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DaoTier {
+    Starter,
+    CommunityMember,
+    Contributor,
+    Founder,
+    Expert,
+    Investor,
+    LaunchpadMaster,
+    Partner,
+    /// Определяется владением Angel Investor NFT, а не количеством VG
+    Angel,
+}
+
+impl DaoTier {
+    /// Минимальное количество застейканных VG для уровня
+    pub const fn min_staked_vg(self) -> u64 {
+        match self {
+            DaoTier::Starter | DaoTier::Angel => 0,
+            DaoTier::CommunityMember => 100 * ONE_TOKEN,
+            DaoTier::Contributor => 500 * ONE_TOKEN,
+            DaoTier::Founder => 1_500 * ONE_TOKEN,
+            DaoTier::Expert => 4_000 * ONE_TOKEN,
+            DaoTier::Investor => 25_000 * ONE_TOKEN,
+            DaoTier::LaunchpadMaster => 50_000 * ONE_TOKEN,
+            DaoTier::Partner => 70_000 * ONE_TOKEN,
+        }
+    }
+
+    /// Минимальный уровень NFT "Investor's Hand", необходимый для уровня DAO
+    pub const fn required_hand(self) -> Option<HandLevel> {
+        match self {
+            DaoTier::Investor => Some(HandLevel::Steel),
+            DaoTier::LaunchpadMaster => Some(HandLevel::Titanium),
+            DaoTier::Partner => Some(HandLevel::Diamond),
+            _ => None,
+        }
+    }
+
+    /// Определяет уровень DAO по застейканным VG и уровню NFT "Investor's Hand".
+    /// Уровень Angel назначается отдельно по Angel Investor NFT.
+    pub fn from_stake(staked_vg: u64, hand: Option<HandLevel>) -> Self {
+        const DESCENDING: [DaoTier; 8] = [
+            DaoTier::Partner,
+            DaoTier::LaunchpadMaster,
+            DaoTier::Investor,
+            DaoTier::Expert,
+            DaoTier::Founder,
+            DaoTier::Contributor,
+            DaoTier::CommunityMember,
+            DaoTier::Starter,
+        ];
+        DESCENDING
+            .into_iter()
+            .find(|tier| {
+                staked_vg >= tier.min_staked_vg()
+                    && match tier.required_hand() {
+                        Some(required) => hand.is_some_and(|h| h >= required),
+                        None => true,
+                    }
+            })
+            .unwrap_or(DaoTier::Starter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_key_tier_thresholds_are_inclusive() {
+        for tier in FeeKeyTier::DESCENDING {
+            assert_eq!(FeeKeyTier::from_locked_lp(tier.threshold()), tier);
+            assert!(FeeKeyTier::from_locked_lp(tier.threshold() - 1) < tier);
+        }
+        assert_eq!(FeeKeyTier::from_locked_lp(0), FeeKeyTier::None);
+        assert_eq!(FeeKeyTier::from_locked_lp(999 * ONE_TOKEN), FeeKeyTier::None);
+        assert_eq!(FeeKeyTier::from_locked_lp(u64::MAX), FeeKeyTier::Platinum);
+    }
+
+    #[test]
+    fn fee_key_tier_level_roundtrip() {
+        for level in 0..=4u8 {
+            let tier = FeeKeyTier::from_level(level).unwrap();
+            assert_eq!(tier as u8, level);
+        }
+        assert_eq!(FeeKeyTier::from_level(5), None);
+    }
+
+    #[test]
+    fn fee_key_tier_multipliers_grow_with_tier() {
+        assert_eq!(FeeKeyTier::None.fee_multiplier_bps(), 0);
+        assert_eq!(FeeKeyTier::Bronze.fee_multiplier_bps(), 10_000);
+        assert_eq!(FeeKeyTier::Silver.fee_multiplier_bps(), 12_000);
+        assert_eq!(FeeKeyTier::Gold.fee_multiplier_bps(), 15_000);
+        assert_eq!(FeeKeyTier::Platinum.fee_multiplier_bps(), 20_000);
+        for pair in FeeKeyTier::DESCENDING.windows(2) {
+            assert!(pair[0].fee_multiplier_bps() > pair[1].fee_multiplier_bps());
+            assert!(pair[0].threshold() > pair[1].threshold());
+        }
+    }
+
+    #[test]
+    fn fee_key_tier_metadata_uri() {
+        assert_eq!(
+            FeeKeyTier::Gold.metadata_uri(7),
+            "https://token-cdn.tech-hy.com/fee-key/gold/7.json"
+        );
+        assert_eq!(FeeKeyTier::Silver.display_name(), "Silver");
+    }
+
+    #[test]
+    fn dao_tier_thresholds() {
+        assert_eq!(DaoTier::from_stake(0, None), DaoTier::Starter);
+        assert_eq!(DaoTier::from_stake(100 * ONE_TOKEN - 1, None), DaoTier::Starter);
+        assert_eq!(DaoTier::from_stake(100 * ONE_TOKEN, None), DaoTier::CommunityMember);
+        assert_eq!(DaoTier::from_stake(1_500 * ONE_TOKEN, None), DaoTier::Founder);
+        // Без NFT "Investor's Hand" уровень ограничен Expert при любом стейке
+        assert_eq!(DaoTier::from_stake(u64::MAX, None), DaoTier::Expert);
+        assert_eq!(DaoTier::from_stake(25_000 * ONE_TOKEN, Some(HandLevel::Wooden)), DaoTier::Expert);
+        assert_eq!(DaoTier::from_stake(25_000 * ONE_TOKEN, Some(HandLevel::Steel)), DaoTier::Investor);
+        assert_eq!(DaoTier::from_stake(70_000 * ONE_TOKEN, Some(HandLevel::Titanium)), DaoTier::LaunchpadMaster);
+        assert_eq!(DaoTier::from_stake(70_000 * ONE_TOKEN, Some(HandLevel::Diamond)), DaoTier::Partner);
+        assert_eq!(DaoTier::from_stake(70_000 * ONE_TOKEN - 1, Some(HandLevel::Diamond)), DaoTier::LaunchpadMaster);
+    }
+
+    #[test]
+    fn hand_level_chain_ends_at_diamond() {
+        let mut level = HandLevel::Paper;
+        let mut steps = 0;
+        while let Some(next) = level.next() {
+            assert!(next > level);
+            assert!(next.multiplier_bps() > level.multiplier_bps());
+            level = next;
+            steps += 1;
+        }
+        assert_eq!(level, HandLevel::Diamond);
+        assert_eq!(steps, 4);
+    }
+}
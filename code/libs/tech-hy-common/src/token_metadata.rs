@@ -0,0 +1,178 @@
+//! TokenMetadata (Token-2022) для VC и VG: ключи и значения дополнительных полей,
+//! проверка имени, символа и URI и создание метаданных в самом минте.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_2022_extensions::{
+    spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_token_metadata_interface::state::{Field, TokenMetadata},
+    token_metadata_initialize, token_metadata_update_authority, token_metadata_update_field,
+    TokenMetadataInitialize, TokenMetadataUpdateAuthority, TokenMetadataUpdateField,
+};
+
+/// Роль токена в экосистеме
+pub const METADATA_KEY_ECOSYSTEM_ROLE: &str = "ecosystem_role";
+/// Налог на перевод в базисных пунктах
+pub const METADATA_KEY_TAX_RATE_BPS: &str = "tax_rate_bps";
+/// Полная эмиссия в минимальных единицах
+pub const METADATA_KEY_TOTAL_SUPPLY: &str = "total_supply";
+/// ID программы Transfer Hook
+pub const METADATA_KEY_TRANSFER_HOOK_PROGRAM: &str = "transfer_hook_program";
+
+/// Роль VC: базовый токен Venture Club
+pub const VC_ECOSYSTEM_ROLE: &str = "venture_club";
+/// Роль VG: токен управления
+pub const VG_ECOSYSTEM_ROLE: &str = "governance";
+
+/// Ограничения Metaplex на имя, символ и URI; TokenMetadata следует им же,
+/// чтобы кошельки и обозреватели показывали VC и VG одинаково
+pub const METADATA_MAX_NAME_LENGTH: usize = 32;
+pub const METADATA_MAX_SYMBOL_LENGTH: usize = 10;
+pub const METADATA_MAX_URI_LENGTH: usize = 200;
+
+/// Поле метаданных, не прошедшее проверку; программа сводит его к своему коду ошибки
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetadataFieldError {
+    Name,
+    Symbol,
+    Uri,
+}
+
+/// Проверяет поля метаданных: непустое имя, символ из заглавных латинских букв и цифр,
+/// URI с https://; длины - в пределах ограничений Metaplex.
+pub fn validate_metadata_fields(name: &str, symbol: &str, uri: &str) -> core::result::Result<(), MetadataFieldError> {
+    if name.is_empty() || name.len() > METADATA_MAX_NAME_LENGTH {
+        return Err(MetadataFieldError::Name);
+    }
+    if symbol.is_empty()
+        || symbol.len() > METADATA_MAX_SYMBOL_LENGTH
+        || !symbol.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+    {
+        return Err(MetadataFieldError::Symbol);
+    }
+    if !uri.starts_with("https://") || uri.len() > METADATA_MAX_URI_LENGTH {
+        return Err(MetadataFieldError::Uri);
+    }
+    Ok(())
+}
+
+/// Поля TokenMetadata, записываемые в минт
+pub struct TokenMetadataFields {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub additional_metadata: Vec<(String, String)>,
+    /// Итоговый update authority метаданных
+    pub update_authority: Pubkey,
+}
+
+/// Создает TokenMetadata в самом минте (MetadataPointer указывает на минт), записывает
+/// дополнительные поля и передает update authority. До передачи авторитетом метаданных
+/// временно является PDA минта, поэтому все CPI подписываются его сидами.
+pub fn initialize_token_metadata<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    fields: TokenMetadataFields,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // Token-2022 сам расширяет аккаунт минта, но лампорты на ренту должны быть внесены заранее
+    let metadata_len = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(mint.key()))?,
+        mint: mint.key(),
+        name: fields.name.clone(),
+        symbol: fields.symbol.clone(),
+        uri: fields.uri.clone(),
+        additional_metadata: fields.additional_metadata.clone(),
+    }
+    .tlv_size_of()?;
+    let required_lamports = Rent::get()?.minimum_balance(mint.data_len() + metadata_len);
+    let top_up = required_lamports.saturating_sub(mint.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: mint.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataInitialize {
+                program_id: token_program.clone(),
+                metadata: mint.clone(),
+                update_authority: mint.clone(),
+                mint_authority: mint.clone(),
+                mint: mint.clone(),
+            },
+            signer_seeds,
+        ),
+        fields.name,
+        fields.symbol,
+        fields.uri,
+    )?;
+
+    for (key, value) in fields.additional_metadata {
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TokenMetadataUpdateField {
+                    program_id: token_program.clone(),
+                    metadata: mint.clone(),
+                    update_authority: mint.clone(),
+                },
+                signer_seeds,
+            ),
+            Field::Key(key),
+            value,
+        )?;
+    }
+
+    token_metadata_update_authority(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataUpdateAuthority {
+                program_id: token_program.clone(),
+                metadata: mint.clone(),
+                current_authority: mint.clone(),
+                new_authority: mint.clone(),
+            },
+            signer_seeds,
+        ),
+        OptionalNonZeroPubkey::try_from(Some(fields.update_authority))?,
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URI: &str = "https://tech-hy.io/metadata/vc-token.json";
+
+    #[test]
+    fn accepts_ecosystem_metadata() {
+        assert_eq!(validate_metadata_fields("Venture Club Token", "VC", URI), Ok(()));
+        assert_eq!(validate_metadata_fields("TECH-HY Governance", "VG2", URI), Ok(()));
+    }
+
+    #[test]
+    fn rejects_bad_fields() {
+        let long_name = "N".repeat(METADATA_MAX_NAME_LENGTH + 1);
+        assert_eq!(validate_metadata_fields("", "VC", URI), Err(MetadataFieldError::Name));
+        assert_eq!(validate_metadata_fields(&long_name, "VC", URI), Err(MetadataFieldError::Name));
+        assert_eq!(validate_metadata_fields("VC", "vc", URI), Err(MetadataFieldError::Symbol));
+        assert_eq!(validate_metadata_fields("VC", "VENTURECLUB", URI), Err(MetadataFieldError::Symbol));
+        assert_eq!(validate_metadata_fields("VC", "", URI), Err(MetadataFieldError::Symbol));
+        assert_eq!(validate_metadata_fields("VC", "VC", "http://tech-hy.io"), Err(MetadataFieldError::Uri));
+        let long_uri = format!("https://{}", "a".repeat(METADATA_MAX_URI_LENGTH));
+        assert_eq!(validate_metadata_fields("VC", "VC", &long_uri), Err(MetadataFieldError::Uri));
+    }
+}
@@ -1,16 +1,15 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    token_interface::{Mint, TokenAccount, TokenInterface},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
     associated_token::AssociatedToken,
 };
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::system_program;
 use libm::log10;
 use solana_program::clock::Clock;
 
-// Импортируем программы VC и VG токенов для CPI вызовов
-
-// Импорт ID программ VC и VG токенов
-use vc_token::ID as VC_TOKEN_ID;
-use vg_token::ID as VG_TOKEN_ID;
+// Реестр экосистемы: канонические адреса минтов и программ, состояние паузы
+use tech_hy_contracts::{EcosystemRegistry, ECOSYSTEM_REGISTRY_SEED, ID as REGISTRY_PROGRAM_ID};
 
 declare_id!("BAEpWRJiqZrZkmyzGbcBAvQYpRKbRq5L3D5WwA1dvYf5");
 
@@ -18,13 +17,34 @@ declare_id!("BAEpWRJiqZrZkmyzGbcBAvQYpRKbRq5L3D5WwA1dvYf5");
 pub const BASE_COEFFICIENT: f64 = 10.0; // C = 10
 pub const BONUS_COEFFICIENT: f64 = 0.2; // B = 0.2
 pub const MIN_LP_AMOUNT: f64 = 1.0; // LP_min = 1
-pub const DECIMALS: u8 = 9; // Десятичные знаки для токенов
 
-// Константы для уровней NFT Fee Key
-pub const BRONZE_LEVEL_THRESHOLD: u64 = 1_000 * 10u64.pow(DECIMALS as u32); // 1,000 LP
-pub const SILVER_LEVEL_THRESHOLD: u64 = 10_000 * 10u64.pow(DECIMALS as u32); // 10,000 LP
-pub const GOLD_LEVEL_THRESHOLD: u64 = 100_000 * 10u64.pow(DECIMALS as u32); // 100,000 LP
-pub const PLATINUM_LEVEL_THRESHOLD: u64 = 1_000_000 * 10u64.pow(DECIMALS as u32); // 1,000,000 LP
+// Ограничения эмиссии VG по умолчанию (меняются через update_emission_config)
+pub const DEFAULT_EPOCH_VG_BUDGET: u64 = 10_000_000 * ONE_TOKEN; // 1% эскроу за эпоху
+pub const DEFAULT_EMISSION_DECAY_BPS: u16 = 10_000; // Линейное затухание до нуля при исчерпании эскроу
+
+// Лимиты скорости burn_and_lock по умолчанию (0 - без ограничения, меняются DAO)
+pub const DEFAULT_MAX_VC_PER_TX: u64 = 10_000_000 * ONE_TOKEN;
+pub const DEFAULT_MAX_VC_PER_USER_PER_EPOCH: u64 = 50_000_000 * ONE_TOKEN;
+pub const DEFAULT_MAX_VC_PER_SLOT: u64 = 100_000_000 * ONE_TOKEN;
+
+/// Масштаб накопленных комиссий на единицу LP (acc_fee_per_lp)
+pub const ACC_FEE_PRECISION: u128 = 1_000_000_000_000;
+
+/// Максимальная доля эмиссии VG, которую DAO может направить рефереру (20%)
+pub const MAX_REFERRAL_BPS: u16 = 2_000;
+
+pub use tech_hy_common::TOKEN_DECIMALS as DECIMALS; // Десятичные знаки для токенов
+
+// Сиды PDA
+pub use tech_hy_common::{
+    BURN_AND_EARN_ESCROW_SEED, BURN_AND_EARN_STATE_SEED, FEE_DISTRIBUTOR_SEED, PERMANENT_LOCK_VAULT_SEED,
+    RATE_LIMIT_CONFIG_SEED, REFERRAL_CONFIG_SEED, REFERRAL_LINK_SEED, REFERRER_STATS_SEED, USER_LP_RECORD_SEED,
+};
+// Уровень NFT Fee Key: порог, название, URI метаданных и множитель комиссий
+pub use tech_hy_common::FeeKeyTier;
+use tech_hy_common::{bps_of, is_valid_bps, mul_div, BPS_DENOMINATOR, ONE_TOKEN, VG_TOTAL_SUPPLY};
+use tech_hy_common::{amm_collect_fees_instruction, AmmCollectFeesAccounts};
+use tech_hy_common::migration::resize_account;
 
 #[program]
 pub mod burn_and_earn {
@@ -33,6 +53,7 @@ pub mod burn_and_earn {
     /// Инициализирует программу Burn and Earn
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let burn_and_earn_state = &mut ctx.accounts.burn_and_earn_state;
+        burn_and_earn_state.version = BurnAndEarnState::VERSION;
         burn_and_earn_state.authority = ctx.accounts.authority.key();
         burn_and_earn_state.vc_mint = ctx.accounts.vc_mint.key();
         burn_and_earn_state.vg_mint = ctx.accounts.vg_mint.key();
@@ -40,21 +61,54 @@ pub mod burn_and_earn {
         burn_and_earn_state.total_vg_minted = 0;
         burn_and_earn_state.total_vc_burned = 0;
         burn_and_earn_state.bump = ctx.bumps.burn_and_earn_state;
+        burn_and_earn_state.escrow_remaining = VG_TOTAL_SUPPLY;
+        burn_and_earn_state.epoch_vg_budget = DEFAULT_EPOCH_VG_BUDGET;
+        burn_and_earn_state.current_epoch = Clock::get()?.epoch;
+        burn_and_earn_state.epoch_vg_emitted = 0;
+        burn_and_earn_state.emission_decay_bps = DEFAULT_EMISSION_DECAY_BPS;
+        burn_and_earn_state.pro_rate_over_cap = true;
+        burn_and_earn_state.acc_fee_per_lp = 0;
+        burn_and_earn_state.total_lp_fees_harvested = 0;
+
+        let rate_limit_config = &mut ctx.accounts.rate_limit_config;
+        rate_limit_config.version = RateLimitConfig::VERSION;
+        rate_limit_config.params = RateLimitParams {
+            max_vc_per_tx: DEFAULT_MAX_VC_PER_TX,
+            max_vc_per_user_per_epoch: DEFAULT_MAX_VC_PER_USER_PER_EPOCH,
+            max_vc_per_slot: DEFAULT_MAX_VC_PER_SLOT,
+        };
+        rate_limit_config.current_slot = 0;
+        rate_limit_config.slot_vc_burned = 0;
+        rate_limit_config.bump = ctx.bumps.rate_limit_config;
         
         msg!("Burn and Earn программа инициализирована");
         Ok(())
     }
 
     /// Блокирует VC токены, конвертирует их в LP и эмитирует VG токены
-    /// в соответствии с формулой VG = LP * C * (1 + B * log10(LP/LP_min))
+    /// в соответствии с формулой VG = LP * C * (1 + B * log10(LP/LP_min)).
+    /// Защита от фронтраннинга: `min_lp_out` и `min_vg_out` - минимально допустимые
+    /// выход пула и эмиссия, `deadline` - unix timestamp, после которого транзакция отклоняется.
+    /// `referrer` записывается в ReferralLink при первой блокировке пользователя и должен
+    /// передаваться на каждой следующей; рефереру начисляется доля эмиссии VG,
+    /// заданная DAO в ReferralConfig.
     pub fn burn_and_lock(
         ctx: Context<BurnAndLock>,
         vc_amount: u64,
+        min_lp_out: u64,
+        min_vg_out: u64,
+        deadline: i64,
+        referrer: Option<Pubkey>,
     ) -> Result<()> {
         // Проверка параметров
         if vc_amount == 0 {
             return err!(BurnAndEarnError::InvalidAmount);
         }
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        if now > deadline {
+            return err!(BurnAndEarnError::DeadlineExceeded);
+        }
 
         msg!("Начало конвертации {} VC токенов", vc_amount);
 
@@ -65,49 +119,175 @@ pub mod burn_and_earn {
         // В реальной реализации здесь будет вызов к Raydium API
         let lp_amount = simulate_vc_to_lp_conversion(vc_amount)?;
         msg!("Сконвертировано в {} LP токенов", lp_amount);
+        if lp_amount < min_lp_out {
+            msg!("Выход пула {} LP меньше минимального {}", lp_amount, min_lp_out);
+            return err!(BurnAndEarnError::LpSlippageExceeded);
+        }
 
         // 3. Блокируем LP токены
         // TODO: Реализовать блокировку в пуле
 
         // 4. Создаем или обновляем запись о заблокированных LP для пользователя
         let user_record = &mut ctx.accounts.user_lp_record;
-        if !user_record.is_initialized {
+        let is_new_user = !user_record.is_initialized;
+        if is_new_user {
+            user_record.version = UserLpRecord::VERSION;
             user_record.owner = ctx.accounts.user.key();
             user_record.is_initialized = true;
             user_record.locked_lp = 0;
             user_record.vg_minted = 0;
             user_record.vc_burned = 0;
-            user_record.nft_level = 0; // 0 = нет NFT
-            user_record.last_update = Clock::get()?.unix_timestamp;
+            user_record.nft_level = FeeKeyTier::None;
+            user_record.last_update = now;
+            user_record.vc_epoch = clock.epoch;
+            user_record.epoch_vc_burned = 0;
+            user_record.fee_debt = 0;
+            user_record.fees_owed = 0;
+        } else if user_record.version != UserLpRecord::VERSION {
+            return err!(BurnAndEarnError::AccountNeedsMigration);
         }
 
+        // Лимиты скорости: на транзакцию, на пользователя за эпоху и глобально на слот
+        enforce_rate_limits(&mut ctx.accounts.rate_limit_config, user_record, vc_amount, clock.epoch, clock.slot)?;
+
+        // Комиссии LP, накопленные на прежний объем, фиксируются до его изменения
+        let acc_fee_per_lp = ctx.accounts.burn_and_earn_state.acc_fee_per_lp;
+        settle_lp_fees(user_record, acc_fee_per_lp)?;
+
         // Обновляем запись для пользователя
         let previous_lp = user_record.locked_lp;
         user_record.locked_lp = user_record.locked_lp.checked_add(lp_amount).ok_or(BurnAndEarnError::MathOverflow)?;
+        user_record.fee_debt = accumulated_lp_fees(user_record.locked_lp, acc_fee_per_lp)?;
         user_record.vc_burned = user_record.vc_burned.checked_add(vc_amount).ok_or(BurnAndEarnError::MathOverflow)?;
-        user_record.last_update = Clock::get()?.unix_timestamp;
+        user_record.last_update = now;
 
         // Обновляем глобальную статистику
         let burn_and_earn_state = &mut ctx.accounts.burn_and_earn_state;
         burn_and_earn_state.total_locked_lp = burn_and_earn_state.total_locked_lp.checked_add(lp_amount).ok_or(BurnAndEarnError::MathOverflow)?;
         burn_and_earn_state.total_vc_burned = burn_and_earn_state.total_vc_burned.checked_add(vc_amount).ok_or(BurnAndEarnError::MathOverflow)?;
 
-        // 5. Рассчитываем количество VG токенов для эмиссии по формуле
-        let new_vg_amount = calculate_vg_emission(user_record.locked_lp, previous_lp)?;
-        msg!("Эмиссия {} VG токенов", new_vg_amount);
+        // 5. Реферер: связь записывается один раз, при первой блокировке пользователя,
+        // и на всех следующих блокировках передается обязательно
+        if referrer == Some(Pubkey::default()) {
+            return err!(BurnAndEarnError::InvalidReferrer);
+        }
+        let user = ctx.accounts.user.key();
+        // Связь создается только при записи реферера, поэтому пользователь без реферера
+        // не платит за нее ренту. PDA передается всегда: существующую связь нельзя обойти
+        let link_info = ctx.accounts.referral_link.to_account_info();
+        let mut referral_link = if link_info.data_is_empty() {
+            None
+        } else {
+            if link_info.owner != &crate::ID {
+                return err!(BurnAndEarnError::InvalidReferrer);
+            }
+            Some(ReferralLink::try_deserialize(&mut &link_info.try_borrow_data()?[..])?)
+        };
+        let link_created = referral_link.is_none() && referrer.is_some();
+        match (&referral_link, referrer) {
+            (Some(link), _) => {
+                if link.referrer != referrer.unwrap_or_default() {
+                    return err!(BurnAndEarnError::InvalidReferrer);
+                }
+            }
+            (None, Some(referrer)) => {
+                // Реферер должен уже иметь заблокированные LP, поэтому связи всегда
+                // указывают на более ранних участников и циклы невозможны
+                if referrer == user {
+                    return err!(BurnAndEarnError::SelfReferral);
+                }
+                let referrer_record = ctx.accounts.referrer_lp_record.as_ref().ok_or(BurnAndEarnError::InvalidReferrer)?;
+                if !is_new_user || referrer_record.locked_lp == 0 {
+                    return err!(BurnAndEarnError::InvalidReferrer);
+                }
+                create_referral_link(
+                    &link_info,
+                    &ctx.accounts.user.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    &[REFERRAL_LINK_SEED, user.as_ref(), &[ctx.bumps.referral_link]],
+                )?;
+                referral_link = Some(ReferralLink {
+                    version: ReferralLink::VERSION,
+                    user,
+                    referrer,
+                    created_at: now,
+                    vg_earned: 0,
+                    bump: ctx.bumps.referral_link,
+                });
+            }
+            (None, None) => {}
+        }
+        let referral_bps = match referrer {
+            Some(_) => ctx.accounts.referral_config.as_ref()
+                .ok_or(BurnAndEarnError::ReferralNotConfigured)?
+                .referral_bps,
+            None => 0,
+        };
+
+        // 6. Рассчитываем количество VG токенов для эмиссии по формуле с учетом затухания,
+        // бюджета эпохи и остатка эскроу. Доля реферера входит в ограничения вместе с
+        // эмиссией пользователя: при урезании обе части уменьшаются пропорционально.
+        let epoch = clock.epoch;
+        let formula_vg_amount = calculate_vg_emission(user_record.locked_lp, previous_lp)?;
+        let gross_vg_amount = bps_of(formula_vg_amount, referral_bps)
+            .and_then(|reward| formula_vg_amount.checked_add(reward))
+            .ok_or(BurnAndEarnError::MathOverflow)?;
+        let total_vg_amount = apply_emission_caps(burn_and_earn_state, gross_vg_amount, epoch)?;
+        let new_vg_amount = mul_div(total_vg_amount, BPS_DENOMINATOR, BPS_DENOMINATOR + referral_bps as u64)
+            .ok_or(BurnAndEarnError::MathOverflow)?;
+        let reward = total_vg_amount - new_vg_amount;
+        msg!("Эмиссия {} VG токенов (по формуле {}), рефереру {}", new_vg_amount, formula_vg_amount, reward);
+        if new_vg_amount < min_vg_out {
+            msg!("Эмиссия {} VG меньше минимальной {}", new_vg_amount, min_vg_out);
+            return err!(BurnAndEarnError::VgSlippageExceeded);
+        }
 
-        // 6. Эмитируем VG токены для пользователя через CPI
+        // 7. Эмитируем VG токены для пользователя через CPI
         // TODO: Реализовать эмиссию VG токенов
 
-        // 7. Обновляем статистику эмиссии
+        // Обновляем статистику эмиссии: из эскроу уходит эмиссия пользователя и доля реферера
         user_record.vg_minted = user_record.vg_minted.checked_add(new_vg_amount).ok_or(BurnAndEarnError::MathOverflow)?;
-        burn_and_earn_state.total_vg_minted = burn_and_earn_state.total_vg_minted.checked_add(new_vg_amount).ok_or(BurnAndEarnError::MathOverflow)?;
+        burn_and_earn_state.total_vg_minted = burn_and_earn_state.total_vg_minted.checked_add(total_vg_amount).ok_or(BurnAndEarnError::MathOverflow)?;
+        burn_and_earn_state.escrow_remaining = burn_and_earn_state.escrow_remaining.checked_sub(total_vg_amount).ok_or(BurnAndEarnError::MathOverflow)?;
+        if burn_and_earn_state.current_epoch != epoch {
+            burn_and_earn_state.current_epoch = epoch;
+            burn_and_earn_state.epoch_vg_emitted = 0;
+        }
+        burn_and_earn_state.epoch_vg_emitted = burn_and_earn_state.epoch_vg_emitted.checked_add(total_vg_amount).ok_or(BurnAndEarnError::MathOverflow)?;
+
+        // Доля реферера, как и эмиссия пользователя, пока только учитывается: она списана
+        // с escrow_remaining и будет выплачена вместе с эмиссией VG из эскроу
+        if let Some(referrer) = referrer {
+            let link = referral_link.as_mut().ok_or(BurnAndEarnError::InvalidReferrer)?;
+            link.vg_earned = link.vg_earned.checked_add(reward).ok_or(BurnAndEarnError::MathOverflow)?;
+            link.try_serialize(&mut &mut link_info.try_borrow_mut_data()?[..])?;
+
+            let stats = ctx.accounts.referrer_stats.as_deref_mut().ok_or(BurnAndEarnError::InvalidReferrer)?;
+            if stats.referrer == Pubkey::default() {
+                stats.version = ReferrerStats::VERSION;
+                stats.referrer = referrer;
+                stats.bump = ctx.bumps.referrer_stats.ok_or(BurnAndEarnError::InvalidReferrer)?;
+            }
+            if link_created {
+                stats.referred_users = stats.referred_users.checked_add(1).ok_or(BurnAndEarnError::MathOverflow)?;
+            }
+            stats.total_vc_referred = stats.total_vc_referred.checked_add(vc_amount).ok_or(BurnAndEarnError::MathOverflow)?;
+            stats.total_vg_earned = stats.total_vg_earned.checked_add(reward).ok_or(BurnAndEarnError::MathOverflow)?;
+
+            emit!(ReferralRewarded {
+                user,
+                referrer,
+                vc_amount,
+                vg_reward: reward,
+            });
+            msg!("Рефереру {} начислено {} VG", referrer, reward);
+        }
 
         // 8. Проверяем нужно ли создавать/обновлять NFT Fee Key
         let nft_level = determine_nft_level(user_record.locked_lp);
         if nft_level > user_record.nft_level {
             user_record.nft_level = nft_level;
-            msg!("Пользователь получает NFT Fee Key уровня {}", nft_level);
+            msg!("Пользователь получает NFT Fee Key уровня {}", nft_level.display_name());
             // TODO: Создаем NFT Fee Key соответствующего уровня
         }
 
@@ -115,6 +295,270 @@ pub mod burn_and_earn {
         Ok(())
     }
 
+    /// Предварительный расчет burn_and_lock (только чтение): ожидаемый выход LP,
+    /// прирост VG по формуле эмиссии с учетом уже заблокированных LP пользователя
+    /// и ограничений эмиссии, итоговый уровень NFT Fee Key и остаток VG в эскроу после операции.
+    /// Результат возвращается через return data, поэтому UI может получить его симуляцией транзакции.
+    /// Rate limits и аварийная пауза не учитываются: котировка может пройти там, где burn_and_lock
+    /// будет отклонен ошибкой лимита (TxVcLimitExceeded и др.) или ProgramPaused.
+    /// Доля реферера тоже не учитывается: у приглашенного пользователя `vg_amount` - верхняя оценка.
+    pub fn quote_burn_and_lock(ctx: Context<QuoteBurnAndLock>, vc_amount: u64) -> Result<BurnAndLockQuote> {
+        if vc_amount == 0 {
+            return err!(BurnAndEarnError::InvalidAmount);
+        }
+
+        // Запись пользователя может еще не существовать: тогда считаем от нуля
+        let (previous_lp, current_tier) = match &ctx.accounts.user_lp_record {
+            Some(record) => {
+                if record.version != UserLpRecord::VERSION
+                    || record.to_account_info().data_len() != 8 + UserLpRecord::INIT_SPACE
+                {
+                    return err!(BurnAndEarnError::AccountNeedsMigration);
+                }
+                (record.locked_lp, record.nft_level)
+            }
+            None => (0, FeeKeyTier::None),
+        };
+
+        let lp_amount = simulate_vc_to_lp_conversion(vc_amount)?;
+        let new_locked_lp = previous_lp.checked_add(lp_amount).ok_or(BurnAndEarnError::MathOverflow)?;
+        let vg_amount = apply_emission_caps(
+            &ctx.accounts.burn_and_earn_state,
+            calculate_vg_emission(new_locked_lp, previous_lp)?,
+            Clock::get()?.epoch,
+        )?;
+        let resulting_tier = determine_nft_level(new_locked_lp).max(current_tier);
+        let escrow_remaining = ctx
+            .accounts
+            .burn_and_earn_state
+            .escrow_remaining
+            .checked_sub(vg_amount)
+            .ok_or(BurnAndEarnError::MathOverflow)?;
+
+        Ok(BurnAndLockQuote {
+            vc_amount,
+            lp_amount,
+            vg_amount,
+            current_tier,
+            resulting_tier,
+            escrow_remaining,
+        })
+    }
+
+    /// Обновляет ограничения эмиссии VG: бюджет на эпоху (0 - без ограничения),
+    /// силу затухания по total_vg_minted и поведение при превышении лимита.
+    /// Только администратор программы.
+    pub fn update_emission_config(
+        ctx: Context<UpdateEmissionConfig>,
+        epoch_vg_budget: u64,
+        emission_decay_bps: u16,
+        pro_rate_over_cap: bool,
+    ) -> Result<()> {
+        if !is_valid_bps(emission_decay_bps) {
+            return err!(BurnAndEarnError::InvalidEmissionConfig);
+        }
+
+        let state = &mut ctx.accounts.burn_and_earn_state;
+        state.epoch_vg_budget = epoch_vg_budget;
+        state.emission_decay_bps = emission_decay_bps;
+        state.pro_rate_over_cap = pro_rate_over_cap;
+
+        msg!(
+            "Ограничения эмиссии: бюджет эпохи {}, затухание {} bps, урезание при превышении: {}",
+            epoch_vg_budget,
+            emission_decay_bps,
+            pro_rate_over_cap
+        );
+        Ok(())
+    }
+
+    /// Создает или обновляет лимиты скорости burn_and_lock (0 отключает лимит).
+    /// Только авторитет DAO из реестра экосистемы.
+    pub fn upsert_rate_limit_config(ctx: Context<UpsertRateLimitConfig>, params: RateLimitParams) -> Result<()> {
+        let rate_limit_config = &mut ctx.accounts.rate_limit_config;
+        if rate_limit_config.version == 0 {
+            rate_limit_config.version = RateLimitConfig::VERSION;
+            rate_limit_config.bump = ctx.bumps.rate_limit_config;
+        } else if rate_limit_config.version != RateLimitConfig::VERSION {
+            return err!(BurnAndEarnError::AccountNeedsMigration);
+        }
+        rate_limit_config.params = params;
+
+        msg!(
+            "Лимиты скорости: {} VC за транзакцию, {} VC на пользователя за эпоху, {} VC за слот",
+            params.max_vc_per_tx,
+            params.max_vc_per_user_per_epoch,
+            params.max_vc_per_slot
+        );
+        Ok(())
+    }
+
+    /// Устанавливает долю эмиссии VG (в bps), которую получает реферер.
+    /// Только авторитет DAO из реестра экосистемы.
+    pub fn upsert_referral_config(ctx: Context<UpsertReferralConfig>, referral_bps: u16) -> Result<()> {
+        if referral_bps > MAX_REFERRAL_BPS {
+            return err!(BurnAndEarnError::InvalidReferralConfig);
+        }
+
+        let referral_config = &mut ctx.accounts.referral_config;
+        if referral_config.version == 0 {
+            referral_config.version = ReferralConfig::VERSION;
+            referral_config.bump = ctx.bumps.referral_config;
+        } else if referral_config.version != ReferralConfig::VERSION {
+            return err!(BurnAndEarnError::AccountNeedsMigration);
+        }
+        referral_config.referral_bps = referral_bps;
+
+        msg!("Реферальное вознаграждение: {} bps эмиссии VG", referral_bps);
+        Ok(())
+    }
+
+    /// Статистика реферера (только чтение): число приглашенных, их объем VC
+    /// и полученные VG. Результат возвращается через return data.
+    pub fn get_referral_stats(ctx: Context<GetReferralStats>, referrer: Pubkey) -> Result<ReferralStatsView> {
+        let referral_bps = ctx.accounts.referral_config.as_ref().map_or(0, |config| config.referral_bps);
+        Ok(match &ctx.accounts.referrer_stats {
+            Some(stats) => ReferralStatsView {
+                referrer,
+                referred_users: stats.referred_users,
+                total_vc_referred: stats.total_vc_referred,
+                total_vg_earned: stats.total_vg_earned,
+                referral_bps,
+            },
+            None => ReferralStatsView {
+                referrer,
+                referred_users: 0,
+                total_vc_referred: 0,
+                total_vg_earned: 0,
+                referral_bps,
+            },
+        })
+    }
+
+    /// Настраивает распределитель комиссий LP: программу AMM-адаптера, пул и позицию
+    /// PermanentLockVault, из которых собираются комиссии, и минт комиссий.
+    /// Только администратор программы.
+    pub fn initialize_fee_distributor(
+        ctx: Context<InitializeFeeDistributor>,
+        amm_program: Pubkey,
+        amm_pool: Pubkey,
+        amm_position: Pubkey,
+    ) -> Result<()> {
+        let fee_distributor = &mut ctx.accounts.fee_distributor;
+        fee_distributor.version = FeeDistributor::VERSION;
+        fee_distributor.amm_program = amm_program;
+        fee_distributor.amm_pool = amm_pool;
+        fee_distributor.amm_position = amm_position;
+        fee_distributor.fee_mint = ctx.accounts.fee_mint.key();
+        fee_distributor.vault = ctx.accounts.distributor_vault.key();
+        fee_distributor.bump = ctx.bumps.fee_distributor;
+
+        msg!("Распределитель комиссий LP настроен: AMM {}, пул {}", amm_program, amm_pool);
+        Ok(())
+    }
+
+    /// Permissionless-крэнк: собирает торговые комиссии позиции PermanentLockVault
+    /// через AMM-адаптер в хранилище распределителя и увеличивает накопленные
+    /// комиссии на единицу заблокированного LP.
+    pub fn harvest_lp_fees(ctx: Context<HarvestLpFees>) -> Result<()> {
+        let total_locked_lp = ctx.accounts.burn_and_earn_state.total_locked_lp;
+        if total_locked_lp == 0 {
+            return err!(BurnAndEarnError::NoLockedLiquidity);
+        }
+
+        let balance_before = ctx.accounts.distributor_vault.amount;
+        let ix = amm_collect_fees_instruction(
+            ctx.accounts.amm_program.key(),
+            &AmmCollectFeesAccounts {
+                pool: ctx.accounts.amm_pool.key(),
+                position: ctx.accounts.amm_position.key(),
+                position_owner: ctx.accounts.permanent_lock_vault.key(),
+                fee_vault: ctx.accounts.amm_fee_vault.key(),
+                fee_mint: ctx.accounts.fee_mint.key(),
+                destination: ctx.accounts.distributor_vault.key(),
+                token_program: ctx.accounts.token_program.key(),
+            },
+        );
+        let vault_seeds = &[PERMANENT_LOCK_VAULT_SEED, &[ctx.bumps.permanent_lock_vault]];
+        invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.amm_pool.to_account_info(),
+                ctx.accounts.amm_position.to_account_info(),
+                ctx.accounts.permanent_lock_vault.to_account_info(),
+                ctx.accounts.amm_fee_vault.to_account_info(),
+                ctx.accounts.fee_mint.to_account_info(),
+                ctx.accounts.distributor_vault.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.amm_program.to_account_info(),
+            ],
+            &[&vault_seeds[..]],
+        )?;
+
+        // Фактически полученная сумма считается по балансу хранилища
+        ctx.accounts.distributor_vault.reload()?;
+        let harvested = ctx.accounts.distributor_vault.amount
+            .checked_sub(balance_before)
+            .ok_or(BurnAndEarnError::MathOverflow)?;
+        if harvested == 0 {
+            return err!(BurnAndEarnError::NothingToHarvest);
+        }
+
+        let state = &mut ctx.accounts.burn_and_earn_state;
+        let increment = (harvested as u128)
+            .checked_mul(ACC_FEE_PRECISION)
+            .and_then(|value| value.checked_div(total_locked_lp as u128))
+            .ok_or(BurnAndEarnError::MathOverflow)?;
+        state.acc_fee_per_lp = state.acc_fee_per_lp.checked_add(increment).ok_or(BurnAndEarnError::MathOverflow)?;
+        state.total_lp_fees_harvested = state.total_lp_fees_harvested.checked_add(harvested).ok_or(BurnAndEarnError::MathOverflow)?;
+
+        emit!(LpFeesHarvested {
+            amount: harvested,
+            total_locked_lp,
+            acc_fee_per_lp: state.acc_fee_per_lp,
+            harvester: ctx.accounts.harvester.key(),
+        });
+        msg!("Собрано {} комиссий LP на {} заблокированных LP", harvested, total_locked_lp);
+        Ok(())
+    }
+
+    /// Выплачивает пользователю его долю собранных комиссий LP, пропорциональную locked_lp.
+    pub fn claim_lp_fees(ctx: Context<ClaimLpFees>) -> Result<()> {
+        let acc_fee_per_lp = ctx.accounts.burn_and_earn_state.acc_fee_per_lp;
+        let user_record = &mut ctx.accounts.user_lp_record;
+        settle_lp_fees(user_record, acc_fee_per_lp)?;
+
+        let amount = user_record.fees_owed;
+        if amount == 0 {
+            return err!(BurnAndEarnError::NothingToClaim);
+        }
+        user_record.fees_owed = 0;
+
+        let distributor_seeds = &[FEE_DISTRIBUTOR_SEED, &[ctx.accounts.fee_distributor.bump]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.distributor_vault.to_account_info(),
+                    mint: ctx.accounts.fee_mint.to_account_info(),
+                    to: ctx.accounts.user_fee_token_account.to_account_info(),
+                    authority: ctx.accounts.fee_distributor.to_account_info(),
+                },
+                &[&distributor_seeds[..]],
+            ),
+            amount,
+            ctx.accounts.fee_mint.decimals,
+        )?;
+
+        emit!(LpFeesClaimed {
+            user: ctx.accounts.user.key(),
+            amount,
+            locked_lp: user_record.locked_lp,
+        });
+        msg!("Выплачено {} комиссий LP", amount);
+        Ok(())
+    }
+
     /// Возвращает статистику по заблокированным LP и эмитированным VG токенам
     pub fn get_statistics(ctx: Context<GetStatistics>) -> Result<()> {
         let state = &ctx.accounts.burn_and_earn_state;
@@ -124,14 +568,18 @@ pub mod burn_and_earn {
         msg!("Total Locked LP: {}", state.total_locked_lp);
         msg!("Total VG Minted: {}", state.total_vg_minted);
         msg!("Total VC Burned: {}", state.total_vc_burned);
+        msg!("Escrow Remaining: {}", state.escrow_remaining);
+        msg!("Epoch {} VG Emitted: {} / {}", state.current_epoch, state.epoch_vg_emitted, state.epoch_vg_budget);
+        msg!("Total LP Fees Harvested: {}", state.total_lp_fees_harvested);
         
         if user_record.is_initialized {
             msg!("User Statistics:");
             msg!("User Locked LP: {}", user_record.locked_lp);
             msg!("User VG Minted: {}", user_record.vg_minted);
             msg!("User VC Burned: {}", user_record.vc_burned);
-            msg!("User NFT Level: {}", user_record.nft_level);
+            msg!("User NFT Level: {}", user_record.nft_level.display_name());
             msg!("Last Update: {}", user_record.last_update);
+            msg!("Unclaimed LP Fees: {}", pending_lp_fees(user_record, state.acc_fee_per_lp)?);
         } else {
             msg!("User has no locked LP tokens");
         }
@@ -144,15 +592,154 @@ pub mod burn_and_earn {
         let user_record = &ctx.accounts.user_lp_record;
         
         // Проверяем, что пользователь имеет право на NFT
-        if !user_record.is_initialized || user_record.nft_level == 0 {
+        if !user_record.is_initialized || user_record.nft_level == FeeKeyTier::None {
             return err!(BurnAndEarnError::NoEligibleNft);
         }
         
         // TODO: Реализовать создание NFT Fee Key через Metaplex
+        // с URI из user_record.nft_level.metadata_uri_template()
         
-        msg!("NFT Fee Key уровня {} успешно создан", user_record.nft_level);
+        msg!("NFT Fee Key уровня {} успешно создан", user_record.nft_level.display_name());
+        Ok(())
+    }
+
+    /// Переводит BurnAndEarnState в текущую версию раскладки.
+    /// Только администратор программы.
+    pub fn migrate_burn_and_earn_state(ctx: Context<MigrateBurnAndEarnState>) -> Result<()> {
+        let state_info = ctx.accounts.burn_and_earn_state.to_account_info();
+
+        let migrated = {
+            let data = state_info.try_borrow_data()?;
+            if !data.starts_with(BurnAndEarnState::DISCRIMINATOR) {
+                return err!(BurnAndEarnError::AccountNeedsMigration);
+            }
+            if data.len() == BurnAndEarnStateV0::LEN {
+                // Раскладка без байта версии
+                BurnAndEarnStateV0::deserialize(&mut &data[8..])?.upgrade()
+            } else {
+                let state = BurnAndEarnState::try_deserialize(&mut &data[..])?;
+                if state.version == BurnAndEarnState::VERSION {
+                    return err!(BurnAndEarnError::AlreadyMigrated);
+                }
+                return err!(BurnAndEarnError::UnsupportedAccountVersion);
+            }
+        };
+
+        if migrated.authority != ctx.accounts.authority.key() {
+            return err!(BurnAndEarnError::Unauthorized);
+        }
+
+        write_migrated(
+            &state_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + BurnAndEarnState::INIT_SPACE,
+            &migrated,
+        )?;
+
+        msg!("BurnAndEarnState мигрирован до версии {}", BurnAndEarnState::VERSION);
         Ok(())
     }
+
+    /// Переводит запись пользователя UserLpRecord в текущую версию раскладки.
+    /// Может вызвать любой плательщик: миграция не меняет данных пользователя.
+    pub fn migrate_user_lp_record(ctx: Context<MigrateUserLpRecord>, _owner: Pubkey) -> Result<()> {
+        let record_info = ctx.accounts.user_lp_record.to_account_info();
+
+        let migrated = {
+            let data = record_info.try_borrow_data()?;
+            if !data.starts_with(UserLpRecord::DISCRIMINATOR) {
+                return err!(BurnAndEarnError::AccountNeedsMigration);
+            }
+            if data.len() == UserLpRecordV0::LEN {
+                // Раскладка без байта версии, уровень NFT хранился как u8
+                UserLpRecordV0::deserialize(&mut &data[8..])?.upgrade()
+            } else {
+                let record = UserLpRecord::try_deserialize(&mut &data[..])?;
+                if record.version == UserLpRecord::VERSION {
+                    return err!(BurnAndEarnError::AlreadyMigrated);
+                }
+                return err!(BurnAndEarnError::UnsupportedAccountVersion);
+            }
+        };
+
+        write_migrated(
+            &record_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + UserLpRecord::INIT_SPACE,
+            &migrated,
+        )?;
+
+        msg!("UserLpRecord {} мигрирован до версии {}", migrated.owner, UserLpRecord::VERSION);
+        Ok(())
+    }
+}
+
+/// Меняет размер аккаунта под новую раскладку и записывает мигрированные данные.
+/// Хвост аккаунта обнуляется, чтобы будущие поля читались как значения по умолчанию.
+fn write_migrated<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+    migrated: &T,
+) -> Result<()> {
+    resize_account(account, payer, system_program, new_len)?;
+    let mut data = account.try_borrow_mut_data()?;
+    data.fill(0);
+    let mut writer: &mut [u8] = &mut data[..];
+    migrated.try_serialize(&mut writer)
+}
+
+/// Создает аккаунт ReferralLink по PDA связи за счет пользователя. Как и `init` в Anchor,
+/// учитывает лампорты, уже переведенные на адрес PDA.
+fn create_referral_link<'info>(
+    link: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    link_seeds: &[&[u8]],
+) -> Result<()> {
+    let space = 8 + ReferralLink::INIT_SPACE;
+    let required = Rent::get()?.minimum_balance(space);
+    if link.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount { from: payer.clone(), to: link.clone() },
+                &[link_seeds],
+            ),
+            required,
+            space as u64,
+            &crate::ID,
+        );
+    }
+    let top_up = required.saturating_sub(link.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer { from: payer.clone(), to: link.clone() },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate { account_to_allocate: link.clone() },
+            &[link_seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign { account_to_assign: link.clone() },
+            &[link_seeds],
+        ),
+        &crate::ID,
+    )
 }
 
 /// Симулирует конвертацию VC токенов в LP токены
@@ -173,8 +760,8 @@ fn simulate_vc_to_lp_conversion(vc_amount: u64) -> Result<u64> {
 /// VG = LP * C * (1 + B * log10(LP/LP_min))
 fn calculate_vg_emission(new_locked_lp: u64, previous_locked_lp: u64) -> Result<u64> {
     // Преобразуем в f64 для расчетов
-    let new_lp_f64 = new_locked_lp as f64 / 10u64.pow(DECIMALS as u32) as f64;
-    let prev_lp_f64 = previous_locked_lp as f64 / 10u64.pow(DECIMALS as u32) as f64;
+    let new_lp_f64 = new_locked_lp as f64 / ONE_TOKEN as f64;
+    let prev_lp_f64 = previous_locked_lp as f64 / ONE_TOKEN as f64;
 
     // Рассчитываем эмиссию для обоих значений LP
     let new_emission = if new_lp_f64 >= MIN_LP_AMOUNT {
@@ -195,24 +782,115 @@ fn calculate_vg_emission(new_locked_lp: u64, previous_locked_lp: u64) -> Result<
     let vg_difference = new_emission - prev_emission;
     
     // Преобразуем обратно в u64 с учетом десятичных знаков
-    let vg_amount = (vg_difference * 10u64.pow(DECIMALS as u32) as f64) as u64;
+    let vg_amount = (vg_difference * ONE_TOKEN as f64) as u64;
     
     Ok(vg_amount)
 }
 
-/// Определяет уровень NFT Fee Key на основе количества заблокированных LP
-fn determine_nft_level(locked_lp: u64) -> u8 {
-    if locked_lp >= PLATINUM_LEVEL_THRESHOLD {
-        4 // Platinum
-    } else if locked_lp >= GOLD_LEVEL_THRESHOLD {
-        3 // Gold
-    } else if locked_lp >= SILVER_LEVEL_THRESHOLD {
-        2 // Silver
-    } else if locked_lp >= BRONZE_LEVEL_THRESHOLD {
-        1 // Bronze
+/// Применяет ограничения эмиссии к приросту VG по формуле вместе с долей реферера:
+/// 1. затухание: множитель `1 - decay_bps / 10000 * total_vg_minted / VG_TOTAL_SUPPLY`;
+/// 2. бюджет текущей эпохи и остаток эскроу.
+///
+/// Если сумма превышает доступное, она урезается до остатка (`pro_rate_over_cap`)
+/// либо операция отклоняется с `EmissionCapReached`.
+fn apply_emission_caps(state: &BurnAndEarnState, formula_vg_amount: u64, epoch: u64) -> Result<u64> {
+    let decay_reduction_bps = mul_div(
+        state.emission_decay_bps as u64,
+        state.total_vg_minted,
+        VG_TOTAL_SUPPLY,
+    )
+    .ok_or(BurnAndEarnError::MathOverflow)?
+    .min(BPS_DENOMINATOR);
+    let decayed_vg_amount = mul_div(
+        formula_vg_amount,
+        BPS_DENOMINATOR - decay_reduction_bps,
+        BPS_DENOMINATOR,
+    )
+    .ok_or(BurnAndEarnError::MathOverflow)?;
+
+    // Счетчик эпохи сбрасывается при первой операции в новой эпохе
+    let epoch_emitted = if epoch == state.current_epoch { state.epoch_vg_emitted } else { 0 };
+    let epoch_available = if state.epoch_vg_budget == 0 {
+        u64::MAX
     } else {
-        0 // No NFT
+        state.epoch_vg_budget.saturating_sub(epoch_emitted)
+    };
+    let available = epoch_available.min(state.escrow_remaining);
+
+    if decayed_vg_amount <= available {
+        return Ok(decayed_vg_amount);
+    }
+    if !state.pro_rate_over_cap || available == 0 {
+        msg!("Эмиссия {} VG превышает доступные {} VG", decayed_vg_amount, available);
+        return err!(BurnAndEarnError::EmissionCapReached);
+    }
+    msg!("Эмиссия урезана с {} до {} VG по лимиту", decayed_vg_amount, available);
+    Ok(available)
+}
+
+/// Проверяет лимиты скорости и учитывает сумму в счетчиках. Счетчики пользователя
+/// сбрасываются с новой эпохой, глобальный счетчик - с новым слотом.
+fn enforce_rate_limits(
+    config: &mut RateLimitConfig,
+    user_record: &mut UserLpRecord,
+    vc_amount: u64,
+    epoch: u64,
+    slot: u64,
+) -> Result<()> {
+    let params = config.params;
+    if params.max_vc_per_tx != 0 && vc_amount > params.max_vc_per_tx {
+        return err!(BurnAndEarnError::TxVcLimitExceeded);
+    }
+
+    if user_record.vc_epoch != epoch {
+        user_record.vc_epoch = epoch;
+        user_record.epoch_vc_burned = 0;
+    }
+    let user_epoch_vc = user_record.epoch_vc_burned.checked_add(vc_amount).ok_or(BurnAndEarnError::MathOverflow)?;
+    if params.max_vc_per_user_per_epoch != 0 && user_epoch_vc > params.max_vc_per_user_per_epoch {
+        return err!(BurnAndEarnError::UserEpochVcLimitExceeded);
     }
+
+    if config.current_slot != slot {
+        config.current_slot = slot;
+        config.slot_vc_burned = 0;
+    }
+    let slot_vc = config.slot_vc_burned.checked_add(vc_amount).ok_or(BurnAndEarnError::MathOverflow)?;
+    if params.max_vc_per_slot != 0 && slot_vc > params.max_vc_per_slot {
+        return err!(BurnAndEarnError::SlotVcLimitExceeded);
+    }
+
+    user_record.epoch_vc_burned = user_epoch_vc;
+    config.slot_vc_burned = slot_vc;
+    Ok(())
+}
+
+/// Комиссии, причитающиеся на `locked_lp` при накопителе `acc_fee_per_lp`
+fn accumulated_lp_fees(locked_lp: u64, acc_fee_per_lp: u128) -> Result<u128> {
+    (locked_lp as u128)
+        .checked_mul(acc_fee_per_lp)
+        .map(|value| value / ACC_FEE_PRECISION)
+        .ok_or(error!(BurnAndEarnError::MathOverflow))
+}
+
+/// Начисленные, но еще не зафиксированные в fees_owed комиссии пользователя
+fn pending_lp_fees(user_record: &UserLpRecord, acc_fee_per_lp: u128) -> Result<u64> {
+    let pending = accumulated_lp_fees(user_record.locked_lp, acc_fee_per_lp)?
+        .saturating_sub(user_record.fee_debt);
+    u64::try_from(pending).map_err(|_| error!(BurnAndEarnError::MathOverflow))
+}
+
+/// Переносит начисленные комиссии в fees_owed и обновляет долг записи
+fn settle_lp_fees(user_record: &mut UserLpRecord, acc_fee_per_lp: u128) -> Result<()> {
+    let pending = pending_lp_fees(user_record, acc_fee_per_lp)?;
+    user_record.fees_owed = user_record.fees_owed.checked_add(pending).ok_or(BurnAndEarnError::MathOverflow)?;
+    user_record.fee_debt = accumulated_lp_fees(user_record.locked_lp, acc_fee_per_lp)?;
+    Ok(())
+}
+
+/// Определяет уровень NFT Fee Key на основе количества заблокированных LP
+fn determine_nft_level(locked_lp: u64) -> FeeKeyTier {
+    FeeKeyTier::from_locked_lp(locked_lp)
 }
 
 #[derive(Accounts)]
@@ -223,43 +901,84 @@ pub struct Initialize<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + BurnAndEarnState::SPACE,
-        seeds = [b"burn_and_earn_state"],
+        space = 8 + BurnAndEarnState::INIT_SPACE,
+        seeds = [BURN_AND_EARN_STATE_SEED],
         bump
     )]
     pub burn_and_earn_state: Account<'info, BurnAndEarnState>,
 
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RateLimitConfig::INIT_SPACE,
+        seeds = [RATE_LIMIT_CONFIG_SEED],
+        bump
+    )]
+    pub rate_limit_config: Account<'info, RateLimitConfig>,
+
+    /// Реестр экосистемы
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
     /// Минт VC токена
+    #[account(address = registry.vc_mint @ BurnAndEarnError::InvalidMint)]
     pub vc_mint: InterfaceAccount<'info, Mint>,
     
     /// Минт VG токена
+    #[account(address = registry.vg_mint @ BurnAndEarnError::InvalidMint)]
     pub vg_mint: InterfaceAccount<'info, Mint>,
     
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
+#[instruction(vc_amount: u64, min_lp_out: u64, min_vg_out: u64, deadline: i64, referrer: Option<Pubkey>)]
 pub struct BurnAndLock<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
     #[account(
         mut,
-        seeds = [b"burn_and_earn_state"],
-        bump = burn_and_earn_state.bump
+        seeds = [BURN_AND_EARN_STATE_SEED],
+        bump = burn_and_earn_state.bump,
+        constraint = burn_and_earn_state.version == BurnAndEarnState::VERSION @ BurnAndEarnError::AccountNeedsMigration,
+        constraint = burn_and_earn_state.to_account_info().data_len() == 8 + BurnAndEarnState::INIT_SPACE @ BurnAndEarnError::AccountNeedsMigration
     )]
     pub burn_and_earn_state: Account<'info, BurnAndEarnState>,
+
+    /// Реестр экосистемы: канонические адреса и состояние аварийной паузы
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ BurnAndEarnError::ProgramPaused,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    /// Лимиты скорости и глобальный счетчик слота
+    #[account(
+        mut,
+        seeds = [RATE_LIMIT_CONFIG_SEED],
+        bump = rate_limit_config.bump,
+        constraint = rate_limit_config.version == RateLimitConfig::VERSION @ BurnAndEarnError::AccountNeedsMigration
+    )]
+    pub rate_limit_config: Account<'info, RateLimitConfig>,
     
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + UserLpRecord::SPACE,
-        seeds = [b"user_lp_record", user.key().as_ref()],
+        space = 8 + UserLpRecord::INIT_SPACE,
+        seeds = [USER_LP_RECORD_SEED, user.key().as_ref()],
         bump
     )]
     pub user_lp_record: Account<'info, UserLpRecord>,
     
     /// Минт VC токена
+    #[account(address = registry.vc_mint @ BurnAndEarnError::InvalidMint)]
     pub vc_mint: InterfaceAccount<'info, Mint>,
     
     /// Токен-аккаунт пользователя для VC
@@ -271,6 +990,7 @@ pub struct BurnAndLock<'info> {
     pub user_vc_token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// Минт VG токена
+    #[account(address = registry.vg_mint @ BurnAndEarnError::InvalidMint)]
     pub vg_mint: InterfaceAccount<'info, Mint>,
     
     /// Токен-аккаунт пользователя для VG
@@ -284,12 +1004,12 @@ pub struct BurnAndLock<'info> {
     
     /// Программа VC токена
     /// CHECK: Проверяется через ограничения и CPI
-    #[account(address = VC_TOKEN_ID)]
+    #[account(address = registry.vc_token_program)]
     pub vc_token_program: AccountInfo<'info>,
     
     /// Программа VG токена
     /// CHECK: Проверяется через ограничения и CPI
-    #[account(address = VG_TOKEN_ID)]
+    #[account(address = registry.vg_token_program)]
     pub vg_token_program: AccountInfo<'info>,
     
     pub token_program: Interface<'info, TokenInterface>,
@@ -297,6 +1017,323 @@ pub struct BurnAndLock<'info> {
     pub system_program: Program<'info, System>,
     /// CHECK: Для получения времени
     pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: Связь пользователя с реферером (ReferralLink); создается в инструкции только
+    /// при записи реферера и передается всегда, поэтому записанного реферера нельзя обойти
+    #[account(
+        mut,
+        seeds = [REFERRAL_LINK_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub referral_link: UncheckedAccount<'info>,
+
+    /// Доля реферера, задаваемая DAO; обязательна вместе с referrer
+    #[account(
+        seeds = [REFERRAL_CONFIG_SEED],
+        bump = referral_config.bump,
+        constraint = referral_config.version == ReferralConfig::VERSION @ BurnAndEarnError::AccountNeedsMigration
+    )]
+    pub referral_config: Option<Box<Account<'info, ReferralConfig>>>,
+
+    /// Запись реферера: нужна только при создании связи
+    #[account(
+        seeds = [USER_LP_RECORD_SEED, referrer.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub referrer_lp_record: Option<Box<Account<'info, UserLpRecord>>>,
+
+    /// Статистика реферера; обязательна вместе с referrer
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + ReferrerStats::INIT_SPACE,
+        seeds = [REFERRER_STATS_SEED, referrer.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub referrer_stats: Option<Box<Account<'info, ReferrerStats>>>,
+}
+
+#[derive(Accounts)]
+pub struct UpsertReferralConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Авторитет DAO из реестра экосистемы
+    #[account(address = registry.authority @ BurnAndEarnError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+
+    /// Реестр экосистемы
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReferralConfig::INIT_SPACE,
+        seeds = [REFERRAL_CONFIG_SEED],
+        bump
+    )]
+    pub referral_config: Account<'info, ReferralConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(referrer: Pubkey)]
+pub struct GetReferralStats<'info> {
+    /// Статистика реферера; не передается, если у него еще нет приглашенных
+    #[account(
+        seeds = [REFERRER_STATS_SEED, referrer.as_ref()],
+        bump = referrer_stats.bump,
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    #[account(seeds = [REFERRAL_CONFIG_SEED], bump = referral_config.bump)]
+    pub referral_config: Option<Account<'info, ReferralConfig>>,
+}
+
+/// Статистика реферера, возвращаемая через return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReferralStatsView {
+    pub referrer: Pubkey,
+    /// Число пользователей, записавших этого реферера
+    pub referred_users: u32,
+    /// VC, сожженные приглашенными пользователями
+    pub total_vc_referred: u64,
+    /// VG, начисленные рефереру из эскроу
+    pub total_vg_earned: u64,
+    /// Текущая доля реферера в bps эмиссии
+    pub referral_bps: u16,
+}
+
+#[event]
+pub struct ReferralRewarded {
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+    pub vc_amount: u64,
+    pub vg_reward: u64,
+}
+
+#[derive(Accounts)]
+pub struct UpdateEmissionConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [BURN_AND_EARN_STATE_SEED],
+        bump = burn_and_earn_state.bump,
+        has_one = authority @ BurnAndEarnError::Unauthorized,
+        constraint = burn_and_earn_state.version == BurnAndEarnState::VERSION @ BurnAndEarnError::AccountNeedsMigration,
+        constraint = burn_and_earn_state.to_account_info().data_len() == 8 + BurnAndEarnState::INIT_SPACE @ BurnAndEarnError::AccountNeedsMigration
+    )]
+    pub burn_and_earn_state: Account<'info, BurnAndEarnState>,
+}
+
+#[derive(Accounts)]
+pub struct UpsertRateLimitConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Авторитет DAO из реестра экосистемы
+    #[account(address = registry.authority @ BurnAndEarnError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+
+    /// Реестр экосистемы
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RateLimitConfig::INIT_SPACE,
+        seeds = [RATE_LIMIT_CONFIG_SEED],
+        bump
+    )]
+    pub rate_limit_config: Account<'info, RateLimitConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeDistributor<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [BURN_AND_EARN_STATE_SEED],
+        bump = burn_and_earn_state.bump,
+        has_one = authority @ BurnAndEarnError::Unauthorized,
+    )]
+    pub burn_and_earn_state: Account<'info, BurnAndEarnState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + FeeDistributor::INIT_SPACE,
+        seeds = [FEE_DISTRIBUTOR_SEED],
+        bump
+    )]
+    pub fee_distributor: Account<'info, FeeDistributor>,
+
+    /// Минт, в котором AMM выплачивает комиссии
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+
+    /// Хранилище распределителя для собранных комиссий
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = fee_mint,
+        associated_token::authority = fee_distributor,
+        associated_token::token_program = token_program,
+    )]
+    pub distributor_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct HarvestLpFees<'info> {
+    /// Любой участник, запускающий крэнк
+    pub harvester: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [BURN_AND_EARN_STATE_SEED],
+        bump = burn_and_earn_state.bump,
+        constraint = burn_and_earn_state.version == BurnAndEarnState::VERSION @ BurnAndEarnError::AccountNeedsMigration,
+        constraint = burn_and_earn_state.to_account_info().data_len() == 8 + BurnAndEarnState::INIT_SPACE @ BurnAndEarnError::AccountNeedsMigration
+    )]
+    pub burn_and_earn_state: Account<'info, BurnAndEarnState>,
+
+    /// Реестр экосистемы: на аварийной паузе движение комиссий останавливается
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ BurnAndEarnError::ProgramPaused,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        seeds = [FEE_DISTRIBUTOR_SEED],
+        bump = fee_distributor.bump,
+        has_one = amm_program @ BurnAndEarnError::InvalidAmmAccount,
+        has_one = amm_pool @ BurnAndEarnError::InvalidAmmAccount,
+        has_one = amm_position @ BurnAndEarnError::InvalidAmmAccount,
+        has_one = fee_mint @ BurnAndEarnError::InvalidMint,
+    )]
+    pub fee_distributor: Account<'info, FeeDistributor>,
+
+    /// CHECK: PDA без данных, владелец позиции заблокированной ликвидности; подписывает сбор
+    #[account(seeds = [PERMANENT_LOCK_VAULT_SEED], bump)]
+    pub permanent_lock_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Программа AMM-адаптера из распределителя
+    #[account(executable)]
+    pub amm_program: UncheckedAccount<'info>,
+
+    /// CHECK: Пул AMM из распределителя, проверяется адаптером
+    #[account(mut)]
+    pub amm_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Позиция PermanentLockVault в пуле, проверяется адаптером
+    #[account(mut)]
+    pub amm_position: UncheckedAccount<'info>,
+
+    /// CHECK: Хранилище комиссий пула, проверяется адаптером
+    #[account(mut)]
+    pub amm_fee_vault: UncheckedAccount<'info>,
+
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = fee_distributor.vault)]
+    pub distributor_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimLpFees<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [BURN_AND_EARN_STATE_SEED],
+        bump = burn_and_earn_state.bump,
+        constraint = burn_and_earn_state.version == BurnAndEarnState::VERSION @ BurnAndEarnError::AccountNeedsMigration,
+        constraint = burn_and_earn_state.to_account_info().data_len() == 8 + BurnAndEarnState::INIT_SPACE @ BurnAndEarnError::AccountNeedsMigration
+    )]
+    pub burn_and_earn_state: Account<'info, BurnAndEarnState>,
+
+    /// Реестр экосистемы: на аварийной паузе движение комиссий останавливается
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ BurnAndEarnError::ProgramPaused,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    /// Запись пользователя: acc_fee_per_lp делится на все заблокированные LP,
+    /// поэтому получать комиссии может любая запись, в том числе ниже порога Bronze
+    #[account(
+        mut,
+        seeds = [USER_LP_RECORD_SEED, user.key().as_ref()],
+        bump,
+        constraint = user_lp_record.version == UserLpRecord::VERSION @ BurnAndEarnError::AccountNeedsMigration,
+        constraint = user_lp_record.to_account_info().data_len() == 8 + UserLpRecord::INIT_SPACE @ BurnAndEarnError::AccountNeedsMigration
+    )]
+    pub user_lp_record: Account<'info, UserLpRecord>,
+
+    #[account(
+        seeds = [FEE_DISTRIBUTOR_SEED],
+        bump = fee_distributor.bump,
+        has_one = fee_mint @ BurnAndEarnError::InvalidMint,
+    )]
+    pub fee_distributor: Account<'info, FeeDistributor>,
+
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = fee_distributor.vault)]
+    pub distributor_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = fee_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_fee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct LpFeesHarvested {
+    pub amount: u64,
+    pub total_locked_lp: u64,
+    pub acc_fee_per_lp: u128,
+    pub harvester: Pubkey,
+}
+
+#[event]
+pub struct LpFeesClaimed {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub locked_lp: u64,
 }
 
 #[derive(Accounts)]
@@ -304,39 +1341,130 @@ pub struct GetStatistics<'info> {
     pub user: Signer<'info>,
     
     #[account(
-        seeds = [b"burn_and_earn_state"],
-        bump = burn_and_earn_state.bump
+        seeds = [BURN_AND_EARN_STATE_SEED],
+        bump = burn_and_earn_state.bump,
+        constraint = burn_and_earn_state.version == BurnAndEarnState::VERSION @ BurnAndEarnError::AccountNeedsMigration,
+        constraint = burn_and_earn_state.to_account_info().data_len() == 8 + BurnAndEarnState::INIT_SPACE @ BurnAndEarnError::AccountNeedsMigration
     )]
     pub burn_and_earn_state: Account<'info, BurnAndEarnState>,
     
     #[account(
-        seeds = [b"user_lp_record", user.key().as_ref()],
+        seeds = [USER_LP_RECORD_SEED, user.key().as_ref()],
         bump,
         constraint = user_lp_record.owner == user.key() @ BurnAndEarnError::Unauthorized
     )]
     pub user_lp_record: Account<'info, UserLpRecord>,
 }
 
+#[derive(Accounts)]
+pub struct QuoteBurnAndLock<'info> {
+    /// CHECK: Пользователь, для которого считается котировка; подпись не требуется
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [BURN_AND_EARN_STATE_SEED],
+        bump = burn_and_earn_state.bump,
+        constraint = burn_and_earn_state.version == BurnAndEarnState::VERSION @ BurnAndEarnError::AccountNeedsMigration,
+        constraint = burn_and_earn_state.to_account_info().data_len() == 8 + BurnAndEarnState::INIT_SPACE @ BurnAndEarnError::AccountNeedsMigration
+    )]
+    pub burn_and_earn_state: Account<'info, BurnAndEarnState>,
+
+    /// Запись пользователя; не передается, если пользователь еще не блокировал LP
+    #[account(
+        seeds = [USER_LP_RECORD_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_lp_record: Option<Account<'info, UserLpRecord>>,
+
+}
+
+/// Котировка burn_and_lock, возвращаемая через return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BurnAndLockQuote {
+    /// Сумма VC, для которой рассчитана котировка
+    pub vc_amount: u64,
+    /// Ожидаемый выход LP
+    pub lp_amount: u64,
+    /// Прирост эмиссии VG
+    pub vg_amount: u64,
+    /// Текущий уровень NFT Fee Key пользователя
+    pub current_tier: FeeKeyTier,
+    /// Уровень NFT Fee Key после операции
+    pub resulting_tier: FeeKeyTier,
+    /// Остаток VG в эскроу после операции
+    pub escrow_remaining: u64,
+}
+
 #[derive(Accounts)]
 pub struct CreateNftFeeKey<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
     #[account(
-        seeds = [b"user_lp_record", user.key().as_ref()],
+        seeds = [USER_LP_RECORD_SEED, user.key().as_ref()],
         bump,
         constraint = user_lp_record.owner == user.key() @ BurnAndEarnError::Unauthorized,
-        constraint = user_lp_record.nft_level > 0 @ BurnAndEarnError::NoEligibleNft
+        constraint = user_lp_record.nft_level > FeeKeyTier::None @ BurnAndEarnError::NoEligibleNft
     )]
     pub user_lp_record: Account<'info, UserLpRecord>,
+
+    /// Реестр экосистемы: канонические адреса и состояние аварийной паузы
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ BurnAndEarnError::ProgramPaused,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
     
     // TODO: Добавить аккаунты, необходимые для создания NFT через Metaplex
     
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct MigrateBurnAndEarnState<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Раскладка может быть устаревшей, поэтому аккаунт читается вручную в инструкции
+    #[account(
+        mut,
+        seeds = [BURN_AND_EARN_STATE_SEED],
+        bump,
+        owner = crate::ID,
+    )]
+    pub burn_and_earn_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct MigrateUserLpRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Раскладка может быть устаревшей, поэтому аккаунт читается вручную в инструкции
+    #[account(
+        mut,
+        seeds = [USER_LP_RECORD_SEED, owner.as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub user_lp_record: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Новые поля добавляются только в конец структур, а VERSION увеличивается
+// вместе с веткой в соответствующей инструкции migrate_*.
+
 #[account]
+#[derive(InitSpace)]
 pub struct BurnAndEarnState {
+    /// Версия раскладки аккаунта
+    pub version: u8,
     /// Администратор программы
     pub authority: Pubkey,
     /// Минт VC токена
@@ -351,21 +1479,183 @@ pub struct BurnAndEarnState {
     pub total_vc_burned: u64,
     /// Bump для PDA
     pub bump: u8,
+    /// Остаток VG в эскроу, доступный для эмиссии
+    pub escrow_remaining: u64,
+    /// Бюджет эмиссии VG на эпоху (0 - без ограничения)
+    pub epoch_vg_budget: u64,
+    /// Эпоха, к которой относится epoch_vg_emitted
+    pub current_epoch: u64,
+    /// VG, эмитированные в current_epoch
+    pub epoch_vg_emitted: u64,
+    /// Сила затухания эмиссии по total_vg_minted в bps (10000 - линейно до нуля)
+    pub emission_decay_bps: u16,
+    /// Урезать эмиссию до доступного остатка вместо ошибки EmissionCapReached
+    pub pro_rate_over_cap: bool,
+    /// Накопленные комиссии LP на единицу заблокированного LP (x ACC_FEE_PRECISION)
+    pub acc_fee_per_lp: u128,
+    /// Всего собрано комиссий LP
+    pub total_lp_fees_harvested: u64,
 }
 
 impl BurnAndEarnState {
-    pub const SPACE: usize = 32 + // authority
-                            32 + // vc_mint
-                            32 + // vg_mint
-                            8 + // total_locked_lp
-                            8 + // total_vg_minted
-                            8 + // total_vc_burned
-                            1 + // bump
-                            64; // padding
+    pub const VERSION: u8 = 1;
+}
+
+/// Раскладка BurnAndEarnState до появления байта версии (с 64 байтами резерва)
+#[derive(AnchorDeserialize)]
+pub struct BurnAndEarnStateV0 {
+    pub authority: Pubkey,
+    pub vc_mint: Pubkey,
+    pub vg_mint: Pubkey,
+    pub total_locked_lp: u64,
+    pub total_vg_minted: u64,
+    pub total_vc_burned: u64,
+    pub bump: u8,
+}
+
+impl BurnAndEarnStateV0 {
+    pub const LEN: usize = 8 + 32 * 3 + 8 * 3 + 1 + 64;
+
+    fn upgrade(self) -> BurnAndEarnState {
+        BurnAndEarnState {
+            version: BurnAndEarnState::VERSION,
+            authority: self.authority,
+            vc_mint: self.vc_mint,
+            vg_mint: self.vg_mint,
+            total_locked_lp: self.total_locked_lp,
+            total_vg_minted: self.total_vg_minted,
+            total_vc_burned: self.total_vc_burned,
+            bump: self.bump,
+            escrow_remaining: VG_TOTAL_SUPPLY.saturating_sub(self.total_vg_minted),
+            epoch_vg_budget: DEFAULT_EPOCH_VG_BUDGET,
+            current_epoch: 0,
+            epoch_vg_emitted: 0,
+            emission_decay_bps: DEFAULT_EMISSION_DECAY_BPS,
+            pro_rate_over_cap: true,
+            acc_fee_per_lp: 0,
+            total_lp_fees_harvested: 0,
+        }
+    }
+}
+
+/// Распределитель комиссий LP: откуда собираются комиссии и где они хранятся до выплаты
+#[account]
+#[derive(InitSpace)]
+pub struct FeeDistributor {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    /// Программа AMM-адаптера
+    pub amm_program: Pubkey,
+    /// Пул AMM с заблокированной ликвидностью
+    pub amm_pool: Pubkey,
+    /// Позиция PermanentLockVault в пуле
+    pub amm_position: Pubkey,
+    /// Минт комиссий
+    pub fee_mint: Pubkey,
+    /// Хранилище собранных комиссий (ATA распределителя)
+    pub vault: Pubkey,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl FeeDistributor {
+    pub const VERSION: u8 = 1;
+}
+
+/// Лимиты скорости burn_and_lock; 0 отключает соответствующий лимит
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, Default)]
+pub struct RateLimitParams {
+    /// Максимум VC за одну транзакцию
+    pub max_vc_per_tx: u64,
+    /// Максимум VC на пользователя за эпоху
+    pub max_vc_per_user_per_epoch: u64,
+    /// Максимум VC всех пользователей за слот
+    pub max_vc_per_slot: u64,
 }
 
 #[account]
+#[derive(InitSpace)]
+pub struct RateLimitConfig {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    /// Лимиты, задаваемые DAO
+    pub params: RateLimitParams,
+    /// Слот, к которому относится slot_vc_burned
+    pub current_slot: u64,
+    /// VC, сожженные всеми пользователями в current_slot
+    pub slot_vc_burned: u64,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl RateLimitConfig {
+    pub const VERSION: u8 = 1;
+}
+
+/// Настройки реферальной программы, задаваемые DAO
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralConfig {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    /// Доля эмиссии VG пользователя, начисляемая рефереру из эскроу
+    pub referral_bps: u16,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl ReferralConfig {
+    pub const VERSION: u8 = 1;
+}
+
+/// Связь пользователя с реферером; записывается один раз и не меняется
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralLink {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    /// Приглашенный пользователь
+    pub user: Pubkey,
+    /// Реферер
+    pub referrer: Pubkey,
+    /// Время записи связи
+    pub created_at: i64,
+    /// VG, начисленные рефереру за этого пользователя
+    pub vg_earned: u64,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl ReferralLink {
+    pub const VERSION: u8 = 1;
+}
+
+/// Накопленная статистика реферера
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerStats {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    pub referrer: Pubkey,
+    /// Число пользователей, записавших этого реферера
+    pub referred_users: u32,
+    /// VC, сожженные приглашенными пользователями
+    pub total_vc_referred: u64,
+    /// VG, начисленные рефереру из эскроу
+    pub total_vg_earned: u64,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl ReferrerStats {
+    pub const VERSION: u8 = 1;
+}
+
+#[account]
+#[derive(InitSpace)]
 pub struct UserLpRecord {
+    /// Версия раскладки аккаунта
+    pub version: u8,
     /// Владелец записи
     pub owner: Pubkey,
     /// Флаг инициализации
@@ -376,23 +1666,63 @@ pub struct UserLpRecord {
     pub vg_minted: u64,
     /// Количество сожженных VC токенов пользователем
     pub vc_burned: u64,
-    /// Уровень NFT Fee Key (0 = нет NFT, 1 = Bronze, 2 = Silver, 3 = Gold, 4 = Platinum)
-    pub nft_level: u8,
+    /// Уровень NFT Fee Key
+    pub nft_level: FeeKeyTier,
     /// Временная метка последнего обновления
     pub last_update: i64,
+    /// Эпоха, к которой относится epoch_vc_burned
+    pub vc_epoch: u64,
+    /// VC, сожженные пользователем в vc_epoch (для лимита на эпоху)
+    pub epoch_vc_burned: u64,
+    /// Комиссии LP, уже учтенные для текущего locked_lp (locked_lp * acc_fee_per_lp)
+    pub fee_debt: u128,
+    /// Зафиксированные, но не выплаченные комиссии LP
+    pub fees_owed: u64,
 }
 
 impl UserLpRecord {
-    pub const SPACE: usize = 32 + // owner
-                            1 + // is_initialized
-                            8 + // locked_lp
-                            8 + // vg_minted
-                            8 + // vc_burned
-                            1 + // nft_level
-                            8 + // last_update
-                            64; // padding
+    pub const VERSION: u8 = 1;
+}
+
+/// Раскладка UserLpRecord до появления байта версии (с 64 байтами резерва)
+#[derive(AnchorDeserialize)]
+pub struct UserLpRecordV0 {
+    pub owner: Pubkey,
+    pub is_initialized: bool,
+    pub locked_lp: u64,
+    pub vg_minted: u64,
+    pub vc_burned: u64,
+    pub nft_level: u8,
+    pub last_update: i64,
+}
+
+impl UserLpRecordV0 {
+    pub const LEN: usize = 8 + 32 + 1 + 8 * 3 + 1 + 8 + 64;
+
+    fn upgrade(self) -> UserLpRecord {
+        UserLpRecord {
+            version: UserLpRecord::VERSION,
+            owner: self.owner,
+            is_initialized: self.is_initialized,
+            locked_lp: self.locked_lp,
+            vg_minted: self.vg_minted,
+            vc_burned: self.vc_burned,
+            nft_level: FeeKeyTier::from_level(self.nft_level)
+                .unwrap_or_else(|| determine_nft_level(self.locked_lp)),
+            last_update: self.last_update,
+            vc_epoch: 0,
+            epoch_vc_burned: 0,
+            // Долг 0: весь объем был заблокирован до любых сборов комиссий LP
+            fee_debt: 0,
+            fees_owed: 0,
+        }
+    }
 }
 
+// Устаревшая раскладка распознается по длине, поэтому она не должна совпадать с текущей
+const _: () = assert!(BurnAndEarnStateV0::LEN != 8 + BurnAndEarnState::INIT_SPACE);
+const _: () = assert!(UserLpRecordV0::LEN != 8 + UserLpRecord::INIT_SPACE);
+
 #[error_code]
 pub enum BurnAndEarnError {
     #[msg("Операция не авторизована")]
@@ -415,4 +1745,67 @@ pub enum BurnAndEarnError {
     
     #[msg("Ошибка создания NFT")]
     NftCreationError,
+    
+    #[msg("Экосистема на аварийной паузе")]
+    ProgramPaused,
+    
+    #[msg("Минт не совпадает с адресом из реестра экосистемы")]
+    InvalidMint,
+    
+    #[msg("Аккаунт использует устаревшую раскладку, требуется миграция")]
+    AccountNeedsMigration,
+    
+    #[msg("Аккаунт уже использует текущую раскладку")]
+    AlreadyMigrated,
+    
+    #[msg("Неизвестная версия раскладки аккаунта")]
+    UnsupportedAccountVersion,
+
+    #[msg("Срок действия транзакции истек")]
+    DeadlineExceeded,
+
+    #[msg("Выход LP из пула меньше минимально допустимого")]
+    LpSlippageExceeded,
+
+    #[msg("Эмиссия VG меньше минимально допустимой")]
+    VgSlippageExceeded,
+
+    #[msg("Достигнут лимит эмиссии VG: бюджет эпохи или остаток эскроу исчерпан")]
+    EmissionCapReached,
+
+    #[msg("Неверные параметры ограничений эмиссии")]
+    InvalidEmissionConfig,
+
+    #[msg("Превышен лимит VC на одну транзакцию")]
+    TxVcLimitExceeded,
+
+    #[msg("Превышен лимит VC на пользователя за эпоху")]
+    UserEpochVcLimitExceeded,
+
+    #[msg("Превышен глобальный лимит VC за слот")]
+    SlotVcLimitExceeded,
+
+    #[msg("Нет заблокированной ликвидности для распределения комиссий")]
+    NoLockedLiquidity,
+
+    #[msg("Нет накопленных комиссий для сбора")]
+    NothingToHarvest,
+
+    #[msg("Нет комиссий для получения")]
+    NothingToClaim,
+
+    #[msg("Аккаунт AMM не совпадает с настройками распределителя")]
+    InvalidAmmAccount,
+
+    #[msg("Нельзя указать себя реферером")]
+    SelfReferral,
+
+    #[msg("Недопустимый реферер: он должен уже участвовать, а связь записывается только при первой блокировке")]
+    InvalidReferrer,
+
+    #[msg("Реферальная программа не настроена DAO")]
+    ReferralNotConfigured,
+
+    #[msg("Доля реферера превышает допустимый максимум")]
+    InvalidReferralConfig,
 } 
\ No newline at end of file
@@ -0,0 +1,342 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+declare_id!("AMMfWTjmKtd9V8VeGvJkL9rUtRtjCkqGwGGCPjHJR6Wh");
+
+// Локальная замена AMM для тестов: реализует интерфейс AMM-адаптера
+// (см. tech_hy_common::amm) без настоящего ценообразования Raydium.
+// В сеть не деплоится.
+
+pub const POOL_SEED: &[u8] = b"pool";
+pub const POSITION_SEED: &[u8] = b"position";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    /// Создает пул пары mint_a/mint_b с хранилищами резервов и хранилищем комиссий.
+    /// Комиссии начисляются в mint_a.
+    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.mint_a = ctx.accounts.mint_a.key();
+        pool.mint_b = ctx.accounts.mint_b.key();
+        pool.vault_a = ctx.accounts.vault_a.key();
+        pool.vault_b = ctx.accounts.vault_b.key();
+        pool.fee_vault = ctx.accounts.fee_vault.key();
+        pool.bump = ctx.bumps.pool;
+        Ok(())
+    }
+
+    /// Открывает позицию ликвидности для владельца (в т.ч. PDA другой программы)
+    pub fn open_position(ctx: Context<OpenPosition>, owner: Pubkey) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.pool = ctx.accounts.pool.key();
+        position.owner = owner;
+        position.fees_owed = 0;
+        position.bump = ctx.bumps.position;
+        Ok(())
+    }
+
+    /// Имитирует накопление торговых комиссий позиции: `amount` токенов mint_a
+    /// переводится в хранилище комиссий и записывается за позицией.
+    pub fn accrue_fees(ctx: Context<AccrueFees>, amount: u64) -> Result<()> {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.funder_token_account.to_account_info(),
+                    mint: ctx.accounts.fee_mint.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.fee_mint.decimals,
+        )?;
+
+        let position = &mut ctx.accounts.position;
+        position.fees_owed = position.fees_owed.checked_add(amount).ok_or(MockAmmError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Инструкция адаптера `collect_fees`: переводит накопленные комиссии позиции
+    /// на `destination`. Подписывает владелец позиции.
+    pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+        let amount = ctx.accounts.position.fees_owed;
+        if amount > 0 {
+            let pool = &ctx.accounts.pool;
+            let seeds = &[POOL_SEED, pool.mint_a.as_ref(), pool.mint_b.as_ref(), &[pool.bump]];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.fee_vault.to_account_info(),
+                        mint: ctx.accounts.fee_mint.to_account_info(),
+                        to: ctx.accounts.destination.to_account_info(),
+                        authority: ctx.accounts.pool.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                amount,
+                ctx.accounts.fee_mint.decimals,
+            )?;
+        }
+        ctx.accounts.position.fees_owed = 0;
+        msg!("Собрано {} комиссий позиции", amount);
+        Ok(())
+    }
+
+    /// Инструкция адаптера `swap`: обмен по формуле постоянного произведения
+    /// резервов без комиссии. Аккаунты Transfer Hook передаются через remaining_accounts.
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        let source_mint = ctx.accounts.source_mint.key();
+        let destination_mint = ctx.accounts.destination_mint.key();
+        let (source_vault, destination_vault) = if source_mint == pool.mint_a && destination_mint == pool.mint_b {
+            (pool.vault_a, pool.vault_b)
+        } else if source_mint == pool.mint_b && destination_mint == pool.mint_a {
+            (pool.vault_b, pool.vault_a)
+        } else {
+            return err!(MockAmmError::InvalidPoolAccount);
+        };
+        if ctx.accounts.pool_source_vault.key() != source_vault
+            || ctx.accounts.pool_destination_vault.key() != destination_vault
+        {
+            return err!(MockAmmError::InvalidPoolAccount);
+        }
+
+        // out = reserve_out * in / (reserve_in + in)
+        let reserve_in = ctx.accounts.pool_source_vault.amount as u128;
+        let reserve_out = ctx.accounts.pool_destination_vault.amount as u128;
+        let amount_out = reserve_out
+            .checked_mul(amount_in as u128)
+            .and_then(|value| value.checked_div(reserve_in.checked_add(amount_in as u128)?))
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or(MockAmmError::MathOverflow)?;
+        if amount_out == 0 || amount_out < min_amount_out {
+            return err!(MockAmmError::SlippageExceeded);
+        }
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.source_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_source.to_account_info(),
+                    mint: ctx.accounts.source_mint.to_account_info(),
+                    to: ctx.accounts.pool_source_vault.to_account_info(),
+                    authority: ctx.accounts.user_authority.to_account_info(),
+                },
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            amount_in,
+            ctx.accounts.source_mint.decimals,
+        )?;
+
+        let seeds = &[POOL_SEED, pool.mint_a.as_ref(), pool.mint_b.as_ref(), &[pool.bump]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.destination_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.pool_destination_vault.to_account_info(),
+                    mint: ctx.accounts.destination_mint.to_account_info(),
+                    to: ctx.accounts.user_destination.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[&seeds[..]],
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            amount_out,
+            ctx.accounts.destination_mint.decimals,
+        )?;
+
+        msg!("Обмен {} -> {}", amount_in, amount_out);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint_a,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint_b,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint_a,
+        token::authority = pool,
+        token::token_program = token_program,
+        seeds = [FEE_VAULT_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct OpenPosition<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [POSITION_SEED, pool.key().as_ref(), owner.as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AccrueFees<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(has_one = fee_vault)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub position: Account<'info, Position>,
+
+    #[account(address = pool.mint_a)]
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = fee_mint, token::authority = funder)]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Порядок аккаунтов задан интерфейсом адаптера (tech_hy_common::AmmCollectFeesAccounts)
+#[derive(Accounts)]
+pub struct CollectFees<'info> {
+    #[account(mut, has_one = fee_vault)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = position.owner == position_owner.key() @ MockAmmError::Unauthorized
+    )]
+    pub position: Account<'info, Position>,
+
+    pub position_owner: Signer<'info>,
+
+    #[account(mut)]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = pool.mint_a)]
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = fee_mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Порядок аккаунтов задан интерфейсом адаптера (tech_hy_common::AmmSwapAccounts)
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    pub user_authority: Signer<'info>,
+
+    #[account(mut, token::mint = source_mint, token::authority = user_authority)]
+    pub user_source: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = destination_mint)]
+    pub user_destination: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub pool_source_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub pool_destination_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub source_mint: InterfaceAccount<'info, Mint>,
+    pub destination_mint: InterfaceAccount<'info, Mint>,
+
+    pub source_token_program: Interface<'info, TokenInterface>,
+    pub destination_token_program: Interface<'info, TokenInterface>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    pub fee_vault: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub fees_owed: u64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum MockAmmError {
+    #[msg("Операция не авторизована")]
+    Unauthorized,
+    #[msg("Математическое переполнение")]
+    MathOverflow,
+    #[msg("Аккаунт не относится к пулу")]
+    InvalidPoolAccount,
+    #[msg("Выход обмена меньше минимального")]
+    SlippageExceeded,
+}
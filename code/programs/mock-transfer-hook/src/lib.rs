@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+// Адрес совпадает с vg_token::VG_TRANSFER_HOOK_PROGRAM_ID для localnet
+declare_id!("Hkmi3BdVG9SyhxMzmeWSVj1MXUyKnqRtuPmiDuC2fFvk");
+
+// Локальная замена программы-хука VG для тестов: Token-2022 вызывает Execute
+// при каждом переводе VG, а заглушка принимает любой перевод. Без нее минт VG
+// с расширением TransferHook нельзя переводить в bankrun. В сеть не деплоится.
+
+#[program]
+pub mod mock_transfer_hook {
+    use super::*;
+
+    /// Execute интерфейса Transfer Hook (и любая другая инструкция): перевод разрешен
+    pub fn fallback<'info>(_program_id: &Pubkey, _accounts: &'info [AccountInfo<'info>], _data: &[u8]) -> Result<()> {
+        Ok(())
+    }
+}
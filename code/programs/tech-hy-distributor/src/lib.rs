@@ -0,0 +1,530 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use tech_hy_contracts::{EcosystemRegistry, ECOSYSTEM_REGISTRY_SEED, ID as REGISTRY_PROGRAM_ID};
+
+declare_id!("DSTwb9je8t2fPnBEq7riafHz5eqhb6Da42yLHCfqrah9");
+
+// Сиды PDA дистрибьютора (общие для экосистемы, см. tech-hy-common)
+pub use tech_hy_common::{CLAIM_BITMAP_SEED, MERKLE_DISTRIBUTOR_SEED};
+use tech_hy_common::{airdrop_leaf, verify_proof, AIRDROP_MAX_CLAIMANTS};
+
+/// Максимум получателей одной раздачи (общий с офчейн-утилитой, см. tech-hy-common)
+pub const MAX_CLAIMANTS: u64 = AIRDROP_MAX_CLAIMANTS;
+/// Максимальная глубина merkle-доказательства (2^20 листьев с запасом)
+pub const MAX_PROOF_LEN: usize = 20;
+
+/// Merkle-дистрибьютор airdrop для кампаний сообщества. DAO фиксирует корень
+/// дерева (index, claimant, amount) и пополняет хранилище VC или VG; получатель
+/// забирает свою сумму, предъявив доказательство. Полученные листья отмечаются
+/// в битовой карте. После истечения срока остаток возвращается в казну DAO.
+/// Дерево и доказательства строит офчейн-утилита tools/airdrop-tree.
+#[program]
+pub mod tech_hy_distributor {
+    use super::*;
+
+    /// Создает раздачу и переводит `max_total_claim` в хранилище дистрибьютора.
+    /// Только DAO (авторитет реестра). Для VG аккаунты Transfer Hook передаются
+    /// через remaining_accounts.
+    pub fn create_distributor<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateDistributor<'info>>,
+        distribution_id: u64,
+        merkle_root: [u8; 32],
+        max_total_claim: u64,
+        num_nodes: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        if max_total_claim == 0 {
+            return err!(DistributorError::InvalidAmount);
+        }
+        if num_nodes == 0 || num_nodes > MAX_CLAIMANTS {
+            return err!(DistributorError::InvalidNumNodes);
+        }
+        let now = Clock::get()?.unix_timestamp;
+        if expires_at <= now {
+            return err!(DistributorError::InvalidExpiry);
+        }
+
+        let distributor = &mut ctx.accounts.distributor;
+        distributor.version = MerkleDistributor::VERSION;
+        distributor.distribution_id = distribution_id;
+        distributor.authority = ctx.accounts.dao_authority.key();
+        distributor.mint = ctx.accounts.mint.key();
+        distributor.vault = ctx.accounts.vault.key();
+        distributor.merkle_root = merkle_root;
+        distributor.max_total_claim = max_total_claim;
+        distributor.num_nodes = num_nodes;
+        distributor.total_claimed = 0;
+        distributor.num_claimed = 0;
+        distributor.expires_at = expires_at;
+        distributor.clawed_back = false;
+        distributor.bump = ctx.bumps.distributor;
+
+        let claim_bitmap = &mut ctx.accounts.claim_bitmap;
+        claim_bitmap.version = ClaimBitmap::VERSION;
+        claim_bitmap.distributor = distributor.key();
+        claim_bitmap.bits = vec![0; ClaimBitmap::bytes_for(num_nodes)];
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.funder_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.dao_authority.to_account_info(),
+                },
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            max_total_claim,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        emit!(DistributorCreated {
+            distributor: distributor.key(),
+            distribution_id,
+            mint: distributor.mint,
+            merkle_root,
+            max_total_claim,
+            num_nodes,
+            expires_at,
+        });
+        msg!("Раздача {} создана: {} получателей, до {}", distribution_id, num_nodes, expires_at);
+        Ok(())
+    }
+
+    /// Получение airdrop по merkle-доказательству. Каждый лист забирается один раз.
+    /// Для VG аккаунты Transfer Hook передаются через remaining_accounts.
+    pub fn claim<'info>(
+        ctx: Context<'_, '_, '_, 'info, Claim<'info>>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let distributor = &mut ctx.accounts.distributor;
+        let now = Clock::get()?.unix_timestamp;
+        if distributor.clawed_back || now >= distributor.expires_at {
+            return err!(DistributorError::DistributionExpired);
+        }
+        if index >= distributor.num_nodes {
+            return err!(DistributorError::InvalidIndex);
+        }
+        if proof.len() > MAX_PROOF_LEN {
+            return err!(DistributorError::InvalidProof);
+        }
+
+        let claim_bitmap = &mut ctx.accounts.claim_bitmap;
+        if claim_bitmap.is_claimed(index) {
+            return err!(DistributorError::AlreadyClaimed);
+        }
+
+        let claimant = ctx.accounts.claimant.key();
+        let leaf = airdrop_leaf(index, &claimant, amount);
+        if !verify_proof(&proof, &distributor.merkle_root, leaf) {
+            return err!(DistributorError::InvalidProof);
+        }
+
+        let total_claimed = distributor
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(DistributorError::MathOverflow)?;
+        if total_claimed > distributor.max_total_claim {
+            return err!(DistributorError::ExceededMaxClaim);
+        }
+        claim_bitmap.set_claimed(index);
+        distributor.total_claimed = total_claimed;
+        distributor.num_claimed = distributor.num_claimed.checked_add(1).ok_or(DistributorError::MathOverflow)?;
+
+        let mint_key = distributor.mint;
+        let id_bytes = distributor.distribution_id.to_le_bytes();
+        let distributor_seeds = &[
+            MERKLE_DISTRIBUTOR_SEED,
+            mint_key.as_ref(),
+            id_bytes.as_ref(),
+            &[distributor.bump],
+        ];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.claimant_token_account.to_account_info(),
+                    authority: ctx.accounts.distributor.to_account_info(),
+                },
+                &[&distributor_seeds[..]],
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        emit!(AirdropClaimed {
+            distributor: ctx.accounts.distributor.key(),
+            claimant,
+            index,
+            amount,
+            timestamp: now,
+        });
+        msg!("Airdrop получен: {} токенов, лист {}", amount, index);
+        Ok(())
+    }
+
+    /// Возврат неполученного остатка в казну DAO после истечения срока раздачи.
+    /// Может вызвать любой аккаунт: получатель средств фиксирован реестром.
+    /// Для VG аккаунты Transfer Hook передаются через remaining_accounts.
+    pub fn clawback<'info>(ctx: Context<'_, '_, '_, 'info, Clawback<'info>>) -> Result<()> {
+        let distributor = &mut ctx.accounts.distributor;
+        let now = Clock::get()?.unix_timestamp;
+        if now < distributor.expires_at {
+            return err!(DistributorError::DistributionNotExpired);
+        }
+        if distributor.clawed_back {
+            return err!(DistributorError::AlreadyClawedBack);
+        }
+        distributor.clawed_back = true;
+
+        let amount = ctx.accounts.vault.amount;
+        if amount > 0 {
+            let mint_key = distributor.mint;
+            let id_bytes = distributor.distribution_id.to_le_bytes();
+            let distributor_seeds = &[
+                MERKLE_DISTRIBUTOR_SEED,
+                mint_key.as_ref(),
+                id_bytes.as_ref(),
+                &[distributor.bump],
+            ];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.vault.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.dao_treasury_token_account.to_account_info(),
+                        authority: ctx.accounts.distributor.to_account_info(),
+                    },
+                    &[&distributor_seeds[..]],
+                )
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+                amount,
+                ctx.accounts.mint.decimals,
+            )?;
+        }
+
+        emit!(DistributorClawedBack {
+            distributor: ctx.accounts.distributor.key(),
+            dao_treasury: ctx.accounts.dao_treasury.key(),
+            amount,
+            timestamp: now,
+        });
+        msg!("Остаток раздачи {} возвращен в казну DAO", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(distribution_id: u64, merkle_root: [u8; 32], max_total_claim: u64, num_nodes: u64)]
+pub struct CreateDistributor<'info> {
+    /// DAO (авторитет реестра): создает раздачу, пополняет хранилище и платит за аккаунты
+    #[account(mut, address = registry.authority @ DistributorError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ DistributorError::ProgramPaused,
+    )]
+    pub registry: Box<Account<'info, EcosystemRegistry>>,
+
+    #[account(
+        init,
+        payer = dao_authority,
+        space = 8 + MerkleDistributor::INIT_SPACE,
+        seeds = [MERKLE_DISTRIBUTOR_SEED, mint.key().as_ref(), &distribution_id.to_le_bytes()],
+        bump
+    )]
+    pub distributor: Box<Account<'info, MerkleDistributor>>,
+
+    #[account(
+        init,
+        payer = dao_authority,
+        space = ClaimBitmap::space(num_nodes),
+        seeds = [CLAIM_BITMAP_SEED, distributor.key().as_ref()],
+        bump
+    )]
+    pub claim_bitmap: Box<Account<'info, ClaimBitmap>>,
+
+    /// Раздаются только VC и VG
+    #[account(
+        mint::token_program = token_program,
+        constraint = mint.key() == registry.vc_mint || mint.key() == registry.vg_mint @ DistributorError::InvalidMint,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = dao_authority,
+        associated_token::mint = mint,
+        associated_token::authority = distributor,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Источник пополнения (например, токен-аккаунт, управляемый DAO)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = dao_authority,
+        token::token_program = token_program,
+    )]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    /// Получатель из листа дерева; платит за создание своего ATA
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ DistributorError::ProgramPaused,
+    )]
+    pub registry: Box<Account<'info, EcosystemRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [
+            MERKLE_DISTRIBUTOR_SEED,
+            distributor.mint.as_ref(),
+            &distributor.distribution_id.to_le_bytes()
+        ],
+        bump = distributor.bump,
+        has_one = mint @ DistributorError::InvalidMint,
+        has_one = vault @ DistributorError::InvalidVault,
+        constraint = distributor.version == MerkleDistributor::VERSION @ DistributorError::AccountNeedsMigration,
+    )]
+    pub distributor: Box<Account<'info, MerkleDistributor>>,
+
+    #[account(
+        mut,
+        seeds = [CLAIM_BITMAP_SEED, distributor.key().as_ref()],
+        bump,
+        constraint = claim_bitmap.version == ClaimBitmap::VERSION @ DistributorError::AccountNeedsMigration,
+    )]
+    pub claim_bitmap: Box<Account<'info, ClaimBitmap>>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = claimant,
+        associated_token::mint = mint,
+        associated_token::authority = claimant,
+        associated_token::token_program = token_program,
+    )]
+    pub claimant_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Clawback<'info> {
+    /// Любой аккаунт; платит за создание ATA казны, если его нет
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ DistributorError::ProgramPaused,
+    )]
+    pub registry: Box<Account<'info, EcosystemRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [
+            MERKLE_DISTRIBUTOR_SEED,
+            distributor.mint.as_ref(),
+            &distributor.distribution_id.to_le_bytes()
+        ],
+        bump = distributor.bump,
+        has_one = mint @ DistributorError::InvalidMint,
+        has_one = vault @ DistributorError::InvalidVault,
+        constraint = distributor.version == MerkleDistributor::VERSION @ DistributorError::AccountNeedsMigration,
+    )]
+    pub distributor: Box<Account<'info, MerkleDistributor>>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Владелец токен-аккаунтов казны DAO, сверяется с реестром
+    #[account(address = registry.dao_treasury @ DistributorError::InvalidTreasury)]
+    pub dao_treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = dao_treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub dao_treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// Новые поля добавляются только в конец структуры, а VERSION увеличивается
+#[account]
+#[derive(InitSpace)]
+pub struct MerkleDistributor {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    /// Номер раздачи (часть сидов PDA)
+    pub distribution_id: u64,
+    /// DAO, создавший раздачу
+    pub authority: Pubkey,
+    /// Минт VC или VG
+    pub mint: Pubkey,
+    /// ATA дистрибьютора с нераспределенными токенами
+    pub vault: Pubkey,
+    /// Корень дерева (index, claimant, amount), см. tech_hy_common::merkle
+    pub merkle_root: [u8; 32],
+    /// Сумма всех листьев дерева (внесена в хранилище при создании)
+    pub max_total_claim: u64,
+    /// Число листьев дерева
+    pub num_nodes: u64,
+    /// Уже получено
+    pub total_claimed: u64,
+    /// Число полученных листьев
+    pub num_claimed: u64,
+    /// Срок раздачи (unix timestamp); после него разрешен только возврат в казну
+    pub expires_at: i64,
+    /// Остаток возвращен в казну
+    pub clawed_back: bool,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl MerkleDistributor {
+    pub const VERSION: u8 = 1;
+}
+
+/// Битовая карта полученных листьев: бит `index` выставлен, если лист получен.
+/// Размер зависит от числа листьев; при смене раскладки VERSION увеличивается
+#[account]
+pub struct ClaimBitmap {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    pub distributor: Pubkey,
+    pub bits: Vec<u8>,
+}
+
+impl ClaimBitmap {
+    pub const VERSION: u8 = 1;
+
+    pub fn bytes_for(num_nodes: u64) -> usize {
+        num_nodes.div_ceil(8) as usize
+    }
+
+    pub fn space(num_nodes: u64) -> usize {
+        8 + 1 + 32 + 4 + Self::bytes_for(num_nodes)
+    }
+
+    pub fn is_claimed(&self, index: u64) -> bool {
+        self.bits[(index / 8) as usize] & (1 << (index % 8)) != 0
+    }
+
+    fn set_claimed(&mut self, index: u64) {
+        self.bits[(index / 8) as usize] |= 1 << (index % 8);
+    }
+}
+
+const _: () = assert!(8 + 1 + 32 + 4 + MAX_CLAIMANTS.div_ceil(8) as usize <= 10_240);
+
+#[event]
+pub struct DistributorCreated {
+    pub distributor: Pubkey,
+    pub distribution_id: u64,
+    pub mint: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub max_total_claim: u64,
+    pub num_nodes: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct AirdropClaimed {
+    pub distributor: Pubkey,
+    pub claimant: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributorClawedBack {
+    pub distributor: Pubkey,
+    pub dao_treasury: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum DistributorError {
+    #[msg("Операция не авторизована")]
+    Unauthorized,
+    #[msg("Сумма не может быть нулевой")]
+    InvalidAmount,
+    #[msg("Раздаются только VC и VG из реестра")]
+    InvalidMint,
+    #[msg("Хранилище не принадлежит дистрибьютору")]
+    InvalidVault,
+    #[msg("Адрес казны не совпадает с реестром")]
+    InvalidTreasury,
+    #[msg("Число получателей должно быть от 1 до MAX_CLAIMANTS")]
+    InvalidNumNodes,
+    #[msg("Срок раздачи должен быть в будущем")]
+    InvalidExpiry,
+    #[msg("Номер листа вне дерева")]
+    InvalidIndex,
+    #[msg("Неверное merkle-доказательство")]
+    InvalidProof,
+    #[msg("Airdrop по этому листу уже получен")]
+    AlreadyClaimed,
+    #[msg("Сумма получений превышает пополнение раздачи")]
+    ExceededMaxClaim,
+    #[msg("Срок раздачи истек")]
+    DistributionExpired,
+    #[msg("Срок раздачи еще не истек")]
+    DistributionNotExpired,
+    #[msg("Остаток уже возвращен в казну")]
+    AlreadyClawedBack,
+    #[msg("Экосистема на аварийной паузе")]
+    ProgramPaused,
+    #[msg("Аккаунт использует устаревшую раскладку, требуется миграция")]
+    AccountNeedsMigration,
+    #[msg("Произошло переполнение при математических операциях")]
+    MathOverflow,
+}
@@ -0,0 +1,519 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use tech_hy_contracts::{EcosystemRegistry, ECOSYSTEM_REGISTRY_SEED, ID as REGISTRY_PROGRAM_ID};
+
+declare_id!("VST29R3XQHfN5CbM9TL5rdXg4rC4rq3ZdSaRzpJEeUo");
+
+// Сиды PDA вестинга (общие для экосистемы, см. tech-hy-common)
+pub use tech_hy_common::{VESTING_INDEX_SEED, VESTING_SCHEDULE_SEED};
+use tech_hy_common::mul_div;
+
+/// Вестинг VC и VG токенов: клифф и линейная разблокировка до `end_ts`.
+/// Графики бенефициара нумеруются счетчиком VestingIndex, поэтому клиент может
+/// перечислить их по PDA [VESTING_SCHEDULE_SEED, beneficiary, index] для index в 0..schedule_count.
+#[program]
+pub mod tech_hy_vesting {
+    use super::*;
+
+    /// Создает график вестинга и переносит всю сумму с токен-аккаунта фандера в хранилище графика.
+    /// Для VG аккаунты Transfer Hook передаются через remaining_accounts.
+    pub fn create_schedule<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateSchedule<'info>>,
+        params: CreateScheduleParams,
+    ) -> Result<()> {
+        if params.total_amount == 0 {
+            return err!(VestingError::InvalidAmount);
+        }
+        // start <= cliff <= end, start < end
+        if params.start_ts > params.cliff_ts
+            || params.cliff_ts > params.end_ts
+            || params.start_ts >= params.end_ts
+        {
+            return err!(VestingError::InvalidSchedule);
+        }
+
+        let vesting_index = &mut ctx.accounts.vesting_index;
+        if vesting_index.beneficiary == Pubkey::default() {
+            vesting_index.version = VestingIndex::VERSION;
+            vesting_index.beneficiary = ctx.accounts.beneficiary.key();
+            vesting_index.bump = ctx.bumps.vesting_index;
+        }
+        let index = vesting_index.schedule_count;
+        vesting_index.schedule_count = index.checked_add(1).ok_or(VestingError::MathOverflow)?;
+
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.version = VestingSchedule::VERSION;
+        schedule.beneficiary = ctx.accounts.beneficiary.key();
+        schedule.index = index;
+        schedule.mint = ctx.accounts.mint.key();
+        schedule.vault = ctx.accounts.vault.key();
+        schedule.funder = ctx.accounts.funder.key();
+        schedule.total_amount = params.total_amount;
+        schedule.claimed_amount = 0;
+        schedule.start_ts = params.start_ts;
+        schedule.cliff_ts = params.cliff_ts;
+        schedule.end_ts = params.end_ts;
+        schedule.revocable = params.revocable;
+        schedule.revoked = false;
+        schedule.bump = ctx.bumps.vesting_schedule;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.funder_token_account.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            params.total_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        emit!(ScheduleCreated {
+            schedule: schedule.key(),
+            beneficiary: schedule.beneficiary,
+            mint: schedule.mint,
+            index,
+            total_amount: params.total_amount,
+            start_ts: params.start_ts,
+            cliff_ts: params.cliff_ts,
+            end_ts: params.end_ts,
+            revocable: params.revocable,
+        });
+        msg!("График вестинга #{} создан для {}: {} токенов", index, schedule.beneficiary, params.total_amount);
+        Ok(())
+    }
+
+    /// Переводит бенефициару разблокированную, но еще не полученную часть графика.
+    pub fn claim_vested<'info>(ctx: Context<'_, '_, '_, 'info, ClaimVested<'info>>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &ctx.accounts.vesting_schedule;
+
+        let vested = schedule.vested_amount(now).ok_or(VestingError::MathOverflow)?;
+        let claimable = vested
+            .checked_sub(schedule.claimed_amount)
+            .ok_or(VestingError::MathOverflow)?;
+        if claimable == 0 {
+            return err!(VestingError::NothingToClaim);
+        }
+
+        let beneficiary_key = schedule.beneficiary;
+        let index_bytes = schedule.index.to_le_bytes();
+        let schedule_seeds = &[
+            VESTING_SCHEDULE_SEED,
+            beneficiary_key.as_ref(),
+            index_bytes.as_ref(),
+            &[schedule.bump],
+        ];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                    authority: ctx.accounts.vesting_schedule.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+                &[&schedule_seeds[..]],
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            claimable,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.claimed_amount = schedule
+            .claimed_amount
+            .checked_add(claimable)
+            .ok_or(VestingError::MathOverflow)?;
+
+        emit!(VestedClaimed {
+            schedule: schedule.key(),
+            beneficiary: schedule.beneficiary,
+            amount: claimable,
+            claimed_total: schedule.claimed_amount,
+            timestamp: now,
+        });
+        msg!("Получено {} токенов по графику #{}", claimable, schedule.index);
+        Ok(())
+    }
+
+    /// Отзывает неразблокированную часть графика и возвращает ее фандеру графика.
+    /// Только авторитет DAO из реестра. Уже разблокированная часть остается доступной бенефициару.
+    pub fn revoke_unvested<'info>(ctx: Context<'_, '_, '_, 'info, RevokeUnvested<'info>>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &ctx.accounts.vesting_schedule;
+        if !schedule.revocable {
+            return err!(VestingError::NotRevocable);
+        }
+        if schedule.revoked {
+            return err!(VestingError::AlreadyRevoked);
+        }
+
+        let vested = schedule.vested_amount(now).ok_or(VestingError::MathOverflow)?;
+        let unvested = schedule
+            .total_amount
+            .checked_sub(vested)
+            .ok_or(VestingError::MathOverflow)?;
+
+        if unvested > 0 {
+            let beneficiary_key = schedule.beneficiary;
+            let index_bytes = schedule.index.to_le_bytes();
+            let schedule_seeds = &[
+                VESTING_SCHEDULE_SEED,
+                beneficiary_key.as_ref(),
+                index_bytes.as_ref(),
+                &[schedule.bump],
+            ];
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: ctx.accounts.funder_token_account.to_account_info(),
+                        authority: ctx.accounts.vesting_schedule.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                    },
+                    &[&schedule_seeds[..]],
+                )
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+                unvested,
+                ctx.accounts.mint.decimals,
+            )?;
+        }
+
+        // После отзыва график считается полностью разблокированным на сумму vested
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.total_amount = vested;
+        schedule.revoked = true;
+
+        emit!(UnvestedRevoked {
+            schedule: schedule.key(),
+            beneficiary: schedule.beneficiary,
+            revoked_amount: unvested,
+            vested_amount: vested,
+            timestamp: now,
+        });
+        msg!("Отозвано {} токенов по графику #{}", unvested, schedule.index);
+        Ok(())
+    }
+}
+
+/// Параметры нового графика вестинга
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateScheduleParams {
+    /// Общая сумма графика
+    pub total_amount: u64,
+    /// Начало линейной разблокировки (unix timestamp)
+    pub start_ts: i64,
+    /// Клифф: до этого момента ничего не разблокировано
+    pub cliff_ts: i64,
+    /// Конец разблокировки: с этого момента разблокирована вся сумма
+    pub end_ts: i64,
+    /// Может ли DAO отозвать неразблокированную часть
+    pub revocable: bool,
+}
+
+// Новые поля добавляются только в конец структур, а VERSION увеличивается
+// вместе с соответствующей инструкцией миграции.
+#[account]
+#[derive(InitSpace)]
+pub struct VestingSchedule {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    /// Получатель токенов
+    pub beneficiary: Pubkey,
+    /// Порядковый номер графика у бенефициара
+    pub index: u64,
+    /// Минт токена (VC или VG)
+    pub mint: Pubkey,
+    /// Токен-аккаунт хранилища (ATA графика)
+    pub vault: Pubkey,
+    /// Кто профинансировал график
+    pub funder: Pubkey,
+    /// Общая сумма графика (после отзыва - сумма, разблокированная на момент отзыва)
+    pub total_amount: u64,
+    /// Уже полученная бенефициаром сумма
+    pub claimed_amount: u64,
+    /// Начало линейной разблокировки
+    pub start_ts: i64,
+    /// Клифф
+    pub cliff_ts: i64,
+    /// Конец разблокировки
+    pub end_ts: i64,
+    /// Может ли DAO отозвать неразблокированную часть
+    pub revocable: bool,
+    /// График отозван
+    pub revoked: bool,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl VestingSchedule {
+    pub const VERSION: u8 = 1;
+
+    /// Разблокированная сумма на момент `now`: 0 до клиффа, затем линейно от `start_ts` до `end_ts`
+    pub fn vested_amount(&self, now: i64) -> Option<u64> {
+        if self.revoked || now >= self.end_ts {
+            return Some(self.total_amount);
+        }
+        if now < self.cliff_ts {
+            return Some(0);
+        }
+        let elapsed = u64::try_from(now.checked_sub(self.start_ts)?).ok()?;
+        let duration = u64::try_from(self.end_ts.checked_sub(self.start_ts)?).ok()?;
+        mul_div(self.total_amount, elapsed, duration)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VestingIndex {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    /// Бенефициар
+    pub beneficiary: Pubkey,
+    /// Количество созданных графиков (следующий свободный index)
+    pub schedule_count: u64,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl VestingIndex {
+    pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct CreateSchedule<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: Бенефициар графика, может быть любым аккаунтом
+    pub beneficiary: UncheckedAccount<'info>,
+
+    /// Реестр экосистемы: канонические минты и состояние аварийной паузы
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ VestingError::ProgramPaused,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + VestingIndex::INIT_SPACE,
+        seeds = [VESTING_INDEX_SEED, beneficiary.key().as_ref()],
+        bump,
+    )]
+    pub vesting_index: Account<'info, VestingIndex>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = 8 + VestingSchedule::INIT_SPACE,
+        seeds = [
+            VESTING_SCHEDULE_SEED,
+            beneficiary.key().as_ref(),
+            &vesting_index.schedule_count.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    // Вестинг поддерживает только токены экосистемы: VC (SPL Token) и VG (Token-2022)
+    #[account(
+        mint::token_program = token_program,
+        constraint = mint.key() == registry.vc_mint || mint.key() == registry.vg_mint @ VestingError::InvalidMint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = funder,
+        token::token_program = token_program,
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = funder,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ VestingError::ProgramPaused,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SCHEDULE_SEED,
+            beneficiary.key().as_ref(),
+            &vesting_schedule.index.to_le_bytes(),
+        ],
+        bump = vesting_schedule.bump,
+        has_one = beneficiary @ VestingError::Unauthorized,
+        has_one = mint @ VestingError::InvalidMint,
+        has_one = vault @ VestingError::InvalidVault,
+        constraint = vesting_schedule.version == VestingSchedule::VERSION @ VestingError::AccountNeedsMigration,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program,
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeUnvested<'info> {
+    /// Авторитет DAO (исполнитель DAO), записанный в реестре
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        has_one = authority @ VestingError::Unauthorized,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SCHEDULE_SEED,
+            vesting_schedule.beneficiary.as_ref(),
+            &vesting_schedule.index.to_le_bytes(),
+        ],
+        bump = vesting_schedule.bump,
+        has_one = mint @ VestingError::InvalidMint,
+        has_one = vault @ VestingError::InvalidVault,
+        has_one = funder @ VestingError::Unauthorized,
+        constraint = vesting_schedule.version == VestingSchedule::VERSION @ VestingError::AccountNeedsMigration,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Фандер графика, сверяется с vesting_schedule.funder
+    pub funder: UncheckedAccount<'info>,
+
+    /// Неразблокированная часть возвращается туда, откуда пришла
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = funder,
+        associated_token::token_program = token_program,
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct ScheduleCreated {
+    pub schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub index: u64,
+    pub total_amount: u64,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+    pub revocable: bool,
+}
+
+#[event]
+pub struct VestedClaimed {
+    pub schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub claimed_total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnvestedRevoked {
+    pub schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub revoked_amount: u64,
+    pub vested_amount: u64,
+    pub timestamp: i64,
+}
+
+// Ошибки программы
+#[error_code]
+pub enum VestingError {
+    #[msg("Операция не авторизована")]
+    Unauthorized,
+    #[msg("Сумма вестинга не может быть нулевой")]
+    InvalidAmount,
+    #[msg("Неверные параметры графика: требуется start <= cliff <= end и start < end")]
+    InvalidSchedule,
+    #[msg("Вестинг поддерживает только минты VC и VG из реестра")]
+    InvalidMint,
+    #[msg("Хранилище не соответствует графику")]
+    InvalidVault,
+    #[msg("Нет разблокированных токенов для получения")]
+    NothingToClaim,
+    #[msg("График не может быть отозван")]
+    NotRevocable,
+    #[msg("График уже отозван")]
+    AlreadyRevoked,
+    #[msg("Экосистема на аварийной паузе")]
+    ProgramPaused,
+    #[msg("Аккаунт использует устаревшую раскладку, требуется миграция")]
+    AccountNeedsMigration,
+    #[msg("Произошло переполнение при математических операциях")]
+    MathOverflow,
+}
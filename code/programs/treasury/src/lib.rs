@@ -0,0 +1,824 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::AccountMeta, program::invoke_signed};
+use anchor_lang::system_program;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use tech_hy_contracts::{EcosystemRegistry, ECOSYSTEM_REGISTRY_SEED, ID as REGISTRY_PROGRAM_ID};
+use burn_and_earn::BurnAndEarnState;
+
+declare_id!("TRSDcgYu73diqRhuNjgFnUunyymFp95RWgzabNAzp3B");
+
+// Сиды PDA казны (общие для экосистемы, см. tech-hy-common)
+pub use tech_hy_common::{TREASURY_BUDGET_SEED, TREASURY_BUYBACK_ROUTE_SEED, TREASURY_SEED, TREASURY_STATE_SEED};
+use tech_hy_common::{amm_swap_instruction, AmmSwapAccounts, BURN_AND_EARN_STATE_SEED};
+
+/// Актив SOL в бюджетах категорий (у SOL нет минта)
+pub const SOL_ASSET: Pubkey = Pubkey::new_from_array([0; 32]);
+
+/// Казна DAO: VC, VG и SOL принадлежат PDA [TREASURY_SEED], поэтому средства
+/// расходуются только инструкциями программы. Чтобы налог VG поступал в казну,
+/// `registry.dao_treasury` должен указывать на этот PDA.
+/// Расходы разрешены только исполнителю governance и ограничены бюджетом категории.
+#[program]
+pub mod treasury {
+    use super::*;
+
+    /// Создает состояние казны и назначает исполнителя governance. Только DAO (авторитет реестра).
+    pub fn initialize(ctx: Context<Initialize>, governance_executor: Pubkey) -> Result<()> {
+        let treasury_state = &mut ctx.accounts.treasury_state;
+        treasury_state.version = TreasuryState::VERSION;
+        treasury_state.total_vg_swapped = 0;
+        treasury_state.total_vc_burned = 0;
+        treasury_state.bump = ctx.bumps.treasury_state;
+        treasury_state.governance_executor = governance_executor;
+
+        msg!("Казна DAO инициализирована, PDA казны: {}", ctx.accounts.treasury.key());
+        Ok(())
+    }
+
+    /// Задает маршрут выкупа VC: программу AMM-адаптера, промежуточный минт, пулы и их
+    /// хранилища. Только DAO (авторитет реестра); выкуп принимает только эти аккаунты.
+    pub fn set_buyback_route(ctx: Context<SetBuybackRoute>, route: BuybackRouteParams) -> Result<()> {
+        let buyback_route = &mut ctx.accounts.buyback_route;
+        buyback_route.version = BuybackRoute::VERSION;
+        buyback_route.route = route;
+        buyback_route.bump = ctx.bumps.buyback_route;
+
+        msg!("Маршрут выкупа: AMM {}, пулы {} и {}", route.amm_program, route.vg_pool, route.vc_pool);
+        Ok(())
+    }
+
+    /// Выкуп и сжигание VC: одобренная DAO сумма VG из казны обменивается через
+    /// AMM-адаптер на промежуточный токен (пул VG), затем на VC (пул VC), и весь
+    /// полученный VC сжигается. У каждого плеча своя граница проскальзывания.
+    /// Программа AMM, пулы и хранилища должны совпадать с маршрутом из `set_buyback_route`.
+    /// Аккаунты Transfer Hook VG передаются через remaining_accounts (первое плечо).
+    pub fn buyback_and_burn_vc<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuybackAndBurnVc<'info>>,
+        vg_amount: u64,
+        min_intermediate_out: u64,
+        min_vc_out: u64,
+    ) -> Result<()> {
+        if vg_amount == 0 {
+            return err!(TreasuryError::InvalidAmount);
+        }
+
+        let treasury_seeds = &[TREASURY_SEED, &[ctx.bumps.treasury]];
+        let hook_metas: Vec<AccountMeta> = ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect();
+
+        // 1. VG -> промежуточный токен
+        let intermediate_before = ctx.accounts.treasury_intermediate_account.amount;
+        let mut vg_swap = amm_swap_instruction(
+            ctx.accounts.amm_program.key(),
+            &AmmSwapAccounts {
+                pool: ctx.accounts.vg_pool.key(),
+                user_authority: ctx.accounts.treasury.key(),
+                user_source: ctx.accounts.treasury_vg_account.key(),
+                user_destination: ctx.accounts.treasury_intermediate_account.key(),
+                pool_source_vault: ctx.accounts.vg_pool_vg_vault.key(),
+                pool_destination_vault: ctx.accounts.vg_pool_intermediate_vault.key(),
+                source_mint: ctx.accounts.vg_mint.key(),
+                destination_mint: ctx.accounts.intermediate_mint.key(),
+                source_token_program: ctx.accounts.vg_token_program.key(),
+                destination_token_program: ctx.accounts.intermediate_token_program.key(),
+            },
+            vg_amount,
+            min_intermediate_out,
+        );
+        vg_swap.accounts.extend(hook_metas);
+        let mut vg_swap_infos = vec![
+            ctx.accounts.vg_pool.to_account_info(),
+            ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.treasury_vg_account.to_account_info(),
+            ctx.accounts.treasury_intermediate_account.to_account_info(),
+            ctx.accounts.vg_pool_vg_vault.to_account_info(),
+            ctx.accounts.vg_pool_intermediate_vault.to_account_info(),
+            ctx.accounts.vg_mint.to_account_info(),
+            ctx.accounts.intermediate_mint.to_account_info(),
+            ctx.accounts.vg_token_program.to_account_info(),
+            ctx.accounts.intermediate_token_program.to_account_info(),
+            ctx.accounts.amm_program.to_account_info(),
+        ];
+        vg_swap_infos.extend(ctx.remaining_accounts.iter().cloned());
+        invoke_signed(&vg_swap, &vg_swap_infos, &[&treasury_seeds[..]])?;
+
+        ctx.accounts.treasury_intermediate_account.reload()?;
+        let intermediate_amount = ctx.accounts.treasury_intermediate_account.amount
+            .checked_sub(intermediate_before)
+            .ok_or(TreasuryError::MathOverflow)?;
+        if intermediate_amount < min_intermediate_out {
+            return err!(TreasuryError::IntermediateSlippageExceeded);
+        }
+
+        // 2. Промежуточный токен -> VC
+        let vc_before = ctx.accounts.treasury_vc_account.amount;
+        let vc_swap = amm_swap_instruction(
+            ctx.accounts.amm_program.key(),
+            &AmmSwapAccounts {
+                pool: ctx.accounts.vc_pool.key(),
+                user_authority: ctx.accounts.treasury.key(),
+                user_source: ctx.accounts.treasury_intermediate_account.key(),
+                user_destination: ctx.accounts.treasury_vc_account.key(),
+                pool_source_vault: ctx.accounts.vc_pool_intermediate_vault.key(),
+                pool_destination_vault: ctx.accounts.vc_pool_vc_vault.key(),
+                source_mint: ctx.accounts.intermediate_mint.key(),
+                destination_mint: ctx.accounts.vc_mint.key(),
+                source_token_program: ctx.accounts.intermediate_token_program.key(),
+                destination_token_program: ctx.accounts.vc_token_program.key(),
+            },
+            intermediate_amount,
+            min_vc_out,
+        );
+        invoke_signed(
+            &vc_swap,
+            &[
+                ctx.accounts.vc_pool.to_account_info(),
+                ctx.accounts.treasury.to_account_info(),
+                ctx.accounts.treasury_intermediate_account.to_account_info(),
+                ctx.accounts.treasury_vc_account.to_account_info(),
+                ctx.accounts.vc_pool_intermediate_vault.to_account_info(),
+                ctx.accounts.vc_pool_vc_vault.to_account_info(),
+                ctx.accounts.intermediate_mint.to_account_info(),
+                ctx.accounts.vc_mint.to_account_info(),
+                ctx.accounts.intermediate_token_program.to_account_info(),
+                ctx.accounts.vc_token_program.to_account_info(),
+                ctx.accounts.amm_program.to_account_info(),
+            ],
+            &[&treasury_seeds[..]],
+        )?;
+
+        ctx.accounts.treasury_vc_account.reload()?;
+        let vc_amount = ctx.accounts.treasury_vc_account.amount
+            .checked_sub(vc_before)
+            .ok_or(TreasuryError::MathOverflow)?;
+        if vc_amount == 0 || vc_amount < min_vc_out {
+            return err!(TreasuryError::VcSlippageExceeded);
+        }
+
+        // 3. Сжигаем только выкупленный VC
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.vc_token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.vc_mint.to_account_info(),
+                    from: ctx.accounts.treasury_vc_account.to_account_info(),
+                    authority: ctx.accounts.treasury.to_account_info(),
+                },
+                &[&treasury_seeds[..]],
+            ),
+            vc_amount,
+        )?;
+
+        let treasury_state = &mut ctx.accounts.treasury_state;
+        treasury_state.total_vg_swapped = treasury_state.total_vg_swapped.checked_add(vg_amount).ok_or(TreasuryError::MathOverflow)?;
+        treasury_state.total_vc_burned = treasury_state.total_vc_burned.checked_add(vc_amount).ok_or(TreasuryError::MathOverflow)?;
+
+        emit!(VcBoughtBackAndBurned {
+            vg_amount,
+            intermediate_amount,
+            vc_burned: vc_amount,
+            total_vc_burned: treasury_state.total_vc_burned,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Выкуплено и сожжено {} VC за {} VG", vc_amount, vg_amount);
+        Ok(())
+    }
+
+    /// Меняет исполнителя governance, которому разрешены расходы казны.
+    /// Только DAO (авторитет реестра).
+    pub fn set_governance_executor(ctx: Context<SetGovernanceExecutor>, governance_executor: Pubkey) -> Result<()> {
+        ctx.accounts.treasury_state.governance_executor = governance_executor;
+        msg!("Исполнитель governance казны: {}", governance_executor);
+        Ok(())
+    }
+
+    /// Устанавливает бюджет категории для актива (минт VC/VG или SOL_ASSET).
+    /// Только DAO (авторитет реестра): исполнитель тратит в рамках бюджета, но не задает его.
+    /// `allocated` - общий лимит расходов категории, не меньше уже потраченного.
+    pub fn set_category_budget(
+        ctx: Context<SetCategoryBudget>,
+        category: BudgetCategory,
+        asset: Pubkey,
+        allocated: u64,
+    ) -> Result<()> {
+        let registry = &ctx.accounts.registry;
+        if asset != registry.vc_mint && asset != registry.vg_mint && asset != SOL_ASSET {
+            return err!(TreasuryError::InvalidAsset);
+        }
+
+        let budget = &mut ctx.accounts.category_budget;
+        if budget.version == 0 {
+            budget.version = CategoryBudget::VERSION;
+            budget.category = category;
+            budget.asset = asset;
+            budget.spent = 0;
+            budget.bump = ctx.bumps.category_budget;
+        }
+        if allocated < budget.spent {
+            return err!(TreasuryError::InvalidBudget);
+        }
+        budget.allocated = allocated;
+
+        emit!(CategoryBudgetSet {
+            category,
+            asset,
+            allocated,
+            spent: budget.spent,
+        });
+        msg!("Бюджет категории {:?}: {} (потрачено {})", category, allocated, budget.spent);
+        Ok(())
+    }
+
+    /// Расход VC или VG казны в рамках бюджета категории. Только исполнитель governance.
+    /// Для VG аккаунты Transfer Hook передаются через remaining_accounts.
+    pub fn spend_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, SpendTokens<'info>>,
+        category: BudgetCategory,
+        amount: u64,
+    ) -> Result<()> {
+        let budget_remaining = charge_budget(&mut ctx.accounts.category_budget, amount)?;
+
+        let treasury_seeds = &[TREASURY_SEED, &[ctx.bumps.treasury]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.treasury_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.treasury.to_account_info(),
+                },
+                &[&treasury_seeds[..]],
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        emit!(TreasuryOutflow {
+            category,
+            asset: ctx.accounts.mint.key(),
+            recipient: ctx.accounts.recipient_token_account.key(),
+            amount,
+            budget_remaining,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Казна: расход {} токенов {} по категории {:?}", amount, ctx.accounts.mint.key(), category);
+        Ok(())
+    }
+
+    /// Расход SOL казны в рамках бюджета категории. Только исполнитель governance.
+    pub fn spend_sol(ctx: Context<SpendSol>, category: BudgetCategory, amount: u64) -> Result<()> {
+        // PDA казны - системный аккаунт без данных: остаток либо 0, либо не ниже ренты
+        let balance = ctx.accounts.treasury.lamports();
+        let left = balance.checked_sub(amount).ok_or(TreasuryError::InsufficientFunds)?;
+        if left != 0 && left < Rent::get()?.minimum_balance(0) {
+            return err!(TreasuryError::InsufficientFunds);
+        }
+
+        let budget_remaining = charge_budget(&mut ctx.accounts.category_budget, amount)?;
+
+        let treasury_seeds = &[TREASURY_SEED, &[ctx.bumps.treasury]];
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    to: ctx.accounts.recipient.to_account_info(),
+                },
+                &[&treasury_seeds[..]],
+            ),
+            amount,
+        )?;
+
+        emit!(TreasuryOutflow {
+            category,
+            asset: SOL_ASSET,
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+            budget_remaining,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Казна: расход {} lamports по категории {:?}", amount, category);
+        Ok(())
+    }
+
+    /// Сожженный VC экосистемы (только чтение). Казна и Burn and Earn ведут счетчики
+    /// раздельно: каждая программа обновляет только свое состояние, а общий объем -
+    /// их сумма. Результат возвращается через return data.
+    pub fn get_vc_burned(ctx: Context<GetVcBurned>) -> Result<VcBurnedReport> {
+        let treasury_vc_burned = ctx.accounts.treasury_state.total_vc_burned;
+        let burn_and_earn_vc_burned = ctx.accounts.burn_and_earn_state.total_vc_burned;
+        let total_vc_burned = treasury_vc_burned
+            .checked_add(burn_and_earn_vc_burned)
+            .ok_or(TreasuryError::MathOverflow)?;
+        msg!("Сожжено VC: {} (казна {}, Burn and Earn {})", total_vc_burned, treasury_vc_burned, burn_and_earn_vc_burned);
+        Ok(VcBurnedReport {
+            treasury_vc_burned,
+            burn_and_earn_vc_burned,
+            total_vc_burned,
+        })
+    }
+}
+
+/// Списывает `amount` с бюджета категории и возвращает остаток бюджета
+fn charge_budget(budget: &mut CategoryBudget, amount: u64) -> Result<u64> {
+    if amount == 0 {
+        return err!(TreasuryError::InvalidAmount);
+    }
+    let spent = budget.spent.checked_add(amount).ok_or(TreasuryError::MathOverflow)?;
+    if spent > budget.allocated {
+        return err!(TreasuryError::BudgetExceeded);
+    }
+    budget.spent = spent;
+    Ok(budget.allocated - spent)
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// DAO (авторитет реестра)
+    #[account(address = registry.authority @ TreasuryError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TreasuryState::INIT_SPACE,
+        seeds = [TREASURY_STATE_SEED],
+        bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    /// CHECK: PDA без данных, владелец токен-аккаунтов казны
+    #[account(seeds = [TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BuybackAndBurnVc<'info> {
+    /// DAO (авторитет реестра) одобряет сумму выкупа и платит за создание ATA казны
+    #[account(mut, address = registry.authority @ TreasuryError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ TreasuryError::ProgramPaused,
+    )]
+    pub registry: Box<Account<'info, EcosystemRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_STATE_SEED],
+        bump = treasury_state.bump,
+        constraint = treasury_state.version == TreasuryState::VERSION @ TreasuryError::AccountNeedsMigration,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    /// CHECK: PDA без данных, владелец токен-аккаунтов казны; подписывает обмены и сжигание
+    #[account(seeds = [TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        address = registry.vg_mint @ TreasuryError::InvalidMint,
+        mint::token_program = vg_token_program,
+    )]
+    pub vg_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        address = registry.vc_mint @ TreasuryError::InvalidMint,
+        mint::token_program = vc_token_program,
+    )]
+    pub vc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Маршрут выкупа, заданный DAO
+    #[account(
+        seeds = [TREASURY_BUYBACK_ROUTE_SEED],
+        bump = buyback_route.bump,
+        constraint = buyback_route.version == BuybackRoute::VERSION @ TreasuryError::AccountNeedsMigration,
+    )]
+    pub buyback_route: Box<Account<'info, BuybackRoute>>,
+
+    /// Промежуточный токен маршрута (например, wSOL)
+    #[account(
+        address = buyback_route.route.intermediate_mint @ TreasuryError::InvalidMint,
+        mint::token_program = intermediate_token_program,
+    )]
+    pub intermediate_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = vg_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = vg_token_program,
+    )]
+    pub treasury_vg_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = dao_authority,
+        associated_token::mint = intermediate_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = intermediate_token_program,
+    )]
+    pub treasury_intermediate_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = dao_authority,
+        associated_token::mint = vc_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = vc_token_program,
+    )]
+    pub treasury_vc_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Программа AMM-адаптера из маршрута выкупа
+    #[account(executable, address = buyback_route.route.amm_program @ TreasuryError::InvalidAmmAccount)]
+    pub amm_program: UncheckedAccount<'info>,
+
+    /// CHECK: Пул VG / промежуточный токен из маршрута выкупа
+    #[account(mut, address = buyback_route.route.vg_pool @ TreasuryError::InvalidAmmAccount)]
+    pub vg_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Хранилище VG пула VG из маршрута выкупа
+    #[account(mut, address = buyback_route.route.vg_pool_vg_vault @ TreasuryError::InvalidAmmAccount)]
+    pub vg_pool_vg_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Хранилище промежуточного токена пула VG из маршрута выкупа
+    #[account(mut, address = buyback_route.route.vg_pool_intermediate_vault @ TreasuryError::InvalidAmmAccount)]
+    pub vg_pool_intermediate_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Пул промежуточный токен / VC из маршрута выкупа
+    #[account(mut, address = buyback_route.route.vc_pool @ TreasuryError::InvalidAmmAccount)]
+    pub vc_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Хранилище промежуточного токена пула VC из маршрута выкупа
+    #[account(mut, address = buyback_route.route.vc_pool_intermediate_vault @ TreasuryError::InvalidAmmAccount)]
+    pub vc_pool_intermediate_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Хранилище VC пула VC из маршрута выкупа
+    #[account(mut, address = buyback_route.route.vc_pool_vc_vault @ TreasuryError::InvalidAmmAccount)]
+    pub vc_pool_vc_vault: UncheckedAccount<'info>,
+
+    pub vg_token_program: Interface<'info, TokenInterface>,
+    pub intermediate_token_program: Interface<'info, TokenInterface>,
+    pub vc_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetBuybackRoute<'info> {
+    /// DAO (авторитет реестра); платит за создание маршрута
+    #[account(mut, address = registry.authority @ TreasuryError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = dao_authority,
+        space = 8 + BuybackRoute::INIT_SPACE,
+        seeds = [TREASURY_BUYBACK_ROUTE_SEED],
+        bump
+    )]
+    pub buyback_route: Account<'info, BuybackRoute>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGovernanceExecutor<'info> {
+    /// DAO (авторитет реестра)
+    #[account(address = registry.authority @ TreasuryError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_STATE_SEED],
+        bump = treasury_state.bump,
+        constraint = treasury_state.version == TreasuryState::VERSION @ TreasuryError::AccountNeedsMigration,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+}
+
+#[derive(Accounts)]
+#[instruction(category: BudgetCategory, asset: Pubkey)]
+pub struct SetCategoryBudget<'info> {
+    /// DAO (авторитет реестра); платит за создание бюджета
+    #[account(mut, address = registry.authority @ TreasuryError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = dao_authority,
+        space = 8 + CategoryBudget::INIT_SPACE,
+        seeds = [TREASURY_BUDGET_SEED, &[category as u8], asset.as_ref()],
+        bump
+    )]
+    pub category_budget: Account<'info, CategoryBudget>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(category: BudgetCategory)]
+pub struct SpendTokens<'info> {
+    #[account(address = treasury_state.governance_executor @ TreasuryError::Unauthorized)]
+    pub governance_executor: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ TreasuryError::ProgramPaused,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        seeds = [TREASURY_STATE_SEED],
+        bump = treasury_state.bump,
+        constraint = treasury_state.version == TreasuryState::VERSION @ TreasuryError::AccountNeedsMigration,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_BUDGET_SEED, &[category as u8], mint.key().as_ref()],
+        bump = category_budget.bump,
+    )]
+    pub category_budget: Account<'info, CategoryBudget>,
+
+    /// CHECK: PDA без данных, владелец токен-аккаунтов казны; подписывает перевод
+    #[account(seeds = [TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+        constraint = mint.key() == registry.vc_mint || mint.key() == registry.vg_mint @ TreasuryError::InvalidAsset,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(category: BudgetCategory)]
+pub struct SpendSol<'info> {
+    #[account(address = treasury_state.governance_executor @ TreasuryError::Unauthorized)]
+    pub governance_executor: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ TreasuryError::ProgramPaused,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        seeds = [TREASURY_STATE_SEED],
+        bump = treasury_state.bump,
+        constraint = treasury_state.version == TreasuryState::VERSION @ TreasuryError::AccountNeedsMigration,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_BUDGET_SEED, &[category as u8], SOL_ASSET.as_ref()],
+        bump = category_budget.bump,
+    )]
+    pub category_budget: Account<'info, CategoryBudget>,
+
+    /// CHECK: PDA без данных, хранит SOL казны; подписывает перевод
+    #[account(mut, seeds = [TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Получатель SOL, любой аккаунт
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetVcBurned<'info> {
+    #[account(
+        seeds = [TREASURY_STATE_SEED],
+        bump = treasury_state.bump,
+        constraint = treasury_state.version == TreasuryState::VERSION @ TreasuryError::AccountNeedsMigration,
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    #[account(
+        seeds = [BURN_AND_EARN_STATE_SEED],
+        bump = burn_and_earn_state.bump,
+        seeds::program = burn_and_earn::ID,
+        constraint = burn_and_earn_state.version == BurnAndEarnState::VERSION @ TreasuryError::AccountNeedsMigration,
+    )]
+    pub burn_and_earn_state: Account<'info, BurnAndEarnState>,
+}
+
+/// Категории расходов казны
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum BudgetCategory {
+    Development,
+    Marketing,
+    Liquidity,
+}
+
+// Новые поля добавляются только в конец структуры, а VERSION увеличивается
+#[account]
+#[derive(InitSpace)]
+pub struct TreasuryState {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    /// Всего VG казны, обмененных при выкупе
+    pub total_vg_swapped: u64,
+    /// Всего VC, выкупленных и сожженных казной; BurnAndEarnState.total_vc_burned
+    /// ведется отдельно, общий объем возвращает get_vc_burned
+    pub total_vc_burned: u64,
+    /// Bump для PDA
+    pub bump: u8,
+    /// Исполнитель governance: единственный, кому разрешены расходы казны
+    pub governance_executor: Pubkey,
+}
+
+impl TreasuryState {
+    pub const VERSION: u8 = 1;
+}
+
+/// Аккаунты AMM, через которые казна выкупает VC
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BuybackRouteParams {
+    /// Программа AMM-адаптера
+    pub amm_program: Pubkey,
+    /// Промежуточный токен маршрута
+    pub intermediate_mint: Pubkey,
+    /// Пул VG / промежуточный токен
+    pub vg_pool: Pubkey,
+    pub vg_pool_vg_vault: Pubkey,
+    pub vg_pool_intermediate_vault: Pubkey,
+    /// Пул промежуточный токен / VC
+    pub vc_pool: Pubkey,
+    pub vc_pool_intermediate_vault: Pubkey,
+    pub vc_pool_vc_vault: Pubkey,
+}
+
+/// Маршрут выкупа VC, заданный DAO
+#[account]
+#[derive(InitSpace)]
+pub struct BuybackRoute {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    pub route: BuybackRouteParams,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl BuybackRoute {
+    pub const VERSION: u8 = 1;
+}
+
+/// Бюджет категории расходов для одного актива
+#[account]
+#[derive(InitSpace)]
+pub struct CategoryBudget {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    pub category: BudgetCategory,
+    /// Минт VC/VG или SOL_ASSET
+    pub asset: Pubkey,
+    /// Общий лимит расходов категории
+    pub allocated: u64,
+    /// Уже потрачено
+    pub spent: u64,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl CategoryBudget {
+    pub const VERSION: u8 = 1;
+}
+
+/// Сожженный VC экосистемы, возвращаемый get_vc_burned через return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VcBurnedReport {
+    /// VC, выкупленные и сожженные казной
+    pub treasury_vc_burned: u64,
+    /// VC, сожженные пользователями в burn_and_lock
+    pub burn_and_earn_vc_burned: u64,
+    /// Общий объем сожженного VC
+    pub total_vc_burned: u64,
+}
+
+#[event]
+pub struct VcBoughtBackAndBurned {
+    pub vg_amount: u64,
+    pub intermediate_amount: u64,
+    pub vc_burned: u64,
+    pub total_vc_burned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryOutflow {
+    pub category: BudgetCategory,
+    pub asset: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub budget_remaining: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CategoryBudgetSet {
+    pub category: BudgetCategory,
+    pub asset: Pubkey,
+    pub allocated: u64,
+    pub spent: u64,
+}
+
+#[error_code]
+pub enum TreasuryError {
+    #[msg("Операция не авторизована")]
+    Unauthorized,
+    #[msg("Сумма не может быть нулевой")]
+    InvalidAmount,
+    #[msg("Минт не совпадает с реестром или маршрутом выкупа")]
+    InvalidMint,
+    #[msg("Выход первого плеча (VG -> промежуточный токен) меньше минимального")]
+    IntermediateSlippageExceeded,
+    #[msg("Выход второго плеча (промежуточный токен -> VC) меньше минимального")]
+    VcSlippageExceeded,
+    #[msg("Экосистема на аварийной паузе")]
+    ProgramPaused,
+    #[msg("Казна хранит только VC, VG и SOL")]
+    InvalidAsset,
+    #[msg("Бюджет не может быть меньше уже потраченного")]
+    InvalidBudget,
+    #[msg("Расход превышает бюджет категории")]
+    BudgetExceeded,
+    #[msg("Недостаточно средств казны")]
+    InsufficientFunds,
+    #[msg("Аккаунт использует устаревшую раскладку, требуется миграция")]
+    AccountNeedsMigration,
+    #[msg("Произошло переполнение при математических операциях")]
+    MathOverflow,
+    #[msg("Аккаунт AMM не совпадает с маршрутом выкупа")]
+    InvalidAmmAccount,
+}
@@ -1,47 +1,144 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
 use anchor_spl::{
-    token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface},
-    associated_token::AssociatedToken,
+    token::Token,
+    token_2022::Token2022,
+    token_interface::{self, Mint, MintTo, TokenInterface},
+    associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken},
     metadata::{mpl_token_metadata},
 };
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{BaseStateWithExtensions, StateWithExtensions, metadata_pointer::MetadataPointer},
+    state::Mint as Token2022Mint,
+};
+use tech_hy_contracts::{EcosystemRegistry, ECOSYSTEM_REGISTRY_SEED, ID as REGISTRY_PROGRAM_ID};
 
 declare_id!("VCzfGwp5qVL8pmta1GHqGrSQqzMa5qsY4M1jbjsdaYJ");
 
-// Константы токена
-pub const VC_TOKEN_MINT_SEED: &[u8] = b"vc_token_mint";
-pub const TOKEN_DECIMALS: u8 = 9;
-pub const TOTAL_SUPPLY: u64 = 5_000_000_000 * 10u64.pow(TOKEN_DECIMALS as u32); // 5 миллиардов токенов с 9 десятичными знаками
+// Константы токена (общие для экосистемы, см. tech-hy-common)
+pub use tech_hy_common::{TOKEN_DECIMALS, VC_CONFIG_SEED, VC_TOKEN_MINT_SEED};
+use tech_hy_common::{
+    initialize_token_metadata, MetadataFieldError, MintAuditExpectation, MintAuditReport, TokenMetadataFields,
+    METADATA_KEY_ECOSYSTEM_ROLE, METADATA_KEY_TAX_RATE_BPS, METADATA_KEY_TOTAL_SUPPLY, METADATA_SEED,
+    VC_ECOSYSTEM_ROLE,
+};
+pub const TOTAL_SUPPLY: u64 = tech_hy_common::VC_TOTAL_SUPPLY; // 5 миллиардов токенов с 9 десятичными знаками
 
 #[program]
 pub mod vc_token {
     use super::*;
 
-    /// Инициализирует новый VC токен с заданными параметрами
-    /// Выпускает все токены в размере 5 миллиардов и передает их на кошелек казны DAO
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+    /// Инициализирует новый VC токен и распределяет всю эмиссию (5 миллиардов) по таблице
+    /// распределения. Сумма долей должна быть равна TOTAL_SUPPLY.
+    /// Для каждой записи через remaining_accounts передается пара
+    /// [владелец получателя, ATA получателя]; ATA создается в этой же транзакции.
+    /// Вызвать может только авторитет реестра экосистемы (DAO), он же сохраняется как
+    /// авторитет VC. Следом в той же транзакции должна идти
+    /// set_metadata: она создает метаданные и отзывает mint authority.
+    pub fn initialize<'info>(
+        ctx: Context<'_, '_, '_, 'info, Initialize<'info>>,
+        distribution: Vec<DistributionEntry>,
+    ) -> Result<()> {
         msg!("Инициализация VC токена...");
 
-        // Создание минта и выпуск всех токенов - оптимизированная версия
+        // Метаданные можно создать только пока жива mint authority, поэтому set_metadata
+        // обязана идти в этой же транзакции и закрыть окно, отозвав mint authority
+        if !has_sibling_instruction(
+            &ctx.accounts.instructions,
+            crate::instruction::SetMetadata::DISCRIMINATOR,
+            SiblingPosition::After,
+        )? {
+            return err!(VcTokenError::MetadataInstructionMissing);
+        }
+
+        let vc_config = &mut ctx.accounts.vc_config;
+        vc_config.version = VcConfig::VERSION;
+        vc_config.authority = ctx.accounts.authority.key();
+        vc_config.metadata_initialized = false;
+        vc_config.bump = ctx.bumps.vc_config;
+
         let seeds = &[
-            VC_TOKEN_MINT_SEED, 
+            VC_TOKEN_MINT_SEED,
             &[ctx.bumps.mint]
         ];
-        let _signer_seeds = &[&seeds[..]];  // Префикс _ для неиспользуемых переменных
-        
-        // Оптимизация: создаем CPI контекст только один раз
-        let cpi_context = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            MintTo {
-                mint: ctx.accounts.mint.to_account_info(),
-                to: ctx.accounts.treasury_token_account.to_account_info(),
-                authority: ctx.accounts.mint.to_account_info(),
-            },
-            _signer_seeds
+        distribute_supply(
+            &distribution,
+            ctx.remaining_accounts,
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.associated_token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[&seeds[..]],
+        )?;
+
+        msg!(
+            "VC токен успешно инициализирован: {} токенов распределено по {} получателям",
+            TOTAL_SUPPLY,
+            distribution.len()
         );
-        
-        // Выполняем минтинг всех токенов
-        token_interface::mint_to(cpi_context, TOTAL_SUPPLY)?;
-        
+
+        Ok(())
+    }
+
+    /// Альтернативная инициализация VC как минта Token-2022 с расширениями MetadataPointer
+    /// и TokenMetadata: метаданные хранятся в самом минте вместо Metaplex, роль в экосистеме,
+    /// налог и эмиссия записываются дополнительными полями. Эмиссия распределяется так же,
+    /// как в initialize, mint authority отзывается в этой же инструкции. Как и initialize,
+    /// доступна только авторитету реестра, который становится update authority метаданных.
+    pub fn initialize_with_token_metadata<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeWithTokenMetadata<'info>>,
+        distribution: Vec<DistributionEntry>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        msg!("Инициализация VC токена (Token-2022 + TokenMetadata)...");
+        validate_metadata_fields(&name, &symbol, &uri)?;
+
+        let vc_config = &mut ctx.accounts.vc_config;
+        vc_config.version = VcConfig::VERSION;
+        vc_config.authority = ctx.accounts.authority.key();
+        vc_config.metadata_initialized = true;
+        vc_config.bump = ctx.bumps.vc_config;
+
+        let seeds = &[
+            VC_TOKEN_MINT_SEED,
+            &[ctx.bumps.mint]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        initialize_token_metadata(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            TokenMetadataFields {
+                name,
+                symbol,
+                uri,
+                additional_metadata: vec![
+                    (METADATA_KEY_ECOSYSTEM_ROLE.to_string(), VC_ECOSYSTEM_ROLE.to_string()),
+                    (METADATA_KEY_TAX_RATE_BPS.to_string(), "0".to_string()),
+                    (METADATA_KEY_TOTAL_SUPPLY.to_string(), TOTAL_SUPPLY.to_string()),
+                ],
+                update_authority: ctx.accounts.authority.key(),
+            },
+            signer_seeds,
+        )?;
+
+        distribute_supply(
+            &distribution,
+            ctx.remaining_accounts,
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.associated_token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            signer_seeds,
+        )?;
+
         // Отзываем mint_authority у PDA mint, чтобы дальнейший минтинг был невозможен
         token_interface::set_authority(
             CpiContext::new_with_signer(
@@ -50,18 +147,23 @@ pub mod vc_token {
                     current_authority: ctx.accounts.mint.to_account_info(),
                     account_or_mint: ctx.accounts.mint.to_account_info(),
                 },
-                _signer_seeds // Используем те же seeds для подписи от имени PDA mint
+                signer_seeds
             ),
             token_interface::spl_token_2022::instruction::AuthorityType::MintTokens,
-            None // Устанавливаем нового mint authority в None
+            None
         )?;
-        
-        msg!("VC токен успешно инициализирован с полной эмиссией {} токенов и отозванной mint authority", TOTAL_SUPPLY);
-        
+
+        msg!(
+            "VC токен (Token-2022) инициализирован: {} токенов распределено по {} получателям, mint authority отозвана",
+            TOTAL_SUPPLY,
+            distribution.len()
+        );
         Ok(())
     }
-    
-    /// Установка метаданных токена с использованием Metaplex
+
+    /// Установка метаданных токена с использованием Metaplex.
+    /// Только авторитет VC и только в одной транзакции с initialize; после создания
+    /// метаданных mint authority отзывается, и повторный вызов отклоняется.
     pub fn set_metadata(
         ctx: Context<SetMetadata>, 
         name: String, 
@@ -69,6 +171,21 @@ pub mod vc_token {
         uri: String
     ) -> Result<()> {
         msg!("Установка метаданных токена: {}, {}, {}", name, symbol, uri);
+        validate_metadata_fields(&name, &symbol, &uri)?;
+
+        if ctx.accounts.vc_config.metadata_initialized {
+            return err!(VcTokenError::MetadataWindowClosed);
+        }
+        if !has_sibling_instruction(
+            &ctx.accounts.instructions,
+            crate::instruction::Initialize::DISCRIMINATOR,
+            SiblingPosition::Before,
+        )? {
+            return err!(VcTokenError::MetadataWindowClosed);
+        }
+        if !ctx.accounts.metadata.data_is_empty() {
+            return err!(VcTokenError::MetadataAlreadyExists);
+        }
 
         // Оптимизированная версия установки метаданных
         let seeds = &[
@@ -81,6 +198,7 @@ pub mod vc_token {
         let metadata = ctx.accounts.metadata.to_account_info();
         let mint = ctx.accounts.mint.to_account_info();
         let payer = ctx.accounts.payer.to_account_info();
+        let authority = ctx.accounts.authority.to_account_info();
         let system_program = ctx.accounts.system_program.to_account_info();
         let rent = ctx.accounts.rent.to_account_info();
 
@@ -101,7 +219,7 @@ pub mod vc_token {
             mint: ctx.accounts.mint.key(),
             mint_authority: ctx.accounts.mint.key(),
             payer: ctx.accounts.payer.key(),
-            update_authority: (ctx.accounts.payer.key(), true),
+            update_authority: (ctx.accounts.authority.key(), true),
             system_program: ctx.accounts.system_program.key(),
             rent: Some(ctx.accounts.rent.key()),
         }
@@ -117,7 +235,7 @@ pub mod vc_token {
             mint.clone(),
             payer.clone(),
             mint, // mint authority
-            payer, // update authority
+            authority, // update authority
             system_program,
             rent,
         ];
@@ -128,48 +246,319 @@ pub mod vc_token {
             &[&seeds[..]],
         )?;
 
-        msg!("Метаданные токена успешно установлены");
+        // Отзываем mint_authority у PDA mint, чтобы дальнейший минтинг был невозможен
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::SetAuthority {
+                    current_authority: ctx.accounts.mint.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                _signer_seeds // Используем те же seeds для подписи от имени PDA mint
+            ),
+            token_interface::spl_token_2022::instruction::AuthorityType::MintTokens,
+            None // Устанавливаем нового mint authority в None
+        )?;
+        ctx.accounts.vc_config.metadata_initialized = true;
+
+        msg!("Метаданные токена успешно установлены, mint authority отозвана");
         Ok(())
     }
+
+    /// Аудит минта VC (только чтение): эмиссия равна TOTAL_SUPPLY, mint и freeze authority
+    /// отсутствуют, десятичные знаки и программа-владелец ожидаемые (SPL Token, либо
+    /// Token-2022 для минта, инициализированного с TokenMetadata).
+    /// Отчет возвращается через return data, инструкция не падает на непройденных проверках.
+    pub fn audit_mint(ctx: Context<AuditMint>) -> Result<MintAuditReport> {
+        let mint = &ctx.accounts.mint;
+        let report = MintAuditReport::build(
+            mint.key(),
+            *mint.to_account_info().owner,
+            mint.supply,
+            mint.decimals,
+            mint.mint_authority.into(),
+            mint.freeze_authority.into(),
+            &MintAuditExpectation {
+                total_supply: TOTAL_SUPPLY,
+                decimals: TOKEN_DECIMALS,
+                owner_program: if has_self_metadata_pointer(&mint.to_account_info()) {
+                    Token2022::id()
+                } else {
+                    Token::id()
+                },
+            },
+        );
+
+        msg!("Аудит минта VC: {}", if report.passed { "пройден" } else { "не пройден" });
+        Ok(report)
+    }
+}
+
+/// Проверяет таблицу распределения и минтит доли получателям.
+/// `recipients` - пары [владелец получателя, ATA получателя] в порядке записей таблицы,
+/// ATA создаются в этой же транзакции, если их еще нет.
+#[allow(clippy::too_many_arguments)]
+fn distribute_supply<'info>(
+    distribution: &[DistributionEntry],
+    recipients: &[AccountInfo<'info>],
+    mint: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if distribution.is_empty() || recipients.len() != distribution.len() * 2 {
+        return err!(VcTokenError::InvalidDistribution);
+    }
+
+    // Проверяем таблицу до любых CPI: нет нулевых долей и повторов, сумма равна эмиссии
+    let mut total: u64 = 0;
+    for (i, entry) in distribution.iter().enumerate() {
+        if entry.amount == 0 || distribution[..i].iter().any(|e| e.recipient == entry.recipient) {
+            return err!(VcTokenError::InvalidDistribution);
+        }
+        total = total.checked_add(entry.amount).ok_or(VcTokenError::MathOverflow)?;
+    }
+    if total != TOTAL_SUPPLY {
+        msg!("Сумма распределения {} не равна эмиссии {}", total, TOTAL_SUPPLY);
+        return err!(VcTokenError::InvalidDistribution);
+    }
+
+    for (entry, accounts) in distribution.iter().zip(recipients.chunks(2)) {
+        let recipient = &accounts[0];
+        let recipient_token_account = &accounts[1];
+
+        if recipient.key() != entry.recipient
+            || recipient_token_account.key()
+                != get_associated_token_address_with_program_id(&entry.recipient, mint.key, token_program.key)
+        {
+            return err!(VcTokenError::InvalidDistributionAccount);
+        }
+
+        // Создаем ATA получателя, если его еще нет
+        associated_token::create_idempotent(CpiContext::new(
+            associated_token_program.clone(),
+            associated_token::Create {
+                payer: payer.clone(),
+                associated_token: recipient_token_account.clone(),
+                authority: recipient.clone(),
+                mint: mint.clone(),
+                system_program: system_program.clone(),
+                token_program: token_program.clone(),
+            },
+        ))?;
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                MintTo {
+                    mint: mint.clone(),
+                    to: recipient_token_account.clone(),
+                    authority: mint.clone(),
+                },
+                signer_seeds
+            ),
+            entry.amount,
+        )?;
+        msg!("Распределено {} VC на {}", entry.amount, entry.recipient);
+    }
+    Ok(())
+}
+
+/// Проверяет поля метаданных (см. tech_hy_common::validate_metadata_fields)
+fn validate_metadata_fields(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    tech_hy_common::validate_metadata_fields(name, symbol, uri).map_err(|field| match field {
+        MetadataFieldError::Name => error!(VcTokenError::InvalidMetadataName),
+        MetadataFieldError::Symbol => error!(VcTokenError::InvalidMetadataSymbol),
+        MetadataFieldError::Uri => error!(VcTokenError::InvalidMetadataUri),
+    })
+}
+
+/// Минт Token-2022, чей MetadataPointer указывает на него самого (путь initialize_with_token_metadata)
+fn has_self_metadata_pointer(mint: &AccountInfo) -> bool {
+    if mint.owner != &Token2022::id() {
+        return false;
+    }
+    let Ok(data) = mint.try_borrow_data() else {
+        return false;
+    };
+    StateWithExtensions::<Token2022Mint>::unpack(&data)
+        .ok()
+        .and_then(|state| {
+            state
+                .get_extension::<MetadataPointer>()
+                .ok()
+                .and_then(|pointer| Option::<Pubkey>::from(pointer.metadata_address))
+        })
+        == Some(mint.key())
+}
+
+/// Положение искомой инструкции относительно текущей
+enum SiblingPosition {
+    Before,
+    After,
+}
+
+/// Проверяет, есть ли в текущей транзакции инструкция этой программы с заданным
+/// дискриминатором до или после текущей инструкции.
+fn has_sibling_instruction(
+    instructions: &AccountInfo,
+    discriminator: &[u8],
+    position: SiblingPosition,
+) -> Result<bool> {
+    let current = load_current_index_checked(instructions)? as usize;
+    let mut index = match position {
+        SiblingPosition::Before => 0,
+        SiblingPosition::After => current + 1,
+    };
+    loop {
+        if matches!(position, SiblingPosition::Before) && index >= current {
+            return Ok(false);
+        }
+        let Ok(ix) = load_instruction_at_checked(index, instructions) else {
+            return Ok(false);
+        };
+        if ix.program_id == crate::ID && ix.data.starts_with(discriminator) {
+            return Ok(true);
+        }
+        index += 1;
+    }
+}
+
+/// Конфигурация VC токена: авторитет (деплоер/DAO) и состояние окна установки метаданных
+#[account]
+#[derive(InitSpace)]
+pub struct VcConfig {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    /// Авторитет, который может устанавливать метаданные
+    pub authority: Pubkey,
+    /// Метаданные созданы, mint authority отозвана
+    pub metadata_initialized: bool,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl VcConfig {
+    pub const VERSION: u8 = 1;
+}
+
+/// Запись таблицы распределения эмиссии VC
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DistributionEntry {
+    /// Владелец токен-аккаунта получателя
+    pub recipient: Pubkey,
+    /// Количество токенов (в минимальных единицах)
+    pub amount: u64,
 }
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    
+
+    /// Авторитет реестра экосистемы (DAO); становится авторитетом VC
+    #[account(address = registry.authority @ VcTokenError::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = TOKEN_DECIMALS,
+        mint::authority = mint,
+        // Важно: отсутствие freeze_authority не позволит замораживать кошельки
+        seeds = [VC_TOKEN_MINT_SEED],
+        bump
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VcConfig::INIT_SPACE,
+        seeds = [VC_CONFIG_SEED],
+        bump
+    )]
+    pub vc_config: Account<'info, VcConfig>,
+
+    pub token_program: Program<'info, Token>, // VC - классический SPL Token; Token-2022 только через initialize_with_token_metadata
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Sysvar инструкций, адрес проверяется
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    // Пары [владелец получателя, ATA получателя] для каждой записи распределения
+    // передаются через remaining_accounts
+}
+
+#[derive(Accounts)]
+pub struct InitializeWithTokenMetadata<'info> {
     #[account(mut)]
-    /// Адрес казначейства DAO, которое получит все выпущенные токены
-    pub treasury: SystemAccount<'info>,
+    pub payer: Signer<'info>,
+
+    /// Авторитет реестра экосистемы (DAO); становится авторитетом VC
+    #[account(address = registry.authority @ VcTokenError::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
 
     #[account(
         init,
         payer = payer,
         mint::decimals = TOKEN_DECIMALS,
         mint::authority = mint,
+        mint::token_program = token_program,
         // Важно: отсутствие freeze_authority не позволит замораживать кошельки
+        extensions::metadata_pointer::authority = authority,
+        extensions::metadata_pointer::metadata_address = mint,
         seeds = [VC_TOKEN_MINT_SEED],
         bump
     )]
     pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
-        init_if_needed,
+        init,
         payer = payer,
-        associated_token::mint = mint,
-        associated_token::authority = treasury,
+        space = 8 + VcConfig::INIT_SPACE,
+        seeds = [VC_CONFIG_SEED],
+        bump
     )]
-    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub vc_config: Account<'info, VcConfig>,
 
-    pub token_program: Interface<'info, TokenInterface>,
+    pub token_program: Program<'info, Token2022>, // Расширения минта поддерживает только Token-2022
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+    // Пары [владелец получателя, ATA получателя] для каждой записи распределения
+    // передаются через remaining_accounts
 }
 
 #[derive(Accounts)]
 pub struct SetMetadata<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VC_CONFIG_SEED],
+        bump = vc_config.bump,
+        has_one = authority @ VcTokenError::Unauthorized,
+        constraint = vc_config.version == VcConfig::VERSION @ VcTokenError::AccountNeedsMigration,
+    )]
+    pub vc_config: Account<'info, VcConfig>,
     
     #[account(
         mut,
@@ -184,7 +573,7 @@ pub struct SetMetadata<'info> {
     #[account(
         mut,
         seeds = [
-            b"metadata",
+            METADATA_SEED,
             metadata_program.key().as_ref(),
             mint.key().as_ref(),
         ],
@@ -198,11 +587,25 @@ pub struct SetMetadata<'info> {
     #[account(address = mpl_token_metadata::ID)]
     pub metadata_program: UncheckedAccount<'info>,
     
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     
     /// CHECK: Это известный sysvarcall
     #[account(address = anchor_lang::solana_program::sysvar::rent::ID)]
     pub rent: UncheckedAccount<'info>,
+
+    /// CHECK: Sysvar инструкций, адрес проверяется
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AuditMint<'info> {
+    #[account(
+        seeds = [VC_TOKEN_MINT_SEED],
+        bump,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
 }
 
 // Ошибки программы
@@ -212,4 +615,24 @@ pub enum VcTokenError {
     Unauthorized,
     #[msg("Ошибка при создании или доступе к минту токена")]
     MintError,
+    #[msg("Неверная таблица распределения: пустая, с нулевыми или повторяющимися долями, либо сумма не равна эмиссии")]
+    InvalidDistribution,
+    #[msg("Аккаунты получателя не соответствуют таблице распределения")]
+    InvalidDistributionAccount,
+    #[msg("Произошло переполнение при математических операциях")]
+    MathOverflow,
+    #[msg("initialize должна сопровождаться set_metadata в той же транзакции")]
+    MetadataInstructionMissing,
+    #[msg("Метаданные устанавливаются только в одной транзакции с initialize")]
+    MetadataWindowClosed,
+    #[msg("Метаданные токена уже существуют")]
+    MetadataAlreadyExists,
+    #[msg("Аккаунт использует устаревшую раскладку, требуется миграция")]
+    AccountNeedsMigration,
+    #[msg("Имя токена пустое или длиннее допустимого")]
+    InvalidMetadataName,
+    #[msg("Символ токена должен состоять из заглавных латинских букв и цифр и не превышать допустимую длину")]
+    InvalidMetadataSymbol,
+    #[msg("URI метаданных должен начинаться с https:// и не превышать допустимую длину")]
+    InvalidMetadataUri,
 } 
\ No newline at end of file
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::TokenInterface};
+use vg_token::cpi_helpers::{self, TransferWithTax};
+use vg_token::program::VgToken;
+use vg_token::{TaxConfig, TAX_CONFIG_SEED};
+
+declare_id!("GDEtj2QxGEsZicE94uEsihRbFUNUApVUaj4nJFSH3WZ9");
+
+pub const VAULT_SEED: &[u8] = b"vault";
+
+/// Пример партнерской программы: VG хранятся на ATA PDA [VAULT_SEED, owner],
+/// а выплаты идут через CPI в vg-token `transfer_with_tax` с подписью PDA,
+/// поэтому налог уходит в казну DAO и держателям NFT Fee Key как при обычном переводе.
+/// PDA хранилища - системный аккаунт без данных; его лампорты оплачивают создание
+/// токен-аккаунтов, которые vg-token создает при переводе.
+#[program]
+pub mod vg_cpi_guide {
+    use super::*;
+
+    /// Выплата `amount` VG из хранилища владельца. Аккаунты Transfer Hook VG
+    /// передаются через remaining_accounts и пробрасываются в vg-token.
+    pub fn pay_from_vault<'info>(
+        ctx: Context<'_, '_, '_, 'info, PayFromVault<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        // Разбивка считается до CPI той же функцией, что использует vg-token
+        let quote = cpi_helpers::quote_tax(amount, &ctx.accounts.tax_config)?;
+
+        let owner = ctx.accounts.owner.key();
+        let vault_seeds = &[VAULT_SEED, owner.as_ref(), &[ctx.bumps.vault]];
+        cpi_helpers::transfer_with_tax_signed(
+            ctx.accounts.vg_token_program.to_account_info(),
+            TransferWithTax {
+                sender_authority: ctx.accounts.vault.to_account_info(),
+                tax_config: ctx.accounts.tax_config.to_account_info(),
+                registry: ctx.accounts.registry.to_account_info(),
+                mint_account: ctx.accounts.mint_account.to_account_info(),
+                sender_token_account: ctx.accounts.vault_token_account.to_account_info(),
+                recipient_authority: ctx.accounts.recipient_authority.to_account_info(),
+                recipient_token_account: ctx.accounts.recipient_token_account.to_account_info(),
+                dao_treasury_authority: ctx.accounts.dao_treasury_authority.to_account_info(),
+                dao_treasury_token_account: ctx
+                    .accounts
+                    .dao_treasury_token_account
+                    .to_account_info(),
+                fee_collector_authority: ctx.accounts.fee_collector_authority.to_account_info(),
+                fee_collector_token_account: ctx
+                    .accounts
+                    .fee_collector_token_account
+                    .to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            ctx.remaining_accounts,
+            &[&vault_seeds[..]],
+            amount,
+        )?;
+
+        emit!(VaultPayment {
+            owner,
+            recipient: ctx.accounts.recipient_authority.key(),
+            amount,
+            net_amount: quote.net_amount,
+            tax_amount: quote.tax_amount,
+        });
+        msg!(
+            "Выплата из хранилища: {} VG, получателю {}, налог {}",
+            amount,
+            quote.net_amount,
+            quote.tax_amount
+        );
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct PayFromVault<'info> {
+    pub owner: Signer<'info>,
+
+    /// PDA хранилища владельца; подписывает перевод через CPI
+    #[account(mut, seeds = [VAULT_SEED, owner.key().as_ref()], bump)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(seeds = [TAX_CONFIG_SEED], bump, seeds::program = vg_token::ID)]
+    pub tax_config: Box<Account<'info, TaxConfig>>,
+
+    /// CHECK: Реестр экосистемы, проверяется vg-token
+    pub registry: UncheckedAccount<'info>,
+
+    /// CHECK: Минт VG, проверяется vg-token
+    #[account(mut)]
+    pub mint_account: UncheckedAccount<'info>,
+
+    /// CHECK: ATA хранилища, проверяется vg-token
+    #[account(mut)]
+    pub vault_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Получатель выплаты, любой аккаунт
+    pub recipient_authority: UncheckedAccount<'info>,
+
+    /// CHECK: ATA получателя, создается vg-token при необходимости
+    #[account(mut)]
+    pub recipient_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Казна DAO, сверяется vg-token с реестром
+    pub dao_treasury_authority: UncheckedAccount<'info>,
+
+    /// CHECK: ATA казны DAO, создается vg-token при необходимости
+    #[account(mut)]
+    pub dao_treasury_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Сборщик налога, сверяется vg-token с реестром
+    pub fee_collector_authority: UncheckedAccount<'info>,
+
+    /// CHECK: ATA сборщика налога, создается vg-token при необходимости
+    #[account(mut)]
+    pub fee_collector_token_account: UncheckedAccount<'info>,
+
+    pub vg_token_program: Program<'info, VgToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct VaultPayment {
+    pub owner: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub net_amount: u64,
+    pub tax_amount: u64,
+}
@@ -5,32 +5,46 @@ use anchor_spl::{
     metadata::{mpl_token_metadata},
 };
 use anchor_spl::token_interface::spl_token_2022::instruction::AuthorityType;
-use anchor_spl::token_2022::{self, InitializeMint2, InitializeTransferHook};
-use spl_token_2022::{
-    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions, transfer_hook::TransferHook},
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{BaseStateWithExtensions, StateWithExtensions, transfer_hook::TransferHook},
     state::Mint as Token2022Mint,
 };
+use tech_hy_contracts::{EcosystemRegistry, ECOSYSTEM_REGISTRY_SEED, ID as REGISTRY_PROGRAM_ID};
 
 declare_id!("VGnHJHKr2NwxSdQQoYrJY9TBZ9YHS5cCwBPEr68mEPG");
 
-// Константы токена
-pub const VG_TOKEN_MINT_SEED: &[u8] = b"vg_token_mint";
-pub const TOKEN_DECIMALS: u8 = 9;
-pub const TOTAL_SUPPLY: u64 = 1_000_000_000 * 10u64.pow(TOKEN_DECIMALS as u32); // 1 миллиард токенов с 9 десятичными знаками
+// Константы токена (общие для экосистемы, см. tech-hy-common)
+pub use tech_hy_common::{TAX_CONFIG_SEED, TOKEN_DECIMALS, VG_TOKEN_MINT_SEED};
+use tech_hy_common::{bps_of, is_valid_bps, split_bps, BPS_DENOMINATOR, MintAuditExpectation, MintAuditReport, METADATA_SEED};
+use tech_hy_common::{
+    initialize_token_metadata, MetadataFieldError, TokenMetadataFields, METADATA_KEY_ECOSYSTEM_ROLE,
+    METADATA_KEY_TOTAL_SUPPLY, METADATA_KEY_TRANSFER_HOOK_PROGRAM, VG_ECOSYSTEM_ROLE,
+};
+use tech_hy_common::migration::resize_account;
+pub const TOTAL_SUPPLY: u64 = tech_hy_common::VG_TOTAL_SUPPLY; // 1 миллиард токенов с 9 десятичными знаками
 pub const TAX_RATE_BPS: u16 = 1000; // 10% налог (basis points: 10% = 1000 из 10000)
-pub const DAO_SHARE_BPS: u16 = 500; // 50% от налога идет в казну DAO
-pub const NFT_HOLDERS_SHARE_BPS: u16 = 500; // 50% от налога идет держателям NFT Fee Key
+pub const DAO_SHARE_BPS: u16 = 5000; // 50% от налога идет в казну DAO (5000 из 10000)
+pub const NFT_HOLDERS_SHARE_BPS: u16 = 5000; // 50% от налога идет держателям NFT Fee Key (5000 из 10000)
+/// Максимум получателей batch_transfer_with_tax (ограничен размером транзакции)
+pub const MAX_BATCH_RECIPIENTS: usize = 20;
+
+// ID программы-хука известен только для localnet: сборка для devnet и mainnet
+// появится вместе с развертыванием хука в этих кластерах
+#[cfg(not(feature = "localnet"))]
+compile_error!("VG_TRANSFER_HOOK_PROGRAM_ID задан только для localnet: соберите vg-token с фичей localnet");
 
-/// Placeholder для ID программы, которая будет реализовывать логику Transfer Hook.
-/// Этот ID нужно будет заменить на реальный ID скомпилированной программы-хука.
-declare_id!("HOOKpGhpY123456789abcdefghijklmnopqrstuvw"); 
-pub use HOOKpGhpY123456789abcdefghijklmnopqrstuvw as vg_transfer_hook_program_id;
+/// ID программы Transfer Hook для VG (`vg_transfer_hook_program`), записывается в
+/// расширение TransferHook минта при инициализации. Для localnet это mock-transfer-hook.
+#[cfg(feature = "localnet")]
+pub const VG_TRANSFER_HOOK_PROGRAM_ID: Pubkey = pubkey!("Hkmi3BdVG9SyhxMzmeWSVj1MXUyKnqRtuPmiDuC2fFvk");
 
 #[program]
 pub mod vg_token {
     use super::*;
 
-    /// Инициализирует новый VG токен.
+    /// Инициализирует новый VG токен (Token-2022 с расширением TransferHook,
+    /// указывающим на VG_TRANSFER_HOOK_PROGRAM_ID).
     /// Выпускает все токены в размере 1 миллиарда и передает их на эскроу-счет,
     /// контролируемый программой Burn and Earn.
     /// Отзывает mint authority и не устанавливает freeze authority.
@@ -78,8 +92,83 @@ pub mod vg_token {
         Ok(())
     }
     
+    /// Альтернативная инициализация VG: помимо TransferHook минт получает расширения
+    /// MetadataPointer и TokenMetadata, метаданные хранятся в самом минте вместо Metaplex.
+    /// Роль в экосистеме, эмиссия и хук записываются дополнительными полями; параметры налога
+    /// в метаданные не попадают, их единственный источник - TaxConfig, который меняет DAO.
+    /// Update authority метаданных передается DAO из реестра.
+    pub fn initialize_with_token_metadata(
+        ctx: Context<InitializeWithTokenMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        msg!("Инициализация VG токена (TransferHook + TokenMetadata)...");
+        validate_metadata_fields(&name, &symbol, &uri)?;
+
+        let mint_seeds = &[
+            VG_TOKEN_MINT_SEED,
+            &[ctx.bumps.mint_account]
+        ];
+        let signer_seeds = &[&mint_seeds[..]];
+
+        // 1. Метаданные в минте (пока mint authority у PDA)
+        initialize_token_metadata(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.mint_account.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            TokenMetadataFields {
+                name,
+                symbol,
+                uri,
+                additional_metadata: vec![
+                    (METADATA_KEY_ECOSYSTEM_ROLE.to_string(), VG_ECOSYSTEM_ROLE.to_string()),
+                    (METADATA_KEY_TOTAL_SUPPLY.to_string(), TOTAL_SUPPLY.to_string()),
+                    (METADATA_KEY_TRANSFER_HOOK_PROGRAM.to_string(), VG_TRANSFER_HOOK_PROGRAM_ID.to_string()),
+                ],
+                update_authority: ctx.accounts.dao_authority.key(),
+            },
+            signer_seeds,
+        )?;
+
+        // 2. Минтинг полной эмиссии на эскроу-счет
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.mint_account.to_account_info(),
+                    to: ctx.accounts.escrow_vault_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_account.to_account_info(),
+                },
+                signer_seeds
+            ),
+            TOTAL_SUPPLY
+        )?;
+
+        // 3. Отзыв mint_authority у PDA mint_account
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::SetAuthority {
+                    current_authority: ctx.accounts.mint_account.to_account_info(),
+                    account_or_mint: ctx.accounts.mint_account.to_account_info(),
+                },
+                signer_seeds
+            ),
+            AuthorityType::MintTokens,
+            None
+        )?;
+
+        msg!("VG токен с TokenMetadata инициализирован, mint authority отозвана.");
+        Ok(())
+    }
+
     /// Кастомная инструкция для перевода VG токенов с взиманием 10% налога.
-    pub fn transfer_with_tax(ctx: Context<TransferWithTax>, amount: u64) -> Result<()> {
+    pub fn transfer_with_tax<'info>(
+        ctx: Context<'_, '_, '_, 'info, TransferWithTax<'info>>,
+        amount: u64,
+    ) -> Result<()> {
         msg!("Выполняется transfer_with_tax для {} VG токенов...", amount);
         if amount == 0 {
             return err!(VgTokenError::InvalidAmount);
@@ -96,34 +185,12 @@ pub mod vg_token {
         // или если это специальный адрес из tax_config.
         // Для более корректной логики, нужно знать PDA эскроу Burn & Earn здесь.
 
-        let mut tax_amount = 0;
-        let mut transfer_to_recipient_amount = amount;
-
-        // Если отправитель НЕ является специальным (например, эскроу Burn & Earn), то взимаем налог
-        // if ctx.accounts.sender_authority.key() != tax_config.burn_and_earn_escrow_authority { // Пример условия
-        // Предположим, что все переводы через эту функцию облагаются налогом, если не указано иное
+        // Если отправитель НЕ является специальным (например, эскроу Burn & Earn), то взимаем налог.
         // Для простоты, пока всегда взимаем налог. Логику исключений можно добавить позже.
+        let (transfer_to_recipient_amount, tax_amount) = apply_tax(amount, tax_config)?;
 
-        let (calculated_tax, overflow) = amount.overflowing_mul(tax_config.tax_rate_bps as u64);
-        if overflow { return err!(VgTokenError::MathOverflow); }
-        
-        let (final_tax, overflow) = calculated_tax.overflowing_div(10000);
-        if overflow { return err!(VgTokenError::MathOverflow); }
-        tax_amount = final_tax;
-
-        let (calculated_transfer, overflow) = amount.overflowing_sub(tax_amount);
-        if overflow { return err!(VgTokenError::MathOverflow); }
-        transfer_to_recipient_amount = calculated_transfer;
-        
-        if tax_amount > 0 && transfer_to_recipient_amount == 0 && amount > 0 {
-             return err!(VgTokenError::InvalidTaxCalculation); 
-        }
-        if amount > 0 && transfer_to_recipient_amount == 0 && tax_amount == 0 {
-            return err!(VgTokenError::InvalidTaxCalculation);
-        }
-        
-        let dao_tax_share = tax_amount.checked_mul(tax_config.dao_share_bps as u64).unwrap_or(0) / 10000;
-        let nft_holders_tax_share = tax_amount.saturating_sub(dao_tax_share);
+        let (dao_tax_share, nft_holders_tax_share) =
+            split_bps(tax_amount, tax_config.dao_share_bps).ok_or(VgTokenError::InvalidTaxCalculation)?;
 
         msg!(
             "Перевод {} VG: {} получателю. Налог: {} (DAO: {}, NFT holders: {})",
@@ -138,59 +205,163 @@ pub mod vg_token {
         let sender_token_account_info = ctx.accounts.sender_token_account.to_account_info();
         let sender_authority_info = ctx.accounts.sender_authority.to_account_info();
         let mint_info = ctx.accounts.mint_account.to_account_info();
+        // Аккаунты Transfer Hook (программа-хук, ExtraAccountMetaList и т.д.) передаются
+        // клиентом через remaining_accounts и пробрасываются в каждый transfer_checked
+        let hook_accounts = ctx.remaining_accounts;
 
-        // 1. Переводим основную сумму получателю
-        if transfer_to_recipient_amount > 0 {
-            token_interface::transfer_checked(
-                CpiContext::new(
-                    token_program_info.clone(),
-                    TransferChecked {
-                        from: sender_token_account_info.clone(),
-                        to: ctx.accounts.recipient_token_account.to_account_info(),
-                        authority: sender_authority_info.clone(),
-                        mint: mint_info.clone(),
-                    },
-                ),
-                transfer_to_recipient_amount,
-                TOKEN_DECIMALS,
+        // Основная сумма получателю, затем доли налога в казну DAO и в пул держателей NFT Fee Key
+        for (destination, leg_amount) in [
+            (ctx.accounts.recipient_token_account.to_account_info(), transfer_to_recipient_amount),
+            (ctx.accounts.dao_treasury_token_account.to_account_info(), dao_tax_share),
+            (ctx.accounts.fee_collector_token_account.to_account_info(), nft_holders_tax_share),
+        ] {
+            transfer_vg(
+                &token_program_info,
+                &sender_token_account_info,
+                &mint_info,
+                destination,
+                &sender_authority_info,
+                hook_accounts,
+                leg_amount,
             )?;
         }
         
-        // 2. Переводим налог в казну DAO
-        if dao_tax_share > 0 {
-            token_interface::transfer_checked(
-                CpiContext::new(
-                    token_program_info.clone(),
-                    TransferChecked {
-                        from: sender_token_account_info.clone(),
-                        to: ctx.accounts.dao_treasury_token_account.to_account_info(),
-                        authority: sender_authority_info.clone(),
-                        mint: mint_info.clone(),
-                    },
-                ),
-                dao_tax_share,
-                TOKEN_DECIMALS,
+        msg!("Кастомный перевод VG токенов с налогом успешно выполнен.");
+        Ok(())
+    }
+
+    /// Перевод VG с налогом от имени делегата: для DEX-роутеров и эскроу-программ.
+    /// Отправитель - любой токен-аккаунт VG (не обязательно ATA); подписывает либо
+    /// его владелец (в том числе PDA через CPI), либо делегат с одобрением не меньше `amount`.
+    /// Получатель - любой токен-аккаунт VG. Аккаунты Transfer Hook передаются через remaining_accounts.
+    pub fn transfer_from_with_tax<'info>(
+        ctx: Context<'_, '_, '_, 'info, TransferFromWithTax<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return err!(VgTokenError::InvalidAmount);
+        }
+
+        let sender = &ctx.accounts.sender_token_account;
+        let authority = ctx.accounts.authority.key();
+        if sender.owner != authority {
+            if Option::<Pubkey>::from(sender.delegate) != Some(authority) {
+                return err!(VgTokenError::Unauthorized);
+            }
+            if sender.delegated_amount < amount {
+                return err!(VgTokenError::InsufficientDelegation);
+            }
+        }
+
+        let (transfer_to_recipient_amount, tax_amount) = apply_tax(amount, &ctx.accounts.tax_config)?;
+        let (dao_tax_share, nft_holders_tax_share) =
+            split_bps(tax_amount, ctx.accounts.tax_config.dao_share_bps).ok_or(VgTokenError::InvalidTaxCalculation)?;
+
+        let token_program_info = ctx.accounts.token_program.to_account_info();
+        let sender_token_account_info = ctx.accounts.sender_token_account.to_account_info();
+        let authority_info = ctx.accounts.authority.to_account_info();
+        let mint_info = ctx.accounts.mint_account.to_account_info();
+        for (destination, leg_amount) in [
+            (ctx.accounts.recipient_token_account.to_account_info(), transfer_to_recipient_amount),
+            (ctx.accounts.dao_treasury_token_account.to_account_info(), dao_tax_share),
+            (ctx.accounts.fee_collector_token_account.to_account_info(), nft_holders_tax_share),
+        ] {
+            transfer_vg(
+                &token_program_info,
+                &sender_token_account_info,
+                &mint_info,
+                destination,
+                &authority_info,
+                ctx.remaining_accounts,
+                leg_amount,
             )?;
         }
-        
-        // 3. Переводим налог в пул для держателей NFT Fee Key
-        if nft_holders_tax_share > 0 {
-            token_interface::transfer_checked(
-                CpiContext::new(
-                    token_program_info,
-                    TransferChecked {
-                        from: sender_token_account_info,
-                        to: ctx.accounts.fee_collector_token_account.to_account_info(),
-                        authority: sender_authority_info,
-                        mint: mint_info,
-                    },
-                ),
-                nft_holders_tax_share,
-                TOKEN_DECIMALS,
+
+        msg!(
+            "Перевод {} VG от {} подписью {}: {} получателю. Налог: {} (DAO: {}, NFT holders: {})",
+            amount,
+            sender_token_account_info.key(),
+            authority,
+            transfer_to_recipient_amount,
+            tax_amount,
+            dao_tax_share,
+            nft_holders_tax_share
+        );
+        Ok(())
+    }
+
+    /// Пакетный перевод VG с налогом для выплат и раздач: `amounts[i]` переводится на
+    /// токен-аккаунт `remaining_accounts[i]` (существующий аккаунт VG), остальные
+    /// remaining_accounts - аккаунты Transfer Hook. Налог считается по каждому переводу,
+    /// а общий налог делится и переводится в казну DAO и сборщику налога один раз.
+    /// Токен-аккаунты казны и сборщика налога должны уже существовать.
+    pub fn batch_transfer_with_tax<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchTransferWithTax<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        if amounts.is_empty() || amounts.len() > MAX_BATCH_RECIPIENTS || ctx.remaining_accounts.len() < amounts.len() {
+            return err!(VgTokenError::InvalidBatch);
+        }
+        let (recipient_accounts, hook_accounts) = ctx.remaining_accounts.split_at(amounts.len());
+
+        let token_program_info = ctx.accounts.token_program.to_account_info();
+        let sender_token_account_info = ctx.accounts.sender_token_account.to_account_info();
+        let sender_authority_info = ctx.accounts.sender_authority.to_account_info();
+        let mint_info = ctx.accounts.mint_account.to_account_info();
+
+        let mut total_amount: u64 = 0;
+        let mut total_tax: u64 = 0;
+        for (recipient_info, &amount) in recipient_accounts.iter().zip(amounts.iter()) {
+            if amount == 0 {
+                return err!(VgTokenError::InvalidAmount);
+            }
+            // Получатель - токен-аккаунт VG той же программы токенов
+            let recipient = InterfaceAccount::<TokenAccount>::try_from(recipient_info)
+                .map_err(|_| error!(VgTokenError::InvalidRecipient))?;
+            if recipient.mint != mint_info.key() {
+                return err!(VgTokenError::InvalidRecipient);
+            }
+
+            let (net_amount, tax_amount) = apply_tax(amount, &ctx.accounts.tax_config)?;
+            total_amount = total_amount.checked_add(amount).ok_or(VgTokenError::MathOverflow)?;
+            total_tax = total_tax.checked_add(tax_amount).ok_or(VgTokenError::MathOverflow)?;
+
+            transfer_vg(
+                &token_program_info,
+                &sender_token_account_info,
+                &mint_info,
+                recipient_info.clone(),
+                &sender_authority_info,
+                hook_accounts,
+                net_amount,
             )?;
         }
-        
-        msg!("Кастомный перевод VG токенов с налогом успешно выполнен.");
+
+        let (dao_tax_share, nft_holders_tax_share) =
+            split_bps(total_tax, ctx.accounts.tax_config.dao_share_bps).ok_or(VgTokenError::InvalidTaxCalculation)?;
+        for (destination, leg_amount) in [
+            (ctx.accounts.dao_treasury_token_account.to_account_info(), dao_tax_share),
+            (ctx.accounts.fee_collector_token_account.to_account_info(), nft_holders_tax_share),
+        ] {
+            transfer_vg(
+                &token_program_info,
+                &sender_token_account_info,
+                &mint_info,
+                destination,
+                &sender_authority_info,
+                hook_accounts,
+                leg_amount,
+            )?;
+        }
+
+        msg!(
+            "Пакетный перевод {} VG на {} получателей. Налог: {} (DAO: {}, NFT holders: {})",
+            total_amount,
+            amounts.len(),
+            total_tax,
+            dao_tax_share,
+            nft_holders_tax_share
+        );
         Ok(())
     }
     
@@ -201,22 +372,72 @@ pub mod vg_token {
         tax_rate_bps: u16, 
         dao_share_bps: u16,
         nft_holders_share_bps: u16,
-        dao_treasury_pubkey: Pubkey,
-        fee_collector_pubkey: Pubkey
     ) -> Result<()> {
+        if !is_valid_bps(tax_rate_bps) || !is_valid_bps(dao_share_bps) || !is_valid_bps(nft_holders_share_bps) {
+            return err!(VgTokenError::InvalidTaxCalculation);
+        }
+        // Налог делится целиком между казной DAO и держателями NFT Fee Key
+        if dao_share_bps as u64 + nft_holders_share_bps as u64 != BPS_DENOMINATOR {
+            return err!(VgTokenError::InvalidTaxCalculation);
+        }
+
         let tax_config = &mut ctx.accounts.tax_config;
+        if tax_config.authority == Pubkey::default() {
+            tax_config.version = TaxConfig::VERSION;
+        } else if tax_config.version != TaxConfig::VERSION {
+            return err!(VgTokenError::AccountNeedsMigration);
+        }
         tax_config.authority = ctx.accounts.authority.key();
         tax_config.tax_rate_bps = tax_rate_bps;
         tax_config.dao_share_bps = dao_share_bps;
         tax_config.nft_holders_share_bps = nft_holders_share_bps;
-        tax_config.dao_treasury_pubkey = dao_treasury_pubkey;
-        tax_config.fee_collector_pubkey = fee_collector_pubkey;
         // tax_config.burn_and_earn_escrow_authority = burn_and_earn_escrow_authority; // Если нужно для исключений
 
         msg!("Конфигурация налога обновлена: Ставка {} BPS, Доля DAO {} BPS, Доля NFT {} BPS", tax_rate_bps, dao_share_bps, nft_holders_share_bps);
         Ok(())
     }
 
+    /// Переводит TaxConfig в текущую версию раскладки.
+    /// Только авторитет DAO, записанный в конфигурации.
+    pub fn migrate_tax_config(ctx: Context<MigrateTaxConfig>) -> Result<()> {
+        let config_info = ctx.accounts.tax_config.to_account_info();
+
+        let migrated = {
+            let data = config_info.try_borrow_data()?;
+            if !data.starts_with(TaxConfig::DISCRIMINATOR) {
+                return err!(VgTokenError::AccountNeedsMigration);
+            }
+            if data.len() == TaxConfigV0::LEN {
+                // Раскладка без байта версии, с адресами казны и сборщика налога
+                TaxConfigV0::deserialize(&mut &data[8..])?.upgrade()
+            } else {
+                let config = TaxConfig::try_deserialize(&mut &data[..])?;
+                if config.version == TaxConfig::VERSION {
+                    return err!(VgTokenError::AlreadyMigrated);
+                }
+                return err!(VgTokenError::UnsupportedAccountVersion);
+            }
+        };
+
+        if migrated.authority != ctx.accounts.authority.key() {
+            return err!(VgTokenError::Unauthorized);
+        }
+
+        resize_account(
+            &config_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + TaxConfig::INIT_SPACE,
+        )?;
+        let mut data = config_info.try_borrow_mut_data()?;
+        data.fill(0);
+        let mut writer: &mut [u8] = &mut data[..];
+        migrated.try_serialize(&mut writer)?;
+
+        msg!("TaxConfig мигрирован до версии {}", TaxConfig::VERSION);
+        Ok(())
+    }
+
     /// Держатель NFT Fee Key вызывает для получения своей доли из пула налогов.
     /// (Логика клейма здесь не реализована полностью, требует NFTHolderInfo и NFTHoldersPool)
     pub fn claim_nft_holder_reward(_ctx: Context<ClaimNFTReward>) -> Result<()> {
@@ -238,6 +459,7 @@ pub mod vg_token {
         uri: String
     ) -> Result<()> {
         msg!("Установка метаданных для VG токена: {}, {}, {}", name, symbol, uri);
+        validate_metadata_fields(&name, &symbol, &uri)?;
 
         let mint_seeds = &[
             VG_TOKEN_MINT_SEED, 
@@ -286,6 +508,249 @@ pub mod vg_token {
         msg!("Метаданные VG токена успешно установлены/обновлены.");
         Ok(())
     }
+
+    /// Обновление метаданных VG через UpdateMetadataAccountV2.
+    /// Подписывает исполнитель DAO (авторитет реестра, обычно PDA governance через CPI),
+    /// который является update_authority метаданных.
+    pub fn update_vg_metadata(
+        ctx: Context<UpdateVgMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        validate_metadata_fields(&name, &symbol, &uri)?;
+
+        let ix = mpl_token_metadata::instructions::UpdateMetadataAccountV2 {
+            metadata: ctx.accounts.metadata_account.key(),
+            update_authority: ctx.accounts.dao_authority.key(),
+        }
+        .instruction(mpl_token_metadata::instructions::UpdateMetadataAccountV2InstructionArgs {
+            data: Some(mpl_token_metadata::types::DataV2 {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri: uri.clone(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            }),
+            new_update_authority: None,
+            primary_sale_happened: None,
+            is_mutable: None,
+        });
+
+        // Подпись исполнителя DAO пробрасывается из внешней инструкции
+        anchor_lang::solana_program::program::invoke(
+            &ix,
+            &[
+                ctx.accounts.metadata_account.to_account_info(),
+                ctx.accounts.dao_authority.to_account_info(),
+            ],
+        )?;
+
+        emit!(VgMetadataUpdated {
+            name,
+            symbol,
+            uri,
+            updated_by: ctx.accounts.dao_authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Метаданные VG токена обновлены DAO");
+        Ok(())
+    }
+
+    /// Аудит минта VG (только чтение): эмиссия равна TOTAL_SUPPLY, mint и freeze authority
+    /// отсутствуют, десятичные знаки и программа-владелец (Token-2022) ожидаемые.
+    /// Отчет возвращается через return data, инструкция не падает на непройденных проверках.
+    pub fn audit_mint(ctx: Context<AuditMint>) -> Result<MintAuditReport> {
+        let mint = &ctx.accounts.mint_account;
+        let report = MintAuditReport::build(
+            mint.key(),
+            *mint.to_account_info().owner,
+            mint.supply,
+            mint.decimals,
+            mint.mint_authority.into(),
+            mint.freeze_authority.into(),
+            &MintAuditExpectation {
+                total_supply: TOTAL_SUPPLY,
+                decimals: TOKEN_DECIMALS,
+                owner_program: Token2022::id(),
+            },
+        );
+
+        msg!("Аудит минта VG: {}", if report.passed { "пройден" } else { "не пройден" });
+        Ok(report)
+    }
+}
+
+/// Проверяет поля метаданных (см. tech_hy_common::validate_metadata_fields)
+fn validate_metadata_fields(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    tech_hy_common::validate_metadata_fields(name, symbol, uri).map_err(|field| match field {
+        MetadataFieldError::Name => error!(VgTokenError::InvalidMetadataName),
+        MetadataFieldError::Symbol => error!(VgTokenError::InvalidMetadataSymbol),
+        MetadataFieldError::Uri => error!(VgTokenError::InvalidMetadataUri),
+    })
+}
+
+/// Сумма получателю и налог перевода `amount` по текущей конфигурации
+fn apply_tax(amount: u64, tax_config: &TaxConfig) -> Result<(u64, u64)> {
+    let tax_amount = bps_of(amount, tax_config.tax_rate_bps).ok_or(VgTokenError::MathOverflow)?;
+    let net_amount = amount.checked_sub(tax_amount).ok_or(VgTokenError::MathOverflow)?;
+    if net_amount == 0 {
+        return err!(VgTokenError::InvalidTaxCalculation);
+    }
+    Ok((net_amount, tax_amount))
+}
+
+/// Одна нога перевода VG; нулевая сумма пропускается. Аккаунты Transfer Hook
+/// пробрасываются в transfer_checked.
+fn transfer_vg<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.clone(),
+            TransferChecked {
+                from: from.clone(),
+                to,
+                authority: authority.clone(),
+                mint: mint.clone(),
+            },
+        ).with_remaining_accounts(hook_accounts.to_vec()),
+        amount,
+        TOKEN_DECIMALS,
+    )
+}
+
+/// Проверяет инварианты минта VG для переводов с налогом:
+/// минт принадлежит Token-2022, mint authority и freeze authority отозваны,
+/// расширение TransferHook указывает на VG_TRANSFER_HOOK_PROGRAM_ID,
+/// а адрес совпадает с PDA минта VG.
+fn is_canonical_vg_mint(mint_account: &AccountInfo) -> bool {
+    if mint_account.owner != &Token2022::id() {
+        msg!("Минт не принадлежит программе Token-2022");
+        return false;
+    }
+    let data = match mint_account.try_borrow_data() {
+        Ok(data) => data,
+        Err(_) => return false,
+    };
+    let mint = match StateWithExtensions::<Token2022Mint>::unpack(&data) {
+        Ok(mint) => mint,
+        Err(_) => {
+            msg!("Аккаунт не является минтом Token-2022");
+            return false;
+        }
+    };
+    if mint.base.mint_authority.is_some() {
+        msg!("У минта не отозвана mint authority");
+        return false;
+    }
+    if mint.base.freeze_authority.is_some() {
+        msg!("У минта установлена freeze authority");
+        return false;
+    }
+    let hook_program_id = mint
+        .get_extension::<TransferHook>()
+        .ok()
+        .and_then(|hook| Option::<Pubkey>::from(hook.program_id));
+    if hook_program_id != Some(VG_TRANSFER_HOOK_PROGRAM_ID) {
+        msg!("Расширение TransferHook минта не указывает на программу-хук VG");
+        return false;
+    }
+    let (expected_mint, _) = Pubkey::find_program_address(&[VG_TOKEN_MINT_SEED], &crate::ID);
+    if mint_account.key() != expected_mint {
+        msg!("Адрес минта не совпадает с PDA минта VG");
+        return false;
+    }
+    true
+}
+
+/// Помощники для программ, которые переводят VG с налогом через CPI (фича `cpi`).
+/// Обертки над сгенерированными Anchor `cpi::transfer_with_tax` и
+/// `cpi::transfer_from_with_tax`: собирают `CpiContext` с подписью PDA и
+/// аккаунтами Transfer Hook в remaining_accounts, вычисляют адреса PDA vg-token
+/// и заранее рассчитывают налог перевода.
+#[cfg(feature = "cpi")]
+pub mod cpi_helpers {
+    use super::*;
+    pub use crate::cpi::accounts::{TransferFromWithTax, TransferWithTax};
+
+    /// PDA конфигурации налога vg-token
+    pub fn tax_config_address() -> Pubkey {
+        Pubkey::find_program_address(&[TAX_CONFIG_SEED], &crate::ID).0
+    }
+
+    /// PDA минта VG
+    pub fn vg_mint_address() -> Pubkey {
+        Pubkey::find_program_address(&[VG_TOKEN_MINT_SEED], &crate::ID).0
+    }
+
+    /// Разбивка перевода `amount` через transfer_with_tax / transfer_from_with_tax
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct TaxQuote {
+        /// Получает получатель
+        pub net_amount: u64,
+        /// Весь налог
+        pub tax_amount: u64,
+        /// Доля налога для казны DAO
+        pub dao_share: u64,
+        /// Доля налога для держателей NFT Fee Key
+        pub nft_holders_share: u64,
+    }
+
+    /// Расчет налога перевода по конфигурации так же, как в transfer_with_tax;
+    /// ошибка - если перевод будет отклонен (нулевая сумма или получателю ничего не остается)
+    pub fn quote_tax(amount: u64, tax_config: &TaxConfig) -> Result<TaxQuote> {
+        if amount == 0 {
+            return err!(VgTokenError::InvalidAmount);
+        }
+        let (net_amount, tax_amount) = apply_tax(amount, tax_config)?;
+        let (dao_share, nft_holders_share) =
+            split_bps(tax_amount, tax_config.dao_share_bps).ok_or(VgTokenError::InvalidTaxCalculation)?;
+        Ok(TaxQuote { net_amount, tax_amount, dao_share, nft_holders_share })
+    }
+
+    /// transfer_with_tax с подписью отправителя-PDA вызывающей программы (`signer_seeds`).
+    /// Отправитель должен иметь лампорты, если токен-аккаунты получателя, казны
+    /// или сборщика налога еще не созданы. `hook_accounts` - аккаунты Transfer Hook VG.
+    pub fn transfer_with_tax_signed<'info>(
+        vg_token_program: AccountInfo<'info>,
+        accounts: TransferWithTax<'info>,
+        hook_accounts: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<()> {
+        crate::cpi::transfer_with_tax(
+            CpiContext::new_with_signer(vg_token_program, accounts, signer_seeds)
+                .with_remaining_accounts(hook_accounts.to_vec()),
+            amount,
+        )
+    }
+
+    /// transfer_from_with_tax с подписью владельца-PDA или делегата-PDA вызывающей программы
+    pub fn transfer_from_with_tax_signed<'info>(
+        vg_token_program: AccountInfo<'info>,
+        accounts: TransferFromWithTax<'info>,
+        hook_accounts: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<()> {
+        crate::cpi::transfer_from_with_tax(
+            CpiContext::new_with_signer(vg_token_program, accounts, signer_seeds)
+                .with_remaining_accounts(hook_accounts.to_vec()),
+            amount,
+        )
+    }
 }
 
 #[derive(Accounts)]
@@ -301,8 +766,11 @@ pub struct Initialize<'info> {
         mint::decimals = TOKEN_DECIMALS,
         mint::authority = mint_account, // PDA mint_account будет временным mint_authority
         // mint::freeze_authority не указываем, будет None по умолчанию
+        mint::token_program = token_program,
+        extensions::transfer_hook::authority = mint_account,
+        extensions::transfer_hook::program_id = VG_TRANSFER_HOOK_PROGRAM_ID,
     )]
-    pub mint_account: InterfaceAccount<'info, Mint>, // Обычный SPL Mint
+    pub mint_account: InterfaceAccount<'info, Mint>, // Token-2022 Mint с расширением TransferHook
 
     /// Эскроу-аккаунт (PDA программы Burn and Earn), который будет авторитетом для токен-аккаунта,
     /// хранящего все выпущенные VG токены для дальнейшего распределения программой Burn and Earn.
@@ -314,10 +782,60 @@ pub struct Initialize<'info> {
         payer = payer,
         associated_token::mint = mint_account,
         associated_token::authority = burn_and_earn_escrow_pda,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>, // Расширения минта поддерживает только Token-2022
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeWithTokenMetadata<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [VG_TOKEN_MINT_SEED],
+        bump,
+        mint::decimals = TOKEN_DECIMALS,
+        mint::authority = mint_account,
+        mint::token_program = token_program,
+        extensions::transfer_hook::authority = mint_account,
+        extensions::transfer_hook::program_id = VG_TRANSFER_HOOK_PROGRAM_ID,
+        extensions::metadata_pointer::authority = dao_authority,
+        extensions::metadata_pointer::metadata_address = mint_account,
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>, // Token-2022 Mint с TransferHook и TokenMetadata
+
+    /// CHECK: Авторитет DAO из реестра - итоговый update authority метаданных
+    #[account(address = registry.authority @ VgTokenError::Unauthorized)]
+    pub dao_authority: UncheckedAccount<'info>,
+
+    /// Реестр экосистемы: авторитет DAO
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    /// CHECK: PDA эскроу программы Burn and Earn, авторитет токен-аккаунта с полной эмиссией
+    pub burn_and_earn_escrow_pda: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_account,
+        associated_token::authority = burn_and_earn_escrow_pda,
+        associated_token::token_program = token_program,
     )]
     pub escrow_vault_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Interface<'info, TokenInterface>, // Стандартная SPL Token программа
+    pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -330,17 +848,26 @@ pub struct TransferWithTax<'info> {
     
     // PDA аккаунт с конфигурацией налога
     #[account(
-        seeds = [b"tax_config_seed"], // Пример сида, нужно определить свой
+        seeds = [TAX_CONFIG_SEED],
         bump,
-        // constraint = tax_config.authority == ... // Возможно, проверка авторитета tax_config
+        constraint = tax_config.version == TaxConfig::VERSION @ VgTokenError::AccountNeedsMigration,
+        constraint = tax_config.to_account_info().data_len() == 8 + TaxConfig::INIT_SPACE @ VgTokenError::AccountNeedsMigration,
     )]
     pub tax_config: Account<'info, TaxConfig>,
+
+    /// Реестр экосистемы: канонические адреса и состояние аварийной паузы
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ VgTokenError::ProgramPaused,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
     
     #[account(
         mut,
-        // constraint = mint_account.is_initialized @ VgTokenError::MintError,
-        // constraint = mint_account.mint_authority.is_none() @ VgTokenError::MintError, // Проверка, что минт больше невозможен
-        // constraint = mint_account.freeze_authority.is_none() @ VgTokenError::MintError, // Проверка, что заморозка невозможна
+        // Минт больше невозможен, заморозка невозможна, хук и адрес канонические
+        constraint = is_canonical_vg_mint(&mint_account.to_account_info()) @ VgTokenError::MintError,
     )]
     pub mint_account: InterfaceAccount<'info, Mint>, // Минт VG токена
     
@@ -364,7 +891,7 @@ pub struct TransferWithTax<'info> {
     pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: Авторитет (владелец) токен-аккаунта казны DAO.
-    #[account(address = tax_config.dao_treasury_pubkey @ VgTokenError::InvalidTaxCalculation)] // Проверка, что это правильный адрес из конфига
+    #[account(address = registry.dao_treasury @ VgTokenError::InvalidTaxCalculation)] // Проверка, что это адрес казны из реестра
     pub dao_treasury_authority: UncheckedAccount<'info>, 
 
     #[account(
@@ -376,7 +903,7 @@ pub struct TransferWithTax<'info> {
     pub dao_treasury_token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: Авторитет (владелец) токен-аккаунта для сбора налога для NFT холдеров.
-    #[account(address = tax_config.fee_collector_pubkey @ VgTokenError::InvalidTaxCalculation)] // Проверка из конфига
+    #[account(address = registry.fee_collector @ VgTokenError::InvalidTaxCalculation)] // Проверка по реестру экосистемы
     pub fee_collector_authority: UncheckedAccount<'info>,
 
     #[account(
@@ -392,18 +919,198 @@ pub struct TransferWithTax<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Аккаунты transfer_from_with_tax: отправитель и получатель - произвольные токен-аккаунты VG
+#[derive(Accounts)]
+pub struct TransferFromWithTax<'info> {
+    /// Владелец или делегат токен-аккаунта отправителя
+    pub authority: Signer<'info>,
+
+    /// Платит за создание токен-аккаунтов казны и сборщика налога, если их нет
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [TAX_CONFIG_SEED],
+        bump,
+        constraint = tax_config.version == TaxConfig::VERSION @ VgTokenError::AccountNeedsMigration,
+        constraint = tax_config.to_account_info().data_len() == 8 + TaxConfig::INIT_SPACE @ VgTokenError::AccountNeedsMigration,
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+
+    /// Реестр экосистемы: канонические адреса и состояние аварийной паузы
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ VgTokenError::ProgramPaused,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        mut,
+        constraint = is_canonical_vg_mint(&mint_account.to_account_info()) @ VgTokenError::MintError,
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = mint_account)]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint_account)]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Авторитет (владелец) токен-аккаунта казны DAO.
+    #[account(address = registry.dao_treasury @ VgTokenError::InvalidTaxCalculation)]
+    pub dao_treasury_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_account,
+        associated_token::authority = dao_treasury_authority,
+    )]
+    pub dao_treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Авторитет (владелец) токен-аккаунта для сбора налога для NFT холдеров.
+    #[account(address = registry.fee_collector @ VgTokenError::InvalidTaxCalculation)]
+    pub fee_collector_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_account,
+        associated_token::authority = fee_collector_authority,
+    )]
+    pub fee_collector_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Аккаунты batch_transfer_with_tax; получатели передаются через remaining_accounts
+#[derive(Accounts)]
+pub struct BatchTransferWithTax<'info> {
+    pub sender_authority: Signer<'info>,
+
+    #[account(
+        seeds = [TAX_CONFIG_SEED],
+        bump,
+        constraint = tax_config.version == TaxConfig::VERSION @ VgTokenError::AccountNeedsMigration,
+        constraint = tax_config.to_account_info().data_len() == 8 + TaxConfig::INIT_SPACE @ VgTokenError::AccountNeedsMigration,
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+
+    /// Реестр экосистемы: канонические адреса и состояние аварийной паузы
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ VgTokenError::ProgramPaused,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        mut,
+        constraint = is_canonical_vg_mint(&mint_account.to_account_info()) @ VgTokenError::MintError,
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = sender_authority,
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Авторитет (владелец) токен-аккаунта казны DAO.
+    #[account(address = registry.dao_treasury @ VgTokenError::InvalidTaxCalculation)]
+    pub dao_treasury_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = dao_treasury_authority,
+    )]
+    pub dao_treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Авторитет (владелец) токен-аккаунта для сбора налога для NFT холдеров.
+    #[account(address = registry.fee_collector @ VgTokenError::InvalidTaxCalculation)]
+    pub fee_collector_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = fee_collector_authority,
+    )]
+    pub fee_collector_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Новые поля добавляются только в конец структуры, а VERSION увеличивается
+// вместе с веткой в migrate_tax_config.
 #[account]
-#[derive(Default)]
+#[derive(Default, InitSpace)]
 pub struct TaxConfig {
+    pub version: u8, // Версия раскладки аккаунта
     pub authority: Pubkey, // Авторитет, который может обновлять эту конфигурацию (DAO)
     pub tax_rate_bps: u16, // Ставка налога в базисных пунктах (1000 = 10%)
     pub dao_share_bps: u16, // Доля DAO от налога в базисных пунктах (5000 = 50%)
     pub nft_holders_share_bps: u16, // Доля NFT холдеров от налога (5000 = 50%)
-    pub dao_treasury_pubkey: Pubkey, // Адрес ТОКЕН-АККАУНТА казны DAO
-    pub fee_collector_pubkey: Pubkey, // Адрес ТОКЕН-АККАУНТА для сбора налога для NFT холдеров
+    // Адреса казны DAO и сборщика налога хранятся в реестре экосистемы (tech_hy_contracts)
     // pub burn_and_earn_escrow_authority: Pubkey, // Опционально, для исключения из налога
 }
 
+impl TaxConfig {
+    pub const VERSION: u8 = 1;
+}
+
+/// Раскладка TaxConfig до появления байта версии (размер считался через std::mem::size_of)
+#[derive(AnchorDeserialize)]
+pub struct TaxConfigV0 {
+    pub authority: Pubkey,
+    pub tax_rate_bps: u16,
+    pub dao_share_bps: u16,
+    pub nft_holders_share_bps: u16,
+    pub dao_treasury_pubkey: Pubkey,
+    pub fee_collector_pubkey: Pubkey,
+}
+
+impl TaxConfigV0 {
+    pub const LEN: usize = 8 + 32 + 2 * 3 + 32 * 2;
+
+    fn upgrade(self) -> TaxConfig {
+        // Адреса казны и сборщика налога теперь берутся из реестра экосистемы
+        TaxConfig {
+            version: TaxConfig::VERSION,
+            authority: self.authority,
+            tax_rate_bps: self.tax_rate_bps,
+            dao_share_bps: self.dao_share_bps,
+            nft_holders_share_bps: self.nft_holders_share_bps,
+        }
+    }
+}
+
+// Устаревшая раскладка распознается по длине, поэтому она не должна совпадать с текущей
+const _: () = assert!(TaxConfigV0::LEN != 8 + TaxConfig::INIT_SPACE);
+
+#[derive(Accounts)]
+pub struct MigrateTaxConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Раскладка может быть устаревшей, поэтому аккаунт читается вручную в инструкции
+    #[account(
+        mut,
+        seeds = [TAX_CONFIG_SEED],
+        bump,
+        owner = crate::ID,
+    )]
+    pub tax_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct UpsertTaxConfig<'info> {
     #[account(mut)]
@@ -412,8 +1119,8 @@ pub struct UpsertTaxConfig<'info> {
     #[account(
         init_if_needed, // Создаем, если нет, или загружаем существующий
         payer = payer,
-        space = 8 + std::mem::size_of::<TaxConfig>(), // 8 байт дискриминатор + размер структуры
-        seeds = [b"tax_config_seed"], // Определяем уникальный сид для PDA
+        space = 8 + TaxConfig::INIT_SPACE, // 8 байт дискриминатор + Borsh-размер структуры
+        seeds = [TAX_CONFIG_SEED],
         bump
     )]
     pub tax_config: Account<'info, TaxConfig>,
@@ -432,7 +1139,15 @@ pub struct UpsertTaxConfig<'info> {
 
 #[derive(Accounts)]
 pub struct ClaimNFTReward<'info> {
-    // TODO: Определить аккаунты для клейма наград
+    /// Реестр экосистемы: канонические адреса и состояние аварийной паузы
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+        constraint = !registry.paused @ VgTokenError::ProgramPaused,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+    // TODO: Определить остальные аккаунты для клейма наград
     // pub nft_owner: Signer<'info>,
     // pub nft_fee_key_mint: InterfaceAccount<'info, Mint>, // Минт NFT Fee Key
     // #[account(mut, seeds = [b"nft_holder_info", nft_fee_key_mint.key().as_ref()], bump)]
@@ -462,37 +1177,101 @@ pub struct SetMetadata<'info> {
     #[account(mut)]
     pub payer: Signer<'info>, 
     
-    /// Авторитет DAO, который будет установлен как update_authority для метаданных.
-    /// CHECK: Должен быть реальным авторитетом DAO (мультисиг или PDA).
-    pub dao_authority: UncheckedAccount<'info>,
+    /// Авторитет DAO из реестра, который будет установлен как update_authority для метаданных.
+    #[account(address = registry.authority @ VgTokenError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+
+    /// Реестр экосистемы: авторитет DAO
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
 
     #[account(
         mut,
         seeds = [VG_TOKEN_MINT_SEED], // PDA минта VG
         bump,
     )]
-    pub mint_account: InterfaceAccount<'info, Mint>, // Обычный SPL Mint
+    pub mint_account: InterfaceAccount<'info, Mint>, // Token-2022 Mint VG
     
+    /// CHECK: Аккаунт метаданных создается и принадлежит программе Metaplex, поэтому
+    /// здесь проверяется только адрес PDA
     #[account(
-        init_if_needed,
-        payer = payer,
+        mut,
         seeds = [
-            b"metadata",
+            METADATA_SEED,
             mpl_token_metadata::ID.as_ref(),
             mint_account.key().as_ref(),
         ],
         bump,
-        space = 8 + mpl_token_metadata::state::MAX_METADATA_LEN,
         seeds::program = mpl_token_metadata::ID
     )]
     pub metadata_account: UncheckedAccount<'info>,
     
+    /// CHECK: Это известная программа Metaplex Token Metadata
     #[account(address = mpl_token_metadata::ID)]
     pub metadata_program: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[derive(Accounts)]
+pub struct UpdateVgMetadata<'info> {
+    /// Исполнитель DAO (авторитет реестра) - update_authority метаданных
+    #[account(address = registry.authority @ VgTokenError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        seeds::program = REGISTRY_PROGRAM_ID,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        seeds = [VG_TOKEN_MINT_SEED],
+        bump,
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Аккаунт метаданных Metaplex, проверяется адрес PDA
+    #[account(
+        mut,
+        seeds = [
+            METADATA_SEED,
+            mpl_token_metadata::ID.as_ref(),
+            mint_account.key().as_ref(),
+        ],
+        bump,
+        seeds::program = mpl_token_metadata::ID
+    )]
+    pub metadata_account: UncheckedAccount<'info>,
+
+    /// CHECK: Это известная программа Metaplex Token Metadata
+    #[account(address = mpl_token_metadata::ID)]
+    pub metadata_program: UncheckedAccount<'info>,
+}
+
+#[event]
+pub struct VgMetadataUpdated {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct AuditMint<'info> {
+    #[account(
+        seeds = [VG_TOKEN_MINT_SEED],
+        bump,
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+}
+
 // Ошибки программы
 #[error_code]
 pub enum VgTokenError {
@@ -510,4 +1289,24 @@ pub enum VgTokenError {
     MathOverflow,
     #[msg("Функция еще не реализована")]
     NotImplemented,
+    #[msg("Экосистема на аварийной паузе")]
+    ProgramPaused,
+    #[msg("Аккаунт использует устаревшую раскладку, требуется миграция")]
+    AccountNeedsMigration,
+    #[msg("Аккаунт уже использует текущую раскладку")]
+    AlreadyMigrated,
+    #[msg("Неизвестная версия раскладки аккаунта")]
+    UnsupportedAccountVersion,
+    #[msg("Имя токена пустое или длиннее допустимого")]
+    InvalidMetadataName,
+    #[msg("Символ токена должен состоять из заглавных латинских букв и цифр и не превышать допустимую длину")]
+    InvalidMetadataSymbol,
+    #[msg("URI метаданных должен начинаться с https:// и не превышать допустимую длину")]
+    InvalidMetadataUri,
+    #[msg("Пакет перевода пуст, больше MAX_BATCH_RECIPIENTS или получателей меньше, чем сумм")]
+    InvalidBatch,
+    #[msg("Получатель не является токен-аккаунтом VG")]
+    InvalidRecipient,
+    #[msg("Одобренная делегату сумма меньше суммы перевода")]
+    InsufficientDelegation,
 } 
\ No newline at end of file
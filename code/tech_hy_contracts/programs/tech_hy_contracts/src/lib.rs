@@ -2,15 +2,420 @@ use anchor_lang::prelude::*;
 
 declare_id!("3oX2qbPpcWeHGfQknX8r1mwASpHCcZQLsoz2edNoHraV");
 
+// Сиды PDA реестра
+pub use tech_hy_common::{ECOSYSTEM_REGISTRY_SEED, EMERGENCY_COUNCIL_SEED};
+
+// Константы аварийного совета
+pub const MAX_COUNCIL_MEMBERS: usize = 10; // Максимальное число участников мультиподписи
+pub const MAX_PAUSE_REASON_LEN: usize = 128; // Максимальная длина причины паузы в байтах
+
+/// Реестр экосистемы TECH-HY.
+/// Хранит канонические адреса минтов, программ, казны DAO и сборщика комиссий,
+/// а также текущее состояние аварийной паузы. Остальные программы сверяют
+/// свои ограничения `address = ...` с этим аккаунтом.
 #[program]
 pub mod tech_hy_contracts {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        msg!("Greetings from: {:?}", ctx.program_id);
+    /// Создает реестр экосистемы. Вызвать может только upgrade authority программы
+    /// (проверка по ProgramData), и он же становится авторитетом реестра до передачи DAO.
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>, params: RegistryParams) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        registry.version = EcosystemRegistry::VERSION;
+        registry.authority = ctx.accounts.authority.key();
+        registry.apply(&params);
+        registry.paused = false;
+        registry.pause_reason = String::new();
+        registry.paused_at = 0;
+        registry.bump = ctx.bumps.registry;
+
+        msg!("Реестр экосистемы инициализирован");
+        Ok(())
+    }
+
+    /// Обновляет адреса в реестре. Только авторитет DAO.
+    pub fn update_registry(ctx: Context<UpdateRegistry>, params: RegistryParams) -> Result<()> {
+        ctx.accounts.registry.apply(&params);
+
+        emit!(RegistryUpdated {
+            vc_mint: params.vc_mint,
+            vg_mint: params.vg_mint,
+            dao_treasury: params.dao_treasury,
+            fee_collector: params.fee_collector,
+        });
+        msg!("Реестр экосистемы обновлен");
+        Ok(())
+    }
+
+    /// Передает права авторитета реестра (например, от деплоера к исполнителю DAO).
+    pub fn set_registry_authority(ctx: Context<UpdateRegistry>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.registry.authority = new_authority;
+        msg!("Новый авторитет реестра: {}", new_authority);
+        Ok(())
+    }
+
+    /// Создает аварийный совет (M-of-N мультиподпись), который может ставить экосистему на паузу.
+    /// Для паузы требуется меньше подписей, чем для снятия паузы.
+    pub fn initialize_emergency_council(
+        ctx: Context<InitializeEmergencyCouncil>,
+        members: Vec<Pubkey>,
+        pause_threshold: u8,
+        unpause_threshold: u8,
+    ) -> Result<()> {
+        validate_council_config(&members, pause_threshold, unpause_threshold)?;
+
+        let council = &mut ctx.accounts.emergency_council;
+        council.version = EmergencyCouncil::VERSION;
+        council.members = members;
+        council.pause_threshold = pause_threshold;
+        council.unpause_threshold = unpause_threshold;
+        council.bump = ctx.bumps.emergency_council;
+
+        msg!(
+            "Аварийный совет создан: {} участников, пауза {}/{}, снятие паузы {}/{}",
+            council.members.len(),
+            pause_threshold,
+            council.members.len(),
+            unpause_threshold,
+            council.members.len()
+        );
+        Ok(())
+    }
+
+    /// Ротация аварийного совета: новый состав и пороги. Только авторитет DAO.
+    /// Аккаунт совета создается под MAX_COUNCIL_MEMBERS участников, поэтому realloc не нужен.
+    pub fn update_emergency_council(
+        ctx: Context<UpdateEmergencyCouncil>,
+        members: Vec<Pubkey>,
+        pause_threshold: u8,
+        unpause_threshold: u8,
+    ) -> Result<()> {
+        validate_council_config(&members, pause_threshold, unpause_threshold)?;
+
+        let council = &mut ctx.accounts.emergency_council;
+        council.members = members;
+        council.pause_threshold = pause_threshold;
+        council.unpause_threshold = unpause_threshold;
+
+        emit!(EmergencyCouncilUpdated {
+            members: council.members.clone(),
+            pause_threshold,
+            unpause_threshold,
+        });
+        msg!(
+            "Аварийный совет обновлен: {} участников, пауза {}, снятие паузы {}",
+            council.members.len(),
+            pause_threshold,
+            unpause_threshold
+        );
+        Ok(())
+    }
+
+    /// Аварийная остановка. Подписи участников совета передаются через remaining_accounts.
+    pub fn pause(ctx: Context<EmergencyAction>, reason: String) -> Result<()> {
+        if reason.len() > MAX_PAUSE_REASON_LEN {
+            return err!(RegistryError::PauseReasonTooLong);
+        }
+
+        let registry = &mut ctx.accounts.registry;
+        if registry.paused {
+            return err!(RegistryError::AlreadyPaused);
+        }
+
+        let council = &ctx.accounts.emergency_council;
+        let approvals = count_council_approvals(council, ctx.remaining_accounts);
+        if approvals < council.pause_threshold {
+            return err!(RegistryError::InsufficientCouncilSignatures);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        registry.paused = true;
+        registry.pause_reason = reason.clone();
+        registry.paused_at = now;
+
+        emit!(EmergencyPaused {
+            reason,
+            approvals,
+            timestamp: now,
+        });
+        msg!("Экосистема поставлена на паузу ({} подписей)", approvals);
+        Ok(())
+    }
+
+    /// Снятие аварийной паузы. Требует unpause_threshold подписей участников совета.
+    pub fn unpause(ctx: Context<EmergencyAction>) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        if !registry.paused {
+            return err!(RegistryError::NotPaused);
+        }
+
+        let council = &ctx.accounts.emergency_council;
+        let approvals = count_council_approvals(council, ctx.remaining_accounts);
+        if approvals < council.unpause_threshold {
+            return err!(RegistryError::InsufficientCouncilSignatures);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        registry.paused = false;
+        registry.pause_reason = String::new();
+
+        emit!(EmergencyUnpaused {
+            approvals,
+            timestamp: now,
+        });
+        msg!("Пауза снята ({} подписей)", approvals);
         Ok(())
     }
 }
 
+/// Проверяет состав совета: 1..=MAX_COUNCIL_MEMBERS уникальных участников
+/// и пороги 1 <= pause_threshold < unpause_threshold <= N.
+fn validate_council_config(members: &[Pubkey], pause_threshold: u8, unpause_threshold: u8) -> Result<()> {
+    if members.is_empty() || members.len() > MAX_COUNCIL_MEMBERS {
+        return err!(RegistryError::InvalidCouncilConfig);
+    }
+    for (i, member) in members.iter().enumerate() {
+        if members[..i].contains(member) {
+            return err!(RegistryError::InvalidCouncilConfig);
+        }
+    }
+    if pause_threshold == 0
+        || pause_threshold >= unpause_threshold
+        || unpause_threshold as usize > members.len()
+    {
+        return err!(RegistryError::InvalidCouncilConfig);
+    }
+    Ok(())
+}
+
+/// Считает уникальных участников совета, подписавших транзакцию.
+fn count_council_approvals(council: &EmergencyCouncil, signers: &[AccountInfo]) -> u8 {
+    let mut approved: Vec<Pubkey> = Vec::with_capacity(signers.len());
+    for account in signers {
+        if account.is_signer && council.members.contains(account.key) && !approved.contains(account.key) {
+            approved.push(*account.key);
+        }
+    }
+    approved.len() as u8
+}
+
+/// Набор адресов, хранящихся в реестре
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RegistryParams {
+    pub vc_mint: Pubkey,
+    pub vg_mint: Pubkey,
+    pub vc_token_program: Pubkey,
+    pub vg_token_program: Pubkey,
+    pub burn_and_earn_program: Pubkey,
+    pub dao_treasury: Pubkey,
+    pub fee_collector: Pubkey,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct EcosystemRegistry {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    /// Авторитет DAO, который может обновлять реестр
+    pub authority: Pubkey,
+    /// Минт VC токена
+    pub vc_mint: Pubkey,
+    /// Минт VG токена
+    pub vg_mint: Pubkey,
+    /// ID программы VC токена
+    pub vc_token_program: Pubkey,
+    /// ID программы VG токена
+    pub vg_token_program: Pubkey,
+    /// ID программы Burn and Earn
+    pub burn_and_earn_program: Pubkey,
+    /// Владелец токен-аккаунтов казны DAO
+    pub dao_treasury: Pubkey,
+    /// Владелец токен-аккаунтов сборщика налога для держателей NFT Fee Key
+    pub fee_collector: Pubkey,
+    /// Флаг аварийной паузы для всей экосистемы
+    pub paused: bool,
+    /// Причина последней паузы
+    #[max_len(MAX_PAUSE_REASON_LEN)]
+    pub pause_reason: String,
+    /// Временная метка последней паузы
+    pub paused_at: i64,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl EcosystemRegistry {
+    pub const VERSION: u8 = 1;
+
+    fn apply(&mut self, params: &RegistryParams) {
+        self.vc_mint = params.vc_mint;
+        self.vg_mint = params.vg_mint;
+        self.vc_token_program = params.vc_token_program;
+        self.vg_token_program = params.vg_token_program;
+        self.burn_and_earn_program = params.burn_and_earn_program;
+        self.dao_treasury = params.dao_treasury;
+        self.fee_collector = params.fee_collector;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct EmergencyCouncil {
+    /// Версия раскладки аккаунта
+    pub version: u8,
+    /// Участники мультиподписи
+    #[max_len(MAX_COUNCIL_MEMBERS)]
+    pub members: Vec<Pubkey>,
+    /// Количество подписей, необходимое для паузы
+    pub pause_threshold: u8,
+    /// Количество подписей, необходимое для снятия паузы (строго больше pause_threshold)
+    pub unpause_threshold: u8,
+    /// Bump для PDA
+    pub bump: u8,
+}
+
+impl EmergencyCouncil {
+    pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    /// Upgrade authority программы реестра
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ RegistryError::Unauthorized)]
+    pub program: Program<'info, crate::program::TechHyContracts>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ RegistryError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + EcosystemRegistry::INIT_SPACE,
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRegistry<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        has_one = authority @ RegistryError::Unauthorized,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeEmergencyCouncil<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        has_one = authority @ RegistryError::Unauthorized,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + EmergencyCouncil::INIT_SPACE,
+        seeds = [EMERGENCY_COUNCIL_SEED],
+        bump
+    )]
+    pub emergency_council: Account<'info, EmergencyCouncil>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
-pub struct Initialize {}
+pub struct UpdateEmergencyCouncil<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+        has_one = authority @ RegistryError::Unauthorized,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        mut,
+        seeds = [EMERGENCY_COUNCIL_SEED],
+        bump = emergency_council.bump,
+    )]
+    pub emergency_council: Account<'info, EmergencyCouncil>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyAction<'info> {
+    #[account(
+        mut,
+        seeds = [ECOSYSTEM_REGISTRY_SEED],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, EcosystemRegistry>,
+
+    #[account(
+        seeds = [EMERGENCY_COUNCIL_SEED],
+        bump = emergency_council.bump,
+    )]
+    pub emergency_council: Account<'info, EmergencyCouncil>,
+    // Подписи участников совета передаются через remaining_accounts
+}
+
+#[event]
+pub struct RegistryUpdated {
+    pub vc_mint: Pubkey,
+    pub vg_mint: Pubkey,
+    pub dao_treasury: Pubkey,
+    pub fee_collector: Pubkey,
+}
+
+#[event]
+pub struct EmergencyCouncilUpdated {
+    pub members: Vec<Pubkey>,
+    pub pause_threshold: u8,
+    pub unpause_threshold: u8,
+}
+
+#[event]
+pub struct EmergencyPaused {
+    pub reason: String,
+    pub approvals: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyUnpaused {
+    pub approvals: u8,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum RegistryError {
+    #[msg("Операция не авторизована")]
+    Unauthorized,
+    #[msg("Недостаточно подписей участников аварийного совета")]
+    InsufficientCouncilSignatures,
+    #[msg("Неверная конфигурация аварийного совета")]
+    InvalidCouncilConfig,
+    #[msg("Экосистема уже на паузе")]
+    AlreadyPaused,
+    #[msg("Экосистема не на паузе")]
+    NotPaused,
+    #[msg("Слишком длинная причина паузы")]
+    PauseReasonTooLong,
+}
@@ -1,16 +1,176 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, BPF_LOADER_UPGRADEABLE_PROGRAM_ID } from "@solana/web3.js";
+import { assert } from "chai";
 import { TechHyContracts } from "../target/types/tech_hy_contracts";
 
-describe("tech_hy_contracts", () => {
-  // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+describe("tech_hy_contracts (ecosystem registry)", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.techHyContracts as Program<TechHyContracts>;
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+  const [registryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("ecosystem_registry")],
+    program.programId
+  );
+
+  // ProgramData реестра: validator разворачивает программу с кошельком провайдера как upgrade authority
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  );
+  const [councilPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("emergency_council")],
+    program.programId
+  );
+
+  const expectError = async (promise: Promise<unknown>, errorName: string) => {
+    try {
+      await promise;
+      assert.fail(`Ожидалась ошибка ${errorName}`);
+    } catch (error) {
+      assert.include(error.toString(), errorName);
+    }
+  };
+
+  const params = {
+    vcMint: Keypair.generate().publicKey,
+    vgMint: Keypair.generate().publicKey,
+    vcTokenProgram: new PublicKey("VCzfGwp5qVL8pmta1GHqGrSQqzMa5qsY4M1jbjsdaYJ"),
+    vgTokenProgram: new PublicKey("VGnHJHKr2NwxSdQQoYrJY9TBZ9YHS5cCwBPEr68mEPG"),
+    burnAndEarnProgram: new PublicKey("BAEpWRJiqZrZkmyzGbcBAvQYpRKbRq5L3D5WwA1dvYf5"),
+    daoTreasury: Keypair.generate().publicKey,
+    feeCollector: Keypair.generate().publicKey,
+  };
+
+  it("Отклоняет инициализацию реестра не upgrade authority", async () => {
+    const stranger = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(stranger.publicKey, 1_000_000_000)
+    );
+
+    await expectError(
+      program.methods
+        .initializeRegistry(params)
+        .accounts({ authority: stranger.publicKey, programData })
+        .signers([stranger])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+
+  it("Инициализирует реестр экосистемы", async () => {
+    await program.methods
+      .initializeRegistry(params)
+      .accounts({ authority: provider.publicKey, programData })
+      .rpc();
+
+    const registry = await program.account.ecosystemRegistry.fetch(registryPda);
+    assert.equal(registry.authority.toBase58(), provider.publicKey.toBase58());
+    assert.equal(registry.vgMint.toBase58(), params.vgMint.toBase58());
+    assert.isFalse(registry.paused);
+  });
+
+  it("Отклоняет обновление реестра не-авторитетом", async () => {
+    const stranger = Keypair.generate();
+    try {
+      await program.methods
+        .updateRegistry(params)
+        .accounts({ authority: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Обновление должно быть доступно только авторитету DAO");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized");
+    }
+  });
+
+  describe("Ротация аварийного совета", () => {
+    const members = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const rotated = [Keypair.generate(), Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const memberSigners = (keys: Keypair[]) =>
+      keys.map((member) => ({ pubkey: member.publicKey, isSigner: true, isWritable: false }));
+
+    before("Создание совета 1-of-3 / 2-of-3", async () => {
+      await program.methods
+        .initializeEmergencyCouncil(
+          members.map((member) => member.publicKey),
+          1,
+          2
+        )
+        .accounts({ payer: provider.publicKey, authority: provider.publicKey })
+        .rpc();
+    });
+
+    it("Отклоняет ротацию не-авторитетом", async () => {
+      const stranger = Keypair.generate();
+      await expectError(
+        program.methods
+          .updateEmergencyCouncil(
+            rotated.map((member) => member.publicKey),
+            2,
+            3
+          )
+          .accounts({ authority: stranger.publicKey })
+          .signers([stranger])
+          .rpc(),
+        "Unauthorized"
+      );
+    });
+
+    it("Отклоняет ротацию с неверными порогами", async () => {
+      await expectError(
+        program.methods
+          .updateEmergencyCouncil(
+            rotated.map((member) => member.publicKey),
+            3,
+            3
+          )
+          .accounts({ authority: provider.publicKey })
+          .rpc(),
+        "InvalidCouncilConfig"
+      );
+    });
+
+    it("Заменяет состав совета: старые участники больше не могут ставить паузу", async () => {
+      await program.methods
+        .updateEmergencyCouncil(
+          rotated.map((member) => member.publicKey),
+          2,
+          3
+        )
+        .accounts({ authority: provider.publicKey })
+        .rpc();
+
+      const council = await program.account.emergencyCouncil.fetch(councilPda);
+      assert.deepEqual(
+        council.members.map((member) => member.toBase58()),
+        rotated.map((member) => member.publicKey.toBase58())
+      );
+      assert.equal(council.pauseThreshold, 2);
+      assert.equal(council.unpauseThreshold, 3);
+
+      await expectError(
+        program.methods
+          .pause("Старый состав")
+          .remainingAccounts(memberSigners(members))
+          .signers(members)
+          .rpc(),
+        "InsufficientCouncilSignatures"
+      );
+
+      await program.methods
+        .pause("Новый состав")
+        .remainingAccounts(memberSigners(rotated.slice(0, 2)))
+        .signers(rotated.slice(0, 2))
+        .rpc();
+      await program.methods
+        .unpause()
+        .remainingAccounts(memberSigners(rotated.slice(0, 3)))
+        .signers(rotated.slice(0, 3))
+        .rpc();
+      assert.isFalse((await program.account.ecosystemRegistry.fetch(registryPda)).paused);
+    });
   });
 });
@@ -0,0 +1,373 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { startAnchor, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { assert } from "chai";
+import { VgToken } from "../target/types/vg_token";
+import { BurnAndEarn } from "../target/types/burn_and_earn";
+import { TechHyContracts } from "../target/types/tech_hy_contracts";
+import vgTokenIdl from "../target/idl/vg_token.json";
+import burnAndEarnIdl from "../target/idl/burn_and_earn.json";
+import registryIdl from "../target/idl/tech_hy_contracts.json";
+import { initializeRegistry, initializeVgToken, vgHookAccounts } from "./utils/bankrun-utils";
+
+// Инструкции migrate_*: устаревшая раскладка записывается напрямую в bankrun,
+// после миграции проверяются все поля, размер аккаунта и рента (resize_account).
+describe("Account Migration Tests", () => {
+  const ONE_TOKEN = BigInt(10 ** 9);
+  const VG_TOTAL_SUPPLY = BigInt(1_000_000_000) * ONE_TOKEN;
+  // Значения burn-and-earn по умолчанию для полей, которых не было в V0
+  const DEFAULT_EPOCH_VG_BUDGET = BigInt(10_000_000) * ONE_TOKEN;
+  const DEFAULT_EMISSION_DECAY_BPS = 10_000;
+
+  // Размеры текущих раскладок: 8 + INIT_SPACE
+  const TAX_CONFIG_SPACE = 8 + 1 + 32 + 2 * 3;
+  const BURN_AND_EARN_STATE_SPACE = 8 + 1 + 32 * 3 + 8 * 3 + 1 + 8 * 4 + 2 + 1 + 16 + 8;
+  const USER_LP_RECORD_SPACE = 8 + 1 + 32 + 1 + 8 * 3 + 1 + 8 * 3 + 16 + 8;
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let vgTokenProgram: Program<VgToken>;
+  let burnAndEarnProgram: Program<BurnAndEarn>;
+
+  const holder = Keypair.generate();
+  const daoTreasury = Keypair.generate();
+  const feeCollector = Keypair.generate();
+  let vgMint: PublicKey;
+
+  const u8 = (value: number) => Buffer.from([value]);
+  const u16 = (value: number) => {
+    const buffer = Buffer.alloc(2);
+    buffer.writeUInt16LE(value);
+    return buffer;
+  };
+  const u64 = (value: bigint) => {
+    const buffer = Buffer.alloc(8);
+    buffer.writeBigUInt64LE(value);
+    return buffer;
+  };
+  const i64 = (value: bigint) => {
+    const buffer = Buffer.alloc(8);
+    buffer.writeBigInt64LE(value);
+    return buffer;
+  };
+
+  const discriminator = (idl: { accounts?: { name: string; discriminator: number[] }[] }, name: string) =>
+    Buffer.from(idl.accounts!.find((account) => account.name === name)!.discriminator);
+
+  const rentFor = async (len: number) => (await context.banksClient.getRent()).minimumBalance(BigInt(len));
+
+  const getLamports = async (address: PublicKey) => {
+    const account = await context.banksClient.getAccount(address);
+    return account === null ? BigInt(0) : BigInt(account.lamports);
+  };
+
+  const getDataLength = async (address: PublicKey) => (await context.banksClient.getAccount(address))!.data.length;
+
+  // Записывает устаревшую раскладку с рентой ровно под ее размер
+  const writeLegacyAccount = async (address: PublicKey, owner: PublicKey, data: Buffer) => {
+    context.setAccount(address, {
+      lamports: Number(await rentFor(data.length)),
+      data,
+      owner,
+      executable: false,
+    });
+  };
+
+  const fundLamports = (owner: PublicKey) =>
+    context.setAccount(owner, {
+      lamports: 1_000_000_000,
+      data: Buffer.alloc(0),
+      owner: anchor.web3.SystemProgram.programId,
+      executable: false,
+    });
+
+  const expectError = async (promise: Promise<unknown>, errorName: string) => {
+    try {
+      await promise;
+      assert.fail(`Ожидалась ошибка ${errorName}`);
+    } catch (error) {
+      assert.include(error.toString(), errorName);
+    }
+  };
+
+  before("Подготовка bankrun, реестра и минта VG", async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    vgTokenProgram = new Program<VgToken>(vgTokenIdl as VgToken, provider);
+    burnAndEarnProgram = new Program<BurnAndEarn>(burnAndEarnIdl as BurnAndEarn, provider);
+    const registryProgram = new Program<TechHyContracts>(registryIdl as TechHyContracts, provider);
+
+    const [vgMintPda] = PublicKey.findProgramAddressSync([Buffer.from("vg_token_mint")], vgTokenProgram.programId);
+    await initializeRegistry(context, registryProgram, {
+      vcMint: Keypair.generate().publicKey,
+      vgMint: vgMintPda,
+      vcTokenProgram: Keypair.generate().publicKey,
+      vgTokenProgram: TOKEN_2022_PROGRAM_ID,
+      burnAndEarnProgram: burnAndEarnProgram.programId,
+      daoTreasury: daoTreasury.publicKey,
+      feeCollector: feeCollector.publicKey,
+    });
+    vgMint = await initializeVgToken(provider, vgTokenProgram, holder.publicKey);
+    fundLamports(holder.publicKey);
+  });
+
+  describe("vg-token: migrate_tax_config", () => {
+    // Первый байт авторитета совпадает с TaxConfig::VERSION: одной проверки версии недостаточно
+    const dao = (() => {
+      let keypair: Keypair;
+      do {
+        keypair = Keypair.generate();
+      } while (keypair.publicKey.toBuffer()[0] !== 1);
+      return keypair;
+    })();
+    let taxConfigPda: PublicKey;
+
+    const ata = (owner: PublicKey) => getAssociatedTokenAddressSync(vgMint, owner, true, TOKEN_2022_PROGRAM_ID);
+
+    const transferVg = (recipient: PublicKey) =>
+      vgTokenProgram.methods
+        .transferWithTax(new BN((BigInt(10) * ONE_TOKEN).toString()))
+        .accountsPartial({
+          senderAuthority: holder.publicKey,
+          mintAccount: vgMint,
+          senderTokenAccount: ata(holder.publicKey),
+          recipientAuthority: recipient,
+          recipientTokenAccount: ata(recipient),
+          daoTreasuryAuthority: daoTreasury.publicKey,
+          daoTreasuryTokenAccount: ata(daoTreasury.publicKey),
+          feeCollectorAuthority: feeCollector.publicKey,
+          feeCollectorTokenAccount: ata(feeCollector.publicKey),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts(vgHookAccounts())
+        .signers([holder])
+        .rpc();
+
+    const migrate = (authority: Keypair) =>
+      vgTokenProgram.methods.migrateTaxConfig().accounts({ authority: authority.publicKey }).signers([authority]).rpc();
+
+    before("Запись TaxConfig V0 (без байта версии, с адресами казны)", async () => {
+      [taxConfigPda] = PublicKey.findProgramAddressSync([Buffer.from("tax_config_seed")], vgTokenProgram.programId);
+      fundLamports(dao.publicKey);
+      await writeLegacyAccount(
+        taxConfigPda,
+        vgTokenProgram.programId,
+        Buffer.concat([
+          discriminator(vgTokenIdl, "TaxConfig"),
+          dao.publicKey.toBuffer(),
+          u16(700),
+          u16(6_000),
+          u16(4_000),
+          Keypair.generate().publicKey.toBuffer(),
+          Keypair.generate().publicKey.toBuffer(),
+        ])
+      );
+    });
+
+    it("Отклоняет перевод по конфигурации V0 с AccountNeedsMigration", async () => {
+      await expectError(transferVg(Keypair.generate().publicKey), "AccountNeedsMigration");
+    });
+
+    it("Отклоняет миграцию не авторитетом из конфигурации", async () => {
+      const stranger = Keypair.generate();
+      fundLamports(stranger.publicKey);
+      await expectError(migrate(stranger), "Unauthorized");
+    });
+
+    it("Мигрирует V0: переносит ставки, уменьшает аккаунт и возвращает излишек ренты", async () => {
+      const legacyRent = await rentFor(110);
+      const daoBefore = await getLamports(dao.publicKey);
+
+      await migrate(dao);
+
+      const config = await vgTokenProgram.account.taxConfig.fetch(taxConfigPda);
+      assert.equal(config.version, 1);
+      assert.equal(config.authority.toBase58(), dao.publicKey.toBase58());
+      assert.equal(config.taxRateBps, 700);
+      assert.equal(config.daoShareBps, 6_000);
+      assert.equal(config.nftHoldersShareBps, 4_000);
+      assert.equal(await getDataLength(taxConfigPda), TAX_CONFIG_SPACE);
+      assert.equal(await getLamports(taxConfigPda), await rentFor(TAX_CONFIG_SPACE));
+      assert.equal(await getLamports(dao.publicKey), daoBefore + legacyRent - (await rentFor(TAX_CONFIG_SPACE)));
+    });
+
+    it("Отклоняет повторную миграцию", async () => {
+      await expectError(migrate(dao), "AlreadyMigrated");
+    });
+
+    it("После миграции переводы проходят со ставкой из мигрированной конфигурации", async () => {
+      const recipient = Keypair.generate().publicKey;
+      await transferVg(recipient);
+      const account = await context.banksClient.getAccount(ata(recipient));
+      assert.isNotNull(account);
+    });
+  });
+
+  describe("burn-and-earn: migrate_burn_and_earn_state", () => {
+    const authority = Keypair.generate();
+    const vcMint = Keypair.generate().publicKey;
+    const legacyMint = Keypair.generate().publicKey;
+    let statePda: PublicKey;
+    let stateBump: number;
+
+    const legacyBody = () =>
+      Buffer.concat([
+        authority.publicKey.toBuffer(),
+        vcMint.toBuffer(),
+        legacyMint.toBuffer(),
+        u64(BigInt(1_500) * ONE_TOKEN),
+        u64(BigInt(40_000) * ONE_TOKEN),
+        u64(BigInt(3_000) * ONE_TOKEN),
+        u8(stateBump),
+      ]);
+
+    const migrate = () =>
+      burnAndEarnProgram.methods
+        .migrateBurnAndEarnState()
+        .accounts({ authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+    const assertMigrated = async () => {
+      const state = await burnAndEarnProgram.account.burnAndEarnState.fetch(statePda);
+      assert.equal(state.version, 1);
+      assert.equal(state.authority.toBase58(), authority.publicKey.toBase58());
+      assert.equal(state.vcMint.toBase58(), vcMint.toBase58());
+      assert.equal(state.vgMint.toBase58(), legacyMint.toBase58());
+      assert.equal(state.totalLockedLp.toString(), (BigInt(1_500) * ONE_TOKEN).toString());
+      assert.equal(state.totalVgMinted.toString(), (BigInt(40_000) * ONE_TOKEN).toString());
+      assert.equal(state.totalVcBurned.toString(), (BigInt(3_000) * ONE_TOKEN).toString());
+      assert.equal(state.bump, stateBump);
+      assert.equal(state.escrowRemaining.toString(), (VG_TOTAL_SUPPLY - BigInt(40_000) * ONE_TOKEN).toString());
+      assert.equal(state.epochVgBudget.toString(), DEFAULT_EPOCH_VG_BUDGET.toString());
+      assert.equal(state.currentEpoch.toNumber(), 0);
+      assert.equal(state.epochVgEmitted.toNumber(), 0);
+      assert.equal(state.emissionDecayBps, DEFAULT_EMISSION_DECAY_BPS);
+      assert.isTrue(state.proRateOverCap);
+      assert.equal(state.accFeePerLp.toString(), "0");
+      assert.equal(state.totalLpFeesHarvested.toNumber(), 0);
+    };
+
+    before(() => {
+      [statePda, stateBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("burn_and_earn_state")],
+        burnAndEarnProgram.programId
+      );
+      fundLamports(authority.publicKey);
+    });
+
+    it("Мигрирует V0 (без байта версии, 64 байта резерва) и возвращает излишек ренты authority", async () => {
+      const legacy = Buffer.concat([discriminator(burnAndEarnIdl, "BurnAndEarnState"), legacyBody(), Buffer.alloc(64)]);
+      await writeLegacyAccount(statePda, burnAndEarnProgram.programId, legacy);
+      const authorityBefore = await getLamports(authority.publicKey);
+
+      await migrate();
+
+      await assertMigrated();
+      assert.equal(await getDataLength(statePda), BURN_AND_EARN_STATE_SPACE);
+      assert.equal(await getLamports(statePda), await rentFor(BURN_AND_EARN_STATE_SPACE));
+      assert.equal(
+        await getLamports(authority.publicKey),
+        authorityBefore - ((await rentFor(BURN_AND_EARN_STATE_SPACE)) - (await rentFor(legacy.length)))
+      );
+    });
+
+    it("Отклоняет повторную миграцию", async () => {
+      await expectError(migrate(), "AlreadyMigrated");
+    });
+  });
+
+  describe("burn-and-earn: migrate_user_lp_record", () => {
+    const owner = Keypair.generate().publicKey;
+    const payer = Keypair.generate();
+    let recordPda: PublicKey;
+
+    const migrate = () =>
+      burnAndEarnProgram.methods
+        .migrateUserLpRecord(owner)
+        .accounts({ payer: payer.publicKey })
+        .signers([payer])
+        .rpc();
+
+    const assertMigrated = async (expectedTier: object) => {
+      const record = await burnAndEarnProgram.account.userLpRecord.fetch(recordPda);
+      assert.equal(record.version, 1);
+      assert.equal(record.owner.toBase58(), owner.toBase58());
+      assert.isTrue(record.isInitialized);
+      assert.equal(record.lockedLp.toString(), (BigInt(12_000) * ONE_TOKEN).toString());
+      assert.equal(record.vgMinted.toString(), (BigInt(250_000) * ONE_TOKEN).toString());
+      assert.equal(record.vcBurned.toString(), (BigInt(24_000) * ONE_TOKEN).toString());
+      assert.deepEqual(record.nftLevel, expectedTier);
+      assert.equal(record.lastUpdate.toNumber(), 1_700_000_000);
+      assert.equal(record.vcEpoch.toNumber(), 0);
+      assert.equal(record.epochVcBurned.toNumber(), 0);
+      assert.equal(record.feeDebt.toString(), "0");
+      assert.equal(record.feesOwed.toNumber(), 0);
+      assert.equal(await getDataLength(recordPda), USER_LP_RECORD_SPACE);
+      assert.equal(await getLamports(recordPda), await rentFor(USER_LP_RECORD_SPACE));
+    };
+
+    const amounts = () =>
+      Buffer.concat([
+        u64(BigInt(12_000) * ONE_TOKEN),
+        u64(BigInt(250_000) * ONE_TOKEN),
+        u64(BigInt(24_000) * ONE_TOKEN),
+      ]);
+
+    before(() => {
+      [recordPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_lp_record"), owner.toBuffer()],
+        burnAndEarnProgram.programId
+      );
+      fundLamports(payer.publicKey);
+    });
+
+    it("Мигрирует V0: уровень NFT из u8 становится FeeKeyTier, излишек ренты получает плательщик", async () => {
+      const legacy = Buffer.concat([
+        discriminator(burnAndEarnIdl, "UserLpRecord"),
+        owner.toBuffer(),
+        u8(1),
+        amounts(),
+        u8(2), // Silver
+        i64(BigInt(1_700_000_000)),
+        Buffer.alloc(64),
+      ]);
+      await writeLegacyAccount(recordPda, burnAndEarnProgram.programId, legacy);
+      const payerBefore = await getLamports(payer.publicKey);
+
+      await migrate();
+
+      await assertMigrated({ silver: {} });
+      assert.equal(
+        await getLamports(payer.publicKey),
+        payerBefore - ((await rentFor(USER_LP_RECORD_SPACE)) - (await rentFor(legacy.length)))
+      );
+    });
+
+    it("Мигрирует V0 с неизвестным уровнем: уровень пересчитывается по заблокированным LP", async () => {
+      const legacy = Buffer.concat([
+        discriminator(burnAndEarnIdl, "UserLpRecord"),
+        owner.toBuffer(),
+        u8(1),
+        amounts(),
+        u8(9),
+        i64(BigInt(1_700_000_000)),
+        Buffer.alloc(64),
+      ]);
+      await writeLegacyAccount(recordPda, burnAndEarnProgram.programId, legacy);
+
+      await migrate();
+
+      // 12,000 LP - уровень Silver
+      await assertMigrated({ silver: {} });
+    });
+
+    it("Отклоняет повторную миграцию", async () => {
+      await expectError(migrate(), "AlreadyMigrated");
+    });
+  });
+});
@@ -0,0 +1,806 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { startAnchor, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { assert } from "chai";
+import { BurnAndEarn } from "../target/types/burn_and_earn";
+import { TechHyContracts } from "../target/types/tech_hy_contracts";
+import { MockAmm } from "../target/types/mock_amm";
+import burnAndEarnIdl from "../target/idl/burn_and_earn.json";
+import registryIdl from "../target/idl/tech_hy_contracts.json";
+import mockAmmIdl from "../target/idl/mock_amm.json";
+import {
+  createMint,
+  mintToOwner,
+  getTokenBalance,
+  getUnixTimestamp,
+  warpToTimestamp,
+  warpToEpoch,
+  warpToSlot,
+  initializeRegistry,
+  setRegistryPaused,
+} from "./utils/bankrun-utils";
+
+describe("Burn and Earn Tests", () => {
+  const TOKEN_DECIMALS = 9;
+  const ONE_TOKEN = BigInt(10 ** TOKEN_DECIMALS);
+  const VC_AMOUNT = new BN((BigInt(100) * ONE_TOKEN).toString());
+  // Имитация пула: LP = VC * 0.5
+  const EXPECTED_LP = new BN((BigInt(50) * ONE_TOKEN).toString());
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<BurnAndEarn>;
+  let registryProgram: Program<TechHyContracts>;
+
+  const user = Keypair.generate();
+  const ESCROW_SUPPLY = BigInt(1_000_000_000) * ONE_TOKEN;
+  let vcMint: PublicKey;
+  let vgMint: PublicKey;
+  let userVcTokenAccount: PublicKey;
+
+  const findUserLpRecord = (owner: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("user_lp_record"), owner.toBuffer()], program.programId)[0];
+
+  const burnAndLockAs = (
+    signer: Keypair,
+    signerVcAccount: PublicKey,
+    vcAmount: BN,
+    minLpOut: BN,
+    minVgOut: BN,
+    deadline: BN
+  ) =>
+    program.methods
+      .burnAndLock(vcAmount, minLpOut, minVgOut, deadline, null)
+      .accounts({
+        user: signer.publicKey,
+        vcMint,
+        userVcTokenAccount: signerVcAccount,
+        vgMint,
+        userVgTokenAccount: getAssociatedTokenAddressSync(vgMint, signer.publicKey, false, TOKEN_PROGRAM_ID),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        referralConfig: null,
+        referrerLpRecord: null,
+        referrerStats: null,
+      })
+      .signers([signer])
+      .rpc();
+
+  const burnAndLock = (vcAmount: BN, minLpOut: BN, minVgOut: BN, deadline: BN) =>
+    burnAndLockAs(user, userVcTokenAccount, vcAmount, minLpOut, minVgOut, deadline);
+
+  const fundUser = async (owner: Keypair) => {
+    context.setAccount(owner.publicKey, {
+      lamports: 1_000_000_000,
+      data: Buffer.alloc(0),
+      owner: anchor.web3.SystemProgram.programId,
+      executable: false,
+    });
+    return mintToOwner(provider, vcMint, owner.publicKey, BigInt(1_000) * ONE_TOKEN);
+  };
+
+  const findState = () =>
+    PublicKey.findProgramAddressSync([Buffer.from("burn_and_earn_state")], program.programId)[0];
+
+  const updateEmissionConfig = (epochBudget: BN, decayBps: number, proRate: boolean) =>
+    program.methods
+      .updateEmissionConfig(epochBudget, decayBps, proRate)
+      .accounts({ authority: provider.wallet.publicKey })
+      .rpc();
+
+  const quote = (owner: PublicKey, vcAmount: BN, withRecord: boolean) =>
+    program.methods
+      .quoteBurnAndLock(vcAmount)
+      .accounts({
+        user: owner,
+        userLpRecord: withRecord ? findUserLpRecord(owner) : null,
+      })
+      .view();
+
+  const deadlineIn = async (seconds: number) => new BN((await getUnixTimestamp(context)) + seconds);
+
+  before("Подготовка bankrun, реестра и Burn and Earn", async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<BurnAndEarn>(burnAndEarnIdl as BurnAndEarn, provider);
+    registryProgram = new Program<TechHyContracts>(registryIdl as TechHyContracts, provider);
+
+    vcMint = await createMint(provider, TOKEN_DECIMALS);
+    vgMint = await createMint(provider, TOKEN_DECIMALS);
+
+    await initializeRegistry(context, registryProgram, {
+      vcMint,
+      vgMint,
+      vcTokenProgram: Keypair.generate().publicKey,
+      vgTokenProgram: Keypair.generate().publicKey,
+      burnAndEarnProgram: program.programId,
+      daoTreasury: Keypair.generate().publicKey,
+      feeCollector: Keypair.generate().publicKey,
+    });
+
+    await program.methods
+      .initialize()
+      .accounts({ authority: provider.wallet.publicKey, vcMint, vgMint })
+      .rpc();
+
+    userVcTokenAccount = await fundUser(user);
+  });
+
+  describe("Котировка", () => {
+    it("Считает котировку для пользователя без записи", async () => {
+      const result = await quote(user.publicKey, VC_AMOUNT, false);
+      assert.equal(result.lpAmount.toString(), EXPECTED_LP.toString());
+      // VG = 50 * 10 * (1 + 0.2 * log10(50)) ≈ 669.9
+      assert.equal(result.vgAmount.div(new BN(ONE_TOKEN.toString())).toNumber(), 669);
+      assert.deepEqual(result.currentTier, { none: {} });
+      assert.deepEqual(result.resultingTier, { none: {} });
+      // Остаток эскроу уже за вычетом VG этой операции
+      assert.equal(result.escrowRemaining.add(result.vgAmount).toString(), ESCROW_SUPPLY.toString());
+    });
+
+    it("Показывает уровень NFT Fee Key, который даст операция", async () => {
+      // 2,000 VC -> 1,000 LP = порог Bronze
+      const result = await quote(user.publicKey, new BN((BigInt(2_000) * ONE_TOKEN).toString()), false);
+      assert.deepEqual(result.resultingTier, { bronze: {} });
+    });
+
+    it("Определяет уровни NFT Fee Key по включительным порогам LP", async () => {
+      const tierFor = async (vcTokens: bigint) =>
+        (await quote(user.publicKey, new BN((vcTokens * ONE_TOKEN).toString()), false)).resultingTier;
+
+      // LP = VC * 0.5: пороги 1,000 / 10,000 / 100,000 / 1,000,000 LP
+      assert.deepEqual(await tierFor(BigInt(1_998)), { none: {} });
+      assert.deepEqual(await tierFor(BigInt(19_998)), { bronze: {} });
+      assert.deepEqual(await tierFor(BigInt(20_000)), { silver: {} });
+      assert.deepEqual(await tierFor(BigInt(200_000)), { gold: {} });
+      assert.deepEqual(await tierFor(BigInt(2_000_000)), { platinum: {} });
+    });
+
+    it("IDL экспортирует уровень как FeeKeyTier с вариантами в порядке сериализации", () => {
+      const tierType = burnAndEarnIdl.types.find((type) => type.name === "FeeKeyTier");
+      assert.isDefined(tierType, "В IDL нет типа FeeKeyTier");
+      assert.isUndefined(burnAndEarnIdl.types.find((type) => type.name === "NftFeeKeyTier"));
+      assert.deepEqual(
+        (tierType!.type as { variants: { name: string }[] }).variants.map((variant) => variant.name),
+        ["None", "Bronze", "Silver", "Gold", "Platinum"]
+      );
+    });
+
+    it("Отклоняет нулевую сумму", async () => {
+      try {
+        await quote(user.publicKey, new BN(0), false);
+        assert.fail("Котировка нулевой суммы должна быть отклонена");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidAmount");
+      }
+    });
+  });
+
+  describe("Защита от проскальзывания", () => {
+    it("Отклоняет транзакцию после дедлайна", async () => {
+      const deadline = await deadlineIn(10);
+      await warpToTimestamp(context, deadline.toNumber() + 1);
+      try {
+        await burnAndLock(VC_AMOUNT, new BN(0), new BN(0), deadline);
+        assert.fail("Транзакция после дедлайна должна быть отклонена");
+      } catch (error) {
+        assert.include(error.toString(), "DeadlineExceeded");
+      }
+    });
+
+    it("Отклоняет выход пула меньше min_lp_out", async () => {
+      try {
+        await burnAndLock(VC_AMOUNT, EXPECTED_LP.addn(1), new BN(0), await deadlineIn(60));
+        assert.fail("Выход LP меньше минимального должен быть отклонен");
+      } catch (error) {
+        assert.include(error.toString(), "LpSlippageExceeded");
+      }
+    });
+
+    it("Отклоняет эмиссию меньше min_vg_out", async () => {
+      const tooMuchVg = new BN((BigInt(1_000) * ONE_TOKEN).toString());
+      try {
+        await burnAndLock(VC_AMOUNT, EXPECTED_LP, tooMuchVg, await deadlineIn(60));
+        assert.fail("Эмиссия VG меньше минимальной должна быть отклонена");
+      } catch (error) {
+        assert.include(error.toString(), "VgSlippageExceeded");
+      }
+    });
+
+    it("Проходит при выполненных границах", async () => {
+      // VG = 50 * 10 * (1 + 0.2 * log10(50)) ≈ 669.9
+      const minVg = new BN((BigInt(669) * ONE_TOKEN).toString());
+      await burnAndLock(VC_AMOUNT, EXPECTED_LP, minVg, await deadlineIn(60));
+
+      const record = await program.account.userLpRecord.fetch(findUserLpRecord(user.publicKey));
+      assert.equal(record.lockedLp.toString(), EXPECTED_LP.toString());
+      assert.isTrue(record.vgMinted.gte(minVg));
+    });
+
+    it("Котировка учитывает уже заблокированные LP пользователя", async () => {
+      const fresh = await quote(Keypair.generate().publicKey, VC_AMOUNT, false);
+      const existing = await quote(user.publicKey, VC_AMOUNT, true);
+      assert.equal(existing.lpAmount.toString(), fresh.lpAmount.toString());
+      // Логарифмический бонус растет с объемом, поэтому прирост для второй блокировки больше
+      assert.isTrue(existing.vgAmount.gt(fresh.vgAmount));
+    });
+  });
+
+  describe("Лимиты эмиссии", () => {
+    const capUser = Keypair.generate();
+    const BUDGET_HEADROOM = new BN((BigInt(100) * ONE_TOKEN).toString());
+    let capUserVcAccount: PublicKey;
+
+    const burnAsCapUser = async (vcTokens: number) =>
+      burnAndLockAs(
+        capUser,
+        capUserVcAccount,
+        new BN((BigInt(vcTokens) * ONE_TOKEN).toString()),
+        new BN(0),
+        new BN(0),
+        await deadlineIn(60)
+      );
+
+    const expectCapReached = async (vcTokens: number) => {
+      try {
+        await burnAsCapUser(vcTokens);
+        assert.fail("Эмиссия сверх лимита должна быть отклонена");
+      } catch (error) {
+        assert.include(error.toString(), "EmissionCapReached");
+      }
+    };
+
+    before(async () => {
+      capUserVcAccount = await fundUser(capUser);
+    });
+
+    it("Отклоняет изменение лимитов не администратором", async () => {
+      const stranger = Keypair.generate();
+      try {
+        await program.methods
+          .updateEmissionConfig(new BN(0), 0, true)
+          .accounts({ authority: stranger.publicKey })
+          .signers([stranger])
+          .rpc();
+        assert.fail("Лимиты эмиссии меняет только администратор");
+      } catch (error) {
+        assert.include(error.toString(), "Unauthorized");
+      }
+    });
+
+    it("Без урезания отклоняет эмиссию сверх бюджета эпохи", async () => {
+      const state = await program.account.burnAndEarnState.fetch(findState());
+      // Бюджет эпохи оставляет 100 VG, а 100 VC дают около 670 VG
+      await updateEmissionConfig(state.epochVgEmitted.add(BUDGET_HEADROOM), 10_000, false);
+      await expectCapReached(100);
+    });
+
+    it("С урезанием выдает остаток бюджета эпохи", async () => {
+      const state = await program.account.burnAndEarnState.fetch(findState());
+      await updateEmissionConfig(state.epochVgBudget, 10_000, true);
+      await burnAsCapUser(100);
+
+      const record = await program.account.userLpRecord.fetch(findUserLpRecord(capUser.publicKey));
+      assert.equal(record.vgMinted.toString(), BUDGET_HEADROOM.toString());
+      const after = await program.account.burnAndEarnState.fetch(findState());
+      assert.equal(after.epochVgEmitted.toString(), after.epochVgBudget.toString());
+      assert.equal(after.escrowRemaining.add(after.totalVgMinted).toString(), ESCROW_SUPPLY.toString());
+    });
+
+    it("Исчерпанный бюджет отклоняет даже с урезанием", async () => {
+      await expectCapReached(101);
+    });
+
+    it("Бюджет восстанавливается в следующей эпохе", async () => {
+      const state = await program.account.burnAndEarnState.fetch(findState());
+      await warpToEpoch(context, state.currentEpoch.toNumber() + 1);
+      await burnAsCapUser(102);
+
+      const after = await program.account.burnAndEarnState.fetch(findState());
+      assert.equal(after.currentEpoch.toNumber(), state.currentEpoch.toNumber() + 1);
+      assert.isTrue(after.epochVgEmitted.gtn(0));
+      assert.isTrue(after.epochVgEmitted.lte(after.epochVgBudget));
+    });
+
+    it("Затухание уменьшает эмиссию по мере роста total_vg_minted", async () => {
+      const fresh = Keypair.generate().publicKey;
+      await updateEmissionConfig(new BN(0), 0, true);
+      const undecayed = await quote(fresh, VC_AMOUNT, false);
+      await updateEmissionConfig(new BN(0), 10_000, true);
+      const decayed = await quote(fresh, VC_AMOUNT, false);
+      assert.isTrue(decayed.vgAmount.lt(undecayed.vgAmount));
+    });
+  });
+
+  describe("Лимиты скорости", () => {
+    const vc = (tokens: number) => new BN((BigInt(tokens) * ONE_TOKEN).toString());
+    const limitUser = Keypair.generate();
+    const slotUserA = Keypair.generate();
+    const slotUserB = Keypair.generate();
+    let limitUserVcAccount: PublicKey;
+    let slotUserAVcAccount: PublicKey;
+    let slotUserBVcAccount: PublicKey;
+
+    const upsertRateLimits = (maxVcPerTx: BN, maxVcPerUserPerEpoch: BN, maxVcPerSlot: BN) =>
+      program.methods
+        .upsertRateLimitConfig({ maxVcPerTx, maxVcPerUserPerEpoch, maxVcPerSlot })
+        .accounts({ payer: provider.wallet.publicKey, daoAuthority: provider.wallet.publicKey })
+        .rpc();
+
+    const burnVc = async (signer: Keypair, vcAccount: PublicKey, tokens: number) =>
+      burnAndLockAs(signer, vcAccount, vc(tokens), new BN(0), new BN(0), await deadlineIn(60));
+
+    const expectError = async (promise: Promise<unknown>, errorName: string) => {
+      try {
+        await promise;
+        assert.fail(`Ожидалась ошибка ${errorName}`);
+      } catch (error) {
+        assert.include(error.toString(), errorName);
+      }
+    };
+
+    before(async () => {
+      limitUserVcAccount = await fundUser(limitUser);
+      slotUserAVcAccount = await fundUser(slotUserA);
+      slotUserBVcAccount = await fundUser(slotUserB);
+    });
+
+    it("Отклоняет изменение лимитов не-DAO", async () => {
+      const stranger = Keypair.generate();
+      await expectError(
+        program.methods
+          .upsertRateLimitConfig({ maxVcPerTx: new BN(0), maxVcPerUserPerEpoch: new BN(0), maxVcPerSlot: new BN(0) })
+          .accounts({ payer: provider.wallet.publicKey, daoAuthority: stranger.publicKey })
+          .signers([stranger])
+          .rpc(),
+        "Unauthorized"
+      );
+    });
+
+    it("Отклоняет сумму сверх лимита на транзакцию", async () => {
+      await upsertRateLimits(vc(50), vc(120), new BN(0));
+      await expectError(burnVc(limitUser, limitUserVcAccount, 51), "TxVcLimitExceeded");
+    });
+
+    it("Отклоняет сумму сверх лимита пользователя за эпоху", async () => {
+      await burnVc(limitUser, limitUserVcAccount, 50);
+      await burnVc(limitUser, limitUserVcAccount, 49);
+      await expectError(burnVc(limitUser, limitUserVcAccount, 30), "UserEpochVcLimitExceeded");
+    });
+
+    it("Лимит пользователя сбрасывается на границе эпохи", async () => {
+      const before = await program.account.userLpRecord.fetch(findUserLpRecord(limitUser.publicKey));
+      assert.equal(before.epochVcBurned.toString(), vc(99).toString());
+
+      await warpToEpoch(context, before.vcEpoch.toNumber() + 1);
+      await burnVc(limitUser, limitUserVcAccount, 31);
+
+      const after = await program.account.userLpRecord.fetch(findUserLpRecord(limitUser.publicKey));
+      assert.equal(after.vcEpoch.toNumber(), before.vcEpoch.toNumber() + 1);
+      assert.equal(after.epochVcBurned.toString(), vc(31).toString());
+    });
+
+    it("Последняя сумма эпохи укладывается в лимит ровно", async () => {
+      // 31 + 89 = 120 - ровно лимит эпохи
+      await upsertRateLimits(vc(100), vc(120), new BN(0));
+      await burnVc(limitUser, limitUserVcAccount, 89);
+      await expectError(burnVc(limitUser, limitUserVcAccount, 1), "UserEpochVcLimitExceeded");
+    });
+
+    it("Глобальный лимит слота суммирует всех пользователей", async () => {
+      await upsertRateLimits(new BN(0), new BN(0), vc(100));
+      warpToSlot(context, Number(await context.banksClient.getSlot()) + 1);
+
+      await burnVc(slotUserA, slotUserAVcAccount, 60);
+      await expectError(burnVc(slotUserB, slotUserBVcAccount, 50), "SlotVcLimitExceeded");
+    });
+
+    it("Лимит слота сбрасывается в следующем слоте", async () => {
+      warpToSlot(context, Number(await context.banksClient.getSlot()) + 1);
+      await burnVc(slotUserB, slotUserBVcAccount, 40);
+
+      const record = await program.account.userLpRecord.fetch(findUserLpRecord(slotUserB.publicKey));
+      assert.equal(record.vcBurned.toString(), vc(40).toString());
+    });
+  });
+
+  describe("Комиссии LP", () => {
+    const ACC_FEE_PRECISION = BigInt(1_000_000_000_000);
+    const vc = (tokens: number) => new BN((BigInt(tokens) * ONE_TOKEN).toString());
+    const keyHolderA = Keypair.generate();
+    const keyHolderB = Keypair.generate();
+    let ammProgram: Program<MockAmm>;
+    let feeMint: PublicKey;
+    let pool: PublicKey;
+    let position: PublicKey;
+    let ammFeeVault: PublicKey;
+    let distributorVault: PublicKey;
+    let funderFeeAccount: PublicKey;
+
+    const findPda = (seeds: Buffer[], programId: PublicKey) => PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+    const accrueFees = (amount: bigint) =>
+      ammProgram.methods
+        .accrueFees(new BN(amount.toString()))
+        .accounts({
+          funder: provider.wallet.publicKey,
+          pool,
+          position,
+          feeMint,
+          funderTokenAccount: funderFeeAccount,
+          feeVault: ammFeeVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    const harvest = () =>
+      program.methods
+        .harvestLpFees()
+        .accounts({
+          harvester: provider.wallet.publicKey,
+          ammProgram: ammProgram.programId,
+          ammPool: pool,
+          ammPosition: position,
+          ammFeeVault,
+          feeMint,
+          distributorVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    const claim = (owner: Keypair) =>
+      program.methods
+        .claimLpFees()
+        .accounts({ user: owner.publicKey, feeMint, distributorVault, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([owner])
+        .rpc();
+
+    const expectError = async (promise: Promise<unknown>, errorName: string) => {
+      try {
+        await promise;
+        assert.fail(`Ожидалась ошибка ${errorName}`);
+      } catch (error) {
+        assert.include(error.toString(), errorName);
+      }
+    };
+
+    before(async () => {
+      ammProgram = new Program<MockAmm>(mockAmmIdl as MockAmm, provider);
+      // Лимиты прошлых блоков не должны мешать блокировкам под уровень Fee Key
+      await program.methods
+        .upsertRateLimitConfig({ maxVcPerTx: new BN(0), maxVcPerUserPerEpoch: new BN(0), maxVcPerSlot: new BN(0) })
+        .accounts({ payer: provider.wallet.publicKey, daoAuthority: provider.wallet.publicKey })
+        .rpc();
+      await updateEmissionConfig(new BN(0), 10_000, true);
+
+      // 2,000 VC -> 1,000 LP (Bronze), 6,000 VC -> 3,000 LP
+      for (const [holder, tokens] of [
+        [keyHolderA, 2_000],
+        [keyHolderB, 6_000],
+      ] as [Keypair, number][]) {
+        const vcAccount = await fundUser(holder);
+        await mintToOwner(provider, vcMint, holder.publicKey, BigInt(tokens) * ONE_TOKEN);
+        await burnAndLockAs(holder, vcAccount, vc(tokens), new BN(0), new BN(0), await deadlineIn(60));
+      }
+
+      feeMint = await createMint(provider, TOKEN_DECIMALS);
+      const quoteMint = await createMint(provider, TOKEN_DECIMALS);
+      pool = findPda([Buffer.from("pool"), feeMint.toBuffer(), quoteMint.toBuffer()], ammProgram.programId);
+      ammFeeVault = findPda([Buffer.from("fee_vault"), pool.toBuffer()], ammProgram.programId);
+      const lockVault = findPda([Buffer.from("permanent_lock_vault")], program.programId);
+      position = findPda([Buffer.from("position"), pool.toBuffer(), lockVault.toBuffer()], ammProgram.programId);
+
+      await ammProgram.methods
+        .initializePool()
+        .accounts({ payer: provider.wallet.publicKey, mintA: feeMint, mintB: quoteMint, tokenProgram: TOKEN_PROGRAM_ID })
+        .rpc();
+      await ammProgram.methods.openPosition(lockVault).accounts({ payer: provider.wallet.publicKey, pool }).rpc();
+
+      await program.methods
+        .initializeFeeDistributor(ammProgram.programId, pool, position)
+        .accounts({ authority: provider.wallet.publicKey, feeMint, tokenProgram: TOKEN_PROGRAM_ID })
+        .rpc();
+      const distributor = findPda([Buffer.from("fee_distributor")], program.programId);
+      distributorVault = getAssociatedTokenAddressSync(feeMint, distributor, true, TOKEN_PROGRAM_ID);
+
+      funderFeeAccount = await mintToOwner(provider, feeMint, provider.wallet.publicKey, BigInt(1_000_000) * ONE_TOKEN);
+    });
+
+    it("Отклоняет настройку распределителя не администратором", async () => {
+      const stranger = Keypair.generate();
+      await expectError(
+        program.methods
+          .initializeFeeDistributor(ammProgram.programId, pool, position)
+          .accounts({ authority: stranger.publicKey, feeMint, tokenProgram: TOKEN_PROGRAM_ID })
+          .signers([stranger])
+          .rpc(),
+        "Unauthorized"
+      );
+    });
+
+    it("Собирает комиссии позиции PermanentLockVault в хранилище распределителя", async () => {
+      const harvested = BigInt(10_000) * ONE_TOKEN;
+      await accrueFees(harvested);
+      await harvest();
+
+      assert.equal(await getTokenBalance(context, distributorVault), harvested);
+      assert.equal(await getTokenBalance(context, ammFeeVault), BigInt(0));
+      const state = await program.account.burnAndEarnState.fetch(findState());
+      assert.equal(state.totalLpFeesHarvested.toString(), harvested.toString());
+      const expectedAcc = (harvested * ACC_FEE_PRECISION) / BigInt(state.totalLockedLp.toString());
+      assert.equal(state.accFeePerLp.toString(), expectedAcc.toString());
+    });
+
+    it("Повторный сбор без новых комиссий отклоняется", async () => {
+      await expectError(harvest(), "NothingToHarvest");
+    });
+
+    it("Держатели Fee Key получают комиссии пропорционально locked_lp", async () => {
+      const state = await program.account.burnAndEarnState.fetch(findState());
+      const acc = BigInt(state.accFeePerLp.toString());
+      const claimed: bigint[] = [];
+      for (const holder of [keyHolderA, keyHolderB]) {
+        const record = await program.account.userLpRecord.fetch(findUserLpRecord(holder.publicKey));
+        assert.notDeepEqual(record.nftLevel, { none: {} });
+        await claim(holder);
+
+        const balance = await getTokenBalance(
+          context,
+          getAssociatedTokenAddressSync(feeMint, holder.publicKey, false, TOKEN_PROGRAM_ID)
+        );
+        assert.equal(balance, (BigInt(record.lockedLp.toString()) * acc) / ACC_FEE_PRECISION);
+        claimed.push(balance);
+      }
+      // 1,000 LP против 3,000 LP
+      assert.equal(claimed[1], claimed[0] * BigInt(3));
+    });
+
+    it("Повторное получение без новых сборов отклоняется", async () => {
+      await expectError(claim(keyHolderA), "NothingToClaim");
+    });
+
+    it("На аварийной паузе сбор и получение комиссий отклоняются", async () => {
+      await accrueFees(BigInt(1_000) * ONE_TOKEN);
+      await setRegistryPaused(context, registryProgram, true);
+      try {
+        await expectError(harvest(), "ProgramPaused");
+        await expectError(claim(keyHolderB), "ProgramPaused");
+      } finally {
+        await setRegistryPaused(context, registryProgram, false);
+      }
+      assert.equal(await getTokenBalance(context, ammFeeVault), BigInt(1_000) * ONE_TOKEN);
+    });
+
+    it("Новая блокировка не получает комиссии, собранные до нее", async () => {
+      const before = await program.account.userLpRecord.fetch(findUserLpRecord(keyHolderA.publicKey));
+      const vcAccount = getAssociatedTokenAddressSync(vcMint, keyHolderA.publicKey, false, TOKEN_PROGRAM_ID);
+      await burnAndLockAs(keyHolderA, vcAccount, vc(1_000), new BN(0), new BN(0), await deadlineIn(60));
+
+      const after = await program.account.userLpRecord.fetch(findUserLpRecord(keyHolderA.publicKey));
+      assert.isTrue(after.lockedLp.gt(before.lockedLp));
+      assert.equal(after.feesOwed.toString(), "0");
+      await expectError(claim(keyHolderA), "NothingToClaim");
+    });
+
+    it("Запись ниже порога Bronze тоже получает свою долю комиссий", async () => {
+      // У user заблокировано меньше порога Bronze, но его LP входят в total_locked_lp
+      const state = await program.account.burnAndEarnState.fetch(findState());
+      const record = await program.account.userLpRecord.fetch(findUserLpRecord(user.publicKey));
+      assert.deepEqual(record.nftLevel, { none: {} });
+      const expected =
+        (BigInt(record.lockedLp.toString()) * BigInt(state.accFeePerLp.toString())) / ACC_FEE_PRECISION -
+        BigInt(record.feeDebt.toString()) +
+        BigInt(record.feesOwed.toString());
+      assert.isTrue(expected > BigInt(0));
+
+      await claim(user);
+
+      const balance = await getTokenBalance(
+        context,
+        getAssociatedTokenAddressSync(feeMint, user.publicKey, false, TOKEN_PROGRAM_ID)
+      );
+      assert.equal(balance, expected);
+    });
+  });
+
+  describe("Реферальная программа", () => {
+    const REFERRAL_BPS = 500;
+    const referrer = Keypair.generate();
+    const invitee = Keypair.generate();
+    let referrerVcAccount: PublicKey;
+    let inviteeVcAccount: PublicKey;
+
+    const findPda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    const findReferralLink = (owner: PublicKey) => findPda([Buffer.from("referral_link"), owner.toBuffer()]);
+    const findReferrerStats = (owner: PublicKey) => findPda([Buffer.from("referrer_stats"), owner.toBuffer()]);
+    const vgAta = (owner: PublicKey) => getAssociatedTokenAddressSync(vgMint, owner, true, TOKEN_PROGRAM_ID);
+
+    const upsertReferralConfig = (daoAuthority: Keypair | null, referralBps: number) => {
+      const builder = program.methods.upsertReferralConfig(referralBps).accounts({
+        payer: provider.wallet.publicKey,
+        daoAuthority: daoAuthority ? daoAuthority.publicKey : provider.wallet.publicKey,
+      });
+      return daoAuthority ? builder.signers([daoAuthority]).rpc() : builder.rpc();
+    };
+
+    const burnWithReferrer = async (
+      signer: Keypair,
+      vcAccount: PublicKey,
+      vcTokens: number,
+      referrerKey: PublicKey,
+      { withReferrerRecord = true, withConfig = true, referrerRecordOwner = referrerKey } = {}
+    ) =>
+      program.methods
+        .burnAndLock(
+          new BN((BigInt(vcTokens) * ONE_TOKEN).toString()),
+          new BN(0),
+          new BN(0),
+          await deadlineIn(60),
+          referrerKey
+        )
+        .accounts({
+          user: signer.publicKey,
+          vcMint,
+          userVcTokenAccount: vcAccount,
+          vgMint,
+          userVgTokenAccount: getAssociatedTokenAddressSync(vgMint, signer.publicKey, false, TOKEN_PROGRAM_ID),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          referralConfig: withConfig ? findPda([Buffer.from("referral_config")]) : null,
+          referrerLpRecord: withReferrerRecord ? findUserLpRecord(referrerRecordOwner) : null,
+          referrerStats: findReferrerStats(referrerKey),
+        })
+        .signers([signer])
+        .rpc();
+
+    const referralStats = (referrerKey: PublicKey, withStats: boolean) =>
+      program.methods
+        .getReferralStats(referrerKey)
+        .accounts({
+          referrerStats: withStats ? findReferrerStats(referrerKey) : null,
+          referralConfig: findPda([Buffer.from("referral_config")]),
+        })
+        .view();
+
+    const expectError = async (promise: Promise<unknown>, errorName: string) => {
+      try {
+        await promise;
+        assert.fail(`Ожидалась ошибка ${errorName}`);
+      } catch (error) {
+        assert.include(error.toString(), errorName);
+      }
+    };
+
+    before(async () => {
+      referrerVcAccount = await fundUser(referrer);
+      inviteeVcAccount = await fundUser(invitee);
+      await burnAndLockAs(referrer, referrerVcAccount, VC_AMOUNT, new BN(0), new BN(0), await deadlineIn(60));
+    });
+
+    it("Без настройки DAO реферальная связь отклоняется", async () => {
+      await expectError(
+        burnWithReferrer(invitee, inviteeVcAccount, 100, referrer.publicKey, { withConfig: false }),
+        "ReferralNotConfigured"
+      );
+    });
+
+    it("Отклоняет изменение доли реферера не-DAO и сверх максимума", async () => {
+      await expectError(upsertReferralConfig(Keypair.generate(), REFERRAL_BPS), "Unauthorized");
+      await expectError(upsertReferralConfig(null, 2_001), "InvalidReferralConfig");
+    });
+
+    it("Отклоняет самореферал", async () => {
+      await upsertReferralConfig(null, REFERRAL_BPS);
+      await expectError(
+        burnWithReferrer(invitee, inviteeVcAccount, 100, invitee.publicKey, { withReferrerRecord: false }),
+        "SelfReferral"
+      );
+    });
+
+    it("Отклоняет реферера без заблокированных LP", async () => {
+      await expectError(
+        burnWithReferrer(invitee, inviteeVcAccount, 100, Keypair.generate().publicKey, {
+          withReferrerRecord: false,
+        }),
+        "InvalidReferrer"
+      );
+    });
+
+    it("Отклоняет чужую запись LP вместо записи реферера", async () => {
+      // Реферер без LP подставляет запись участника с заблокированными LP
+      await expectError(
+        burnWithReferrer(invitee, inviteeVcAccount, 100, Keypair.generate().publicKey, {
+          referrerRecordOwner: referrer.publicKey,
+        }),
+        "ConstraintSeeds"
+      );
+    });
+
+    it("Блокировка без реферера не создает реферальную связь", async () => {
+      const link = await context.banksClient.getAccount(findReferralLink(referrer.publicKey));
+      assert.isNull(link, "Пользователь без реферера не должен платить за связь");
+    });
+
+    it("Записывает реферера при первой блокировке и начисляет ему долю эмиссии", async () => {
+      const stateBefore = await program.account.burnAndEarnState.fetch(findState());
+      const referrerBalanceBefore = await getTokenBalance(context, vgAta(referrer.publicKey));
+      await burnWithReferrer(invitee, inviteeVcAccount, 100, referrer.publicKey);
+
+      const link = await program.account.referralLink.fetch(findReferralLink(invitee.publicKey));
+      assert.equal(link.user.toBase58(), invitee.publicKey.toBase58());
+      assert.equal(link.referrer.toBase58(), referrer.publicKey.toBase58());
+
+      // Доля реферера - referral_bps от эмиссии пользователя (с точностью до округления)
+      const record = await program.account.userLpRecord.fetch(findUserLpRecord(invitee.publicKey));
+      const reward = BigInt(link.vgEarned.toString());
+      const expectedReward = (BigInt(record.vgMinted.toString()) * BigInt(REFERRAL_BPS)) / BigInt(10_000);
+      assert.isTrue(reward > BigInt(0));
+      assert.isTrue(reward - expectedReward <= BigInt(1) && expectedReward - reward <= BigInt(1));
+
+      // Как и эмиссия пользователя, доля реферера пока только учитывается, без перевода
+      assert.equal(await getTokenBalance(context, vgAta(referrer.publicKey)), referrerBalanceBefore);
+
+      const stats = await program.account.referrerStats.fetch(findReferrerStats(referrer.publicKey));
+      assert.equal(stats.referredUsers, 1);
+      assert.equal(stats.totalVcReferred.toString(), VC_AMOUNT.toString());
+      assert.equal(stats.totalVgEarned.toString(), reward.toString());
+
+      // Эмиссия пользователя и доля реферера вместе проходят через ограничения эмиссии
+      const stateAfter = await program.account.burnAndEarnState.fetch(findState());
+      const escrowSpent = BigInt(stateBefore.escrowRemaining.sub(stateAfter.escrowRemaining).toString());
+      assert.equal(escrowSpent, BigInt(record.vgMinted.toString()) + reward);
+      const epochEmitted = BigInt(stateAfter.epochVgEmitted.sub(stateBefore.epochVgEmitted).toString());
+      assert.equal(epochEmitted, escrowSpent);
+    });
+
+    it("Записанного реферера нельзя пропустить на следующей блокировке", async () => {
+      const inviteeVgBefore = await getTokenBalance(context, vgAta(invitee.publicKey));
+      await expectError(
+        burnAndLockAs(invitee, inviteeVcAccount, VC_AMOUNT, new BN(0), new BN(0), await deadlineIn(60)),
+        "InvalidReferrer"
+      );
+      assert.equal(await getTokenBalance(context, vgAta(invitee.publicKey)), inviteeVgBefore);
+    });
+
+    it("Связь не меняется: другой реферер отклоняется", async () => {
+      const otherReferrer = Keypair.generate();
+      await expectError(
+        burnWithReferrer(invitee, inviteeVcAccount, 100, otherReferrer.publicKey, {
+          withReferrerRecord: false,
+        }),
+        "InvalidReferrer"
+      );
+    });
+
+    it("Повторная блокировка снова вознаграждает того же реферера", async () => {
+      const before = await program.account.referrerStats.fetch(findReferrerStats(referrer.publicKey));
+      await burnWithReferrer(invitee, inviteeVcAccount, 100, referrer.publicKey, { withReferrerRecord: false });
+
+      const after = await program.account.referrerStats.fetch(findReferrerStats(referrer.publicKey));
+      assert.equal(after.referredUsers, 1);
+      assert.isTrue(after.totalVgEarned.gt(before.totalVgEarned));
+      assert.equal(after.totalVcReferred.toString(), VC_AMOUNT.muln(2).toString());
+    });
+
+    it("Отклоняет цикл: существующий участник не может записать приглашенного реферером", async () => {
+      await expectError(
+        burnWithReferrer(referrer, referrerVcAccount, 100, invitee.publicKey),
+        "InvalidReferrer"
+      );
+    });
+
+    it("Возвращает статистику реферера", async () => {
+      const stats = await program.account.referrerStats.fetch(findReferrerStats(referrer.publicKey));
+      const view = await referralStats(referrer.publicKey, true);
+      assert.equal(view.referredUsers, 1);
+      assert.equal(view.totalVgEarned.toString(), stats.totalVgEarned.toString());
+      assert.equal(view.referralBps, REFERRAL_BPS);
+
+      const empty = await referralStats(Keypair.generate().publicKey, false);
+      assert.equal(empty.referredUsers, 0);
+      assert.equal(empty.totalVgEarned.toString(), "0");
+    });
+  });
+});
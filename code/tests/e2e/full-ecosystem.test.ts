@@ -310,8 +310,9 @@ describe('TECH-HY Ecosystem E2E Tests', () => {
         console.log(`\n${user.name} is converting ${amountToBurn.toString()} VC tokens (${ratio * 100}% of balance)...`);
         
         // Используем Burn and Earn для конвертации VC в LP и эмиссии VG
+        const deadline = new BN(Math.floor(Date.now() / 1000) + 60);
         await burnAndEarnProgram.methods
-          .burnAndLock(amountToBurn)
+          .burnAndLock(amountToBurn, new BN(0), new BN(0), deadline, null)
           .accounts({
             user: user.wallet.publicKey,
             burnAndEarnState: burnAndEarnState,
@@ -326,6 +327,9 @@ describe('TECH-HY Ecosystem E2E Tests', () => {
             associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            referralConfig: null,
+            referrerLpRecord: null,
+            referrerStats: null,
           })
           .signers([user.wallet])
           .rpc();
@@ -0,0 +1,231 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { startAnchor, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { assert } from "chai";
+import { TechHyContracts } from "../target/types/tech_hy_contracts";
+import { VgToken } from "../target/types/vg_token";
+import { BurnAndEarn } from "../target/types/burn_and_earn";
+import registryIdl from "../target/idl/tech_hy_contracts.json";
+import vgTokenIdl from "../target/idl/vg_token.json";
+import burnAndEarnIdl from "../target/idl/burn_and_earn.json";
+import {
+  createMint,
+  mintToOwner,
+  getTokenBalance,
+  getUnixTimestamp,
+  initializeRegistry,
+  initializeVgToken,
+  vgHookAccounts,
+} from "./utils/bankrun-utils";
+
+// Аварийный совет и пауза на собственном реестре: пока экосистема на паузе,
+// переводы VG, burn_and_lock и клейм наград отклоняются с ProgramPaused.
+describe("Emergency Council Tests", () => {
+  const TOKEN_DECIMALS = 9;
+  const ONE_TOKEN = BigInt(10 ** TOKEN_DECIMALS);
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<TechHyContracts>;
+  let vgTokenProgram: Program<VgToken>;
+  let burnAndEarnProgram: Program<BurnAndEarn>;
+
+  // Совет 2-из-4 для паузы и 3-из-4 для снятия паузы
+  const members = [Keypair.generate(), Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const PAUSE_THRESHOLD = 2;
+  const UNPAUSE_THRESHOLD = 3;
+
+  const holder = Keypair.generate();
+  const user = Keypair.generate();
+  const daoTreasury = Keypair.generate();
+  const feeCollector = Keypair.generate();
+  let vcMint: PublicKey;
+  let vgMint: PublicKey;
+  let userVcTokenAccount: PublicKey;
+
+  let councilPda: PublicKey;
+  let registryPda: PublicKey;
+
+  // Подписи участников передаются через remaining_accounts
+  const asSigners = (signers: Keypair[]) =>
+    signers.map((kp) => ({ pubkey: kp.publicKey, isSigner: true, isWritable: false }));
+
+  const expectError = async (promise: Promise<unknown>, errorName: string) => {
+    try {
+      await promise;
+      assert.fail(`Ожидалась ошибка ${errorName}`);
+    } catch (error) {
+      assert.include(error.toString(), errorName);
+    }
+  };
+
+  const vgAta = (owner: PublicKey) => getAssociatedTokenAddressSync(vgMint, owner, true, TOKEN_2022_PROGRAM_ID);
+
+  const transferVg = (recipient: PublicKey, amount: bigint) =>
+    vgTokenProgram.methods
+      .transferWithTax(new BN(amount.toString()))
+      .accountsPartial({
+        senderAuthority: holder.publicKey,
+        mintAccount: vgMint,
+        senderTokenAccount: vgAta(holder.publicKey),
+        recipientAuthority: recipient,
+        recipientTokenAccount: vgAta(recipient),
+        daoTreasuryAuthority: daoTreasury.publicKey,
+        daoTreasuryTokenAccount: vgAta(daoTreasury.publicKey),
+        feeCollectorAuthority: feeCollector.publicKey,
+        feeCollectorTokenAccount: vgAta(feeCollector.publicKey),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(vgHookAccounts())
+      .signers([holder])
+      .rpc();
+
+  const burnAndLock = async (vcAmount: bigint) =>
+    burnAndEarnProgram.methods
+      .burnAndLock(
+        new BN(vcAmount.toString()),
+        new BN(0),
+        new BN(0),
+        new BN((await getUnixTimestamp(context)) + 60),
+        null
+      )
+      .accounts({
+        user: user.publicKey,
+        vcMint,
+        userVcTokenAccount,
+        vgMint,
+        userVgTokenAccount: vgAta(user.publicKey),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        referralConfig: null,
+        referrerLpRecord: null,
+        referrerStats: null,
+      })
+      .signers([user])
+      .rpc();
+
+  const fundLamports = (owner: PublicKey) =>
+    context.setAccount(owner, {
+      lamports: 1_000_000_000,
+      data: Buffer.alloc(0),
+      owner: anchor.web3.SystemProgram.programId,
+      executable: false,
+    });
+
+  before("Подготовка bankrun, реестра, VG и Burn and Earn", async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<TechHyContracts>(registryIdl as TechHyContracts, provider);
+    vgTokenProgram = new Program<VgToken>(vgTokenIdl as VgToken, provider);
+    burnAndEarnProgram = new Program<BurnAndEarn>(burnAndEarnIdl as BurnAndEarn, provider);
+
+    [councilPda] = PublicKey.findProgramAddressSync([Buffer.from("emergency_council")], program.programId);
+    [registryPda] = PublicKey.findProgramAddressSync([Buffer.from("ecosystem_registry")], program.programId);
+    const [vgMintPda] = PublicKey.findProgramAddressSync([Buffer.from("vg_token_mint")], vgTokenProgram.programId);
+
+    vcMint = await createMint(provider, TOKEN_DECIMALS);
+    await initializeRegistry(context, program, {
+      vcMint,
+      vgMint: vgMintPda,
+      vcTokenProgram: TOKEN_PROGRAM_ID,
+      vgTokenProgram: TOKEN_2022_PROGRAM_ID,
+      burnAndEarnProgram: burnAndEarnProgram.programId,
+      daoTreasury: daoTreasury.publicKey,
+      feeCollector: feeCollector.publicKey,
+    });
+
+    vgMint = await initializeVgToken(provider, vgTokenProgram, holder.publicKey);
+    await burnAndEarnProgram.methods
+      .initialize()
+      .accounts({ authority: provider.wallet.publicKey, vcMint, vgMint })
+      .rpc();
+
+    fundLamports(holder.publicKey);
+    fundLamports(user.publicKey);
+    userVcTokenAccount = await mintToOwner(provider, vcMint, user.publicKey, BigInt(1_000) * ONE_TOKEN);
+  });
+
+  it("Создает аварийный совет", async () => {
+    await program.methods
+      .initializeEmergencyCouncil(
+        members.map((kp) => kp.publicKey),
+        PAUSE_THRESHOLD,
+        UNPAUSE_THRESHOLD
+      )
+      .accounts({ payer: provider.wallet.publicKey, authority: provider.wallet.publicKey })
+      .rpc();
+
+    const council = await program.account.emergencyCouncil.fetch(councilPda);
+    assert.equal(council.members.length, members.length);
+    assert.equal(council.pauseThreshold, PAUSE_THRESHOLD);
+    assert.equal(council.unpauseThreshold, UNPAUSE_THRESHOLD);
+  });
+
+  it("Отклоняет паузу с недостаточным количеством подписей", async () => {
+    await expectError(
+      program.methods
+        .pause("test")
+        .remainingAccounts(asSigners(members.slice(0, 1)))
+        .signers(members.slice(0, 1))
+        .rpc(),
+      "InsufficientCouncilSignatures"
+    );
+  });
+
+  it("Ставит экосистему на паузу подписями 2-из-4", async () => {
+    await program.methods
+      .pause("critical vulnerability")
+      .remainingAccounts(asSigners(members.slice(0, 2)))
+      .signers(members.slice(0, 2))
+      .rpc();
+
+    const registry = await program.account.ecosystemRegistry.fetch(registryPda);
+    assert.isTrue(registry.paused);
+    assert.equal(registry.pauseReason, "critical vulnerability");
+  });
+
+  it("На паузе отклоняет transfer_with_tax", async () => {
+    await expectError(transferVg(Keypair.generate().publicKey, BigInt(10) * ONE_TOKEN), "ProgramPaused");
+  });
+
+  it("На паузе отклоняет burn_and_lock", async () => {
+    await expectError(burnAndLock(BigInt(100) * ONE_TOKEN), "ProgramPaused");
+    assert.equal(await getTokenBalance(context, userVcTokenAccount), BigInt(1_000) * ONE_TOKEN);
+  });
+
+  it("На паузе отклоняет клейм награды держателя NFT Fee Key", async () => {
+    await expectError(vgTokenProgram.methods.claimNftHolderReward().rpc(), "ProgramPaused");
+  });
+
+  it("Не снимает паузу теми же 2 подписями", async () => {
+    await expectError(
+      program.methods
+        .unpause()
+        .remainingAccounts(asSigners(members.slice(0, 2)))
+        .signers(members.slice(0, 2))
+        .rpc(),
+      "InsufficientCouncilSignatures"
+    );
+  });
+
+  it("Снимает паузу подписями 3-из-4", async () => {
+    await program.methods
+      .unpause()
+      .remainingAccounts(asSigners(members.slice(1, 4)))
+      .signers(members.slice(1, 4))
+      .rpc();
+
+    const registry = await program.account.ecosystemRegistry.fetch(registryPda);
+    assert.isFalse(registry.paused);
+  });
+
+  it("После снятия паузы переводы VG снова проходят", async () => {
+    const recipient = Keypair.generate().publicKey;
+    await transferVg(recipient, BigInt(10) * ONE_TOKEN);
+    // Налог 10% (см. initializeVgToken)
+    assert.equal(await getTokenBalance(context, vgAta(recipient)), BigInt(9) * ONE_TOKEN);
+  });
+});
@@ -50,6 +50,7 @@ describe('TECH-HY Ecosystem Integration Tests', () => {
   let userVgTokenAccount: PublicKey;
   let userLpRecord: PublicKey;
   
+  const VC_TOTAL_SUPPLY = new BN(5_000_000_000).mul(new BN(10).pow(new BN(9))); // Полная эмиссия VC
   const VC_AMOUNT = new BN(10000).mul(new BN(10).pow(new BN(9))); // 10,000 VC
   const VC_AMOUNT_TO_BURN = new BN(5000).mul(new BN(10).pow(new BN(9))); // 5,000 VC
   const VC_AMOUNT_TO_TRANSFER = new BN(1000).mul(new BN(10).pow(new BN(9))); // 1,000 VC
@@ -91,28 +92,12 @@ describe('TECH-HY Ecosystem Integration Tests', () => {
     );
     
     try {
-      // Инициализация VC токена
-      await vcTokenProgram.methods
-        .initialize()
-        .accounts({
-          payer: provider.wallet.publicKey,
-          treasury: daoWallet.publicKey,
-          mint: vcMintPubkey,
-          treasuryTokenAccount: daoVcTokenAccount,
-          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        })
-        .rpc();
-      
-      console.log('VC Token initialized successfully');
-      
-      // Установка метаданных
+      // Метаданные VC устанавливаются в той же транзакции, что и инициализация
       const metadataUri = 'https://tech-hy.io/token-metadata/vc.json';
       const name = 'TECH-HY VC';
       const symbol = 'VC';
       
-      await vcTokenProgram.methods
+      const setMetadataIx = await vcTokenProgram.methods
         .setMetadata(name, symbol, metadataUri)
         .accounts({
           authority: provider.wallet.publicKey,
@@ -127,11 +112,32 @@ describe('TECH-HY Ecosystem Integration Tests', () => {
             new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s')
           )[0],
           metadataProgram: new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s'),
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         })
+        .instruction();
+
+      // Инициализация VC токена
+      // Вся эмиссия VC распределяется в казну DAO одной записью
+      await vcTokenProgram.methods
+        .initialize([{ recipient: daoWallet.publicKey, amount: VC_TOTAL_SUPPLY }])
+        .accounts({
+          payer: provider.wallet.publicKey,
+          authority: provider.wallet.publicKey,
+          mint: vcMintPubkey,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: daoWallet.publicKey, isSigner: false, isWritable: false },
+          { pubkey: daoVcTokenAccount, isSigner: false, isWritable: true },
+        ])
+        .postInstructions([setMetadataIx])
         .rpc();
       
+      console.log('VC Token initialized successfully');
       console.log('VC Token metadata set successfully');
       
       // Проверяем, что токены были выпущены на кошелек казны DAO
@@ -313,8 +319,10 @@ describe('TECH-HY Ecosystem Integration Tests', () => {
       const initialVcBalance = await getTokenBalance(connection, userVcTokenAccount);
       
       // Используем Burn and Earn для конвертации VC в LP и эмиссии VG
+      // (без ограничений на выход, срок действия - минута)
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 60);
       await burnAndEarnProgram.methods
-        .burnAndLock(VC_AMOUNT_TO_BURN)
+        .burnAndLock(VC_AMOUNT_TO_BURN, new anchor.BN(0), new anchor.BN(0), deadline, null)
         .accounts({
           user: userWallet.publicKey,
           burnAndEarnState: burnAndEarnState,
@@ -329,6 +337,9 @@ describe('TECH-HY Ecosystem Integration Tests', () => {
           associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          referralConfig: null,
+          referrerLpRecord: null,
+          referrerStats: null,
         })
         .signers([userWallet])
         .rpc();
@@ -0,0 +1,228 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { startAnchor, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { assert } from "chai";
+import { createHash } from "crypto";
+import { TechHyDistributor } from "../target/types/tech_hy_distributor";
+import { TechHyContracts } from "../target/types/tech_hy_contracts";
+import distributorIdl from "../target/idl/tech_hy_distributor.json";
+import registryIdl from "../target/idl/tech_hy_contracts.json";
+import {
+  createMint,
+  mintToOwner,
+  getTokenBalance,
+  getUnixTimestamp,
+  warpToTimestamp,
+  warpToSlot,
+  initializeRegistry,
+} from "./utils/bankrun-utils";
+
+describe("Airdrop Distributor Tests", () => {
+  const TOKEN_DECIMALS = 9;
+  const ONE_TOKEN = BigInt(10 ** TOKEN_DECIMALS);
+  const DURATION_SECONDS = 1_000;
+  const DISTRIBUTION_ID = 1;
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<TechHyDistributor>;
+
+  const daoTreasury = Keypair.generate();
+  const claimants = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const amounts = [BigInt(100) * ONE_TOKEN, BigInt(250) * ONE_TOKEN, BigInt(650) * ONE_TOKEN];
+  const maxTotalClaim = amounts.reduce((total, amount) => total + amount, BigInt(0));
+
+  let vcMint: PublicKey;
+  let funderTokenAccount: PublicKey;
+  let distributor: PublicKey;
+  let expiresAt: number;
+
+  // Дерево как в tech_hy_common::merkle: лист sha256(0x00 || index || claimant || amount),
+  // узел sha256(0x01 || min || max), узел без пары переносится выше
+  const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+  const u64Le = (value: number | bigint) => new BN(value.toString()).toArrayLike(Buffer, "le", 8);
+  const leaf = (index: number, claimant: PublicKey, amount: bigint) =>
+    sha256(Buffer.from([0]), u64Le(index), claimant.toBuffer(), u64Le(amount));
+  const hashPair = (a: Buffer, b: Buffer) =>
+    Buffer.compare(a, b) <= 0 ? sha256(Buffer.from([1]), a, b) : sha256(Buffer.from([1]), b, a);
+
+  const buildLayers = (leaves: Buffer[]) => {
+    const layers = [leaves];
+    while (layers[layers.length - 1].length > 1) {
+      const layer = layers[layers.length - 1];
+      const next: Buffer[] = [];
+      for (let i = 0; i < layer.length; i += 2) {
+        next.push(i + 1 < layer.length ? hashPair(layer[i], layer[i + 1]) : layer[i]);
+      }
+      layers.push(next);
+    }
+    return layers;
+  };
+
+  const layers = buildLayers(claimants.map((claimant, index) => leaf(index, claimant.publicKey, amounts[index])));
+  const root = layers[layers.length - 1][0];
+  const proofFor = (index: number) => {
+    const proof: number[][] = [];
+    let position = index;
+    for (const layer of layers.slice(0, -1)) {
+      const sibling = layer[position ^ 1];
+      if (sibling) {
+        proof.push(Array.from(sibling));
+      }
+      position = Math.floor(position / 2);
+    }
+    return proof;
+  };
+
+  const findPda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const ata = (owner: PublicKey) => getAssociatedTokenAddressSync(vcMint, owner, true, TOKEN_PROGRAM_ID);
+
+  const claim = (claimant: Keypair, index: number, amount: bigint, proof: number[][]) =>
+    program.methods
+      .claim(new BN(index), new BN(amount.toString()), proof)
+      .accounts({
+        claimant: claimant.publicKey,
+        distributor,
+        mint: vcMint,
+        vault: ata(distributor),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([claimant])
+      .rpc();
+
+  const clawback = () =>
+    program.methods
+      .clawback()
+      .accounts({
+        payer: provider.wallet.publicKey,
+        distributor,
+        mint: vcMint,
+        vault: ata(distributor),
+        daoTreasury: daoTreasury.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  // Повтор той же транзакции требует нового blockhash, иначе bankrun отклонит ее как дубликат
+  const nextSlot = async () => warpToSlot(context, Number(await context.banksClient.getSlot()) + 1);
+
+  const expectError = async (promise: Promise<unknown>, errorName: string) => {
+    try {
+      await promise;
+      assert.fail(`Ожидалась ошибка ${errorName}`);
+    } catch (error) {
+      assert.include(error.toString(), errorName);
+    }
+  };
+
+  before("Подготовка bankrun, реестра и минта VC", async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<TechHyDistributor>(distributorIdl as TechHyDistributor, provider);
+    const registryProgram = new Program<TechHyContracts>(registryIdl as TechHyContracts, provider);
+
+    vcMint = await createMint(provider, TOKEN_DECIMALS);
+    funderTokenAccount = await mintToOwner(provider, vcMint, provider.wallet.publicKey, maxTotalClaim);
+
+    await initializeRegistry(context, registryProgram, {
+      vcMint,
+      vgMint: Keypair.generate().publicKey,
+      vcTokenProgram: Keypair.generate().publicKey,
+      vgTokenProgram: Keypair.generate().publicKey,
+      burnAndEarnProgram: Keypair.generate().publicKey,
+      daoTreasury: daoTreasury.publicKey,
+      feeCollector: Keypair.generate().publicKey,
+    });
+
+    // Получателям нужны лампорты для создания своего ATA
+    for (const claimant of claimants) {
+      context.setAccount(claimant.publicKey, {
+        lamports: 1_000_000_000,
+        data: Buffer.alloc(0),
+        owner: anchor.web3.SystemProgram.programId,
+        executable: false,
+      });
+    }
+
+    distributor = findPda([Buffer.from("merkle_distributor"), vcMint.toBuffer(), u64Le(DISTRIBUTION_ID)]);
+    expiresAt = (await getUnixTimestamp(context)) + DURATION_SECONDS;
+  });
+
+  it("Создает раздачу и пополняет хранилище", async () => {
+    await program.methods
+      .createDistributor(
+        new BN(DISTRIBUTION_ID),
+        Array.from(root),
+        new BN(maxTotalClaim.toString()),
+        new BN(claimants.length),
+        new BN(expiresAt)
+      )
+      .accounts({
+        daoAuthority: provider.wallet.publicKey,
+        mint: vcMint,
+        funderTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const state = await program.account.merkleDistributor.fetch(distributor);
+    assert.deepEqual(state.merkleRoot, Array.from(root));
+    assert.equal(state.numNodes.toNumber(), claimants.length);
+    assert.equal(await getTokenBalance(context, ata(distributor)), maxTotalClaim);
+  });
+
+  it("Выдает airdrop по верному доказательству", async () => {
+    await claim(claimants[0], 0, amounts[0], proofFor(0));
+    // Лист без пары: доказательство короче
+    await claim(claimants[2], 2, amounts[2], proofFor(2));
+
+    assert.equal(await getTokenBalance(context, ata(claimants[0].publicKey)), amounts[0]);
+    assert.equal(await getTokenBalance(context, ata(claimants[2].publicKey)), amounts[2]);
+
+    const state = await program.account.merkleDistributor.fetch(distributor);
+    assert.equal(state.numClaimed.toNumber(), 2);
+    assert.equal(state.totalClaimed.toString(), (amounts[0] + amounts[2]).toString());
+
+    const bitmap = await program.account.claimBitmap.fetch(findPda([Buffer.from("claim_bitmap"), distributor.toBuffer()]));
+    assert.equal(bitmap.version, 1);
+    assert.equal(bitmap.bits.length, 1);
+    assert.equal(bitmap.bits[0], 0b101);
+  });
+
+  it("Отклоняет повторное получение", async () => {
+    await nextSlot();
+    await expectError(claim(claimants[0], 0, amounts[0], proofFor(0)), "AlreadyClaimed");
+  });
+
+  it("Отклоняет неверное доказательство, чужой лист и завышенную сумму", async () => {
+    await expectError(claim(claimants[1], 1, amounts[1], proofFor(0)), "InvalidProof");
+    await expectError(claim(claimants[0], 1, amounts[1], proofFor(1)), "InvalidProof");
+    await expectError(claim(claimants[1], 1, amounts[1] * BigInt(2), proofFor(1)), "InvalidProof");
+    await expectError(claim(claimants[1], 3, amounts[1], proofFor(1)), "InvalidIndex");
+  });
+
+  it("Отклоняет возврат в казну до истечения срока", async () => {
+    await expectError(clawback(), "DistributionNotExpired");
+  });
+
+  it("Отклоняет получение после истечения срока", async () => {
+    await warpToTimestamp(context, expiresAt);
+    await expectError(claim(claimants[1], 1, amounts[1], proofFor(1)), "DistributionExpired");
+  });
+
+  it("Возвращает неполученный остаток в казну DAO после срока", async () => {
+    await clawback();
+
+    assert.equal(await getTokenBalance(context, ata(daoTreasury.publicKey)), amounts[1]);
+    assert.equal(await getTokenBalance(context, ata(distributor)), BigInt(0));
+    const state = await program.account.merkleDistributor.fetch(distributor);
+    assert.isTrue(state.clawedBack);
+
+    await nextSlot();
+    await expectError(clawback(), "AlreadyClawedBack");
+  });
+});
@@ -0,0 +1,224 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  ExtensionType,
+  getAssociatedTokenAddressSync,
+  getExtensionData,
+  unpackMint,
+} from "@solana/spl-token";
+import { unpack as unpackTokenMetadata } from "@solana/spl-token-metadata";
+import { startAnchor, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { assert } from "chai";
+import { VcToken } from "../target/types/vc_token";
+import { VgToken } from "../target/types/vg_token";
+import { TechHyContracts } from "../target/types/tech_hy_contracts";
+import vcIdl from "../target/idl/vc_token.json";
+import vgIdl from "../target/idl/vg_token.json";
+import registryIdl from "../target/idl/tech_hy_contracts.json";
+import { initializeRegistry } from "./utils/bankrun-utils";
+
+// Альтернативная инициализация VC и VG как минтов Token-2022 с TokenMetadata.
+// Оба пути используют те же PDA минтов, что и initialize, поэтому запускаются на bankrun.
+describe("Token-2022 TokenMetadata Init Tests", () => {
+  const TOKEN_DECIMALS = 9;
+  const VC_TOTAL_SUPPLY = BigInt(5_000_000_000) * BigInt(10 ** TOKEN_DECIMALS);
+  const VG_TOTAL_SUPPLY = BigInt(1_000_000_000) * BigInt(10 ** TOKEN_DECIMALS);
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let vcProgram: Program<VcToken>;
+  let vgProgram: Program<VgToken>;
+  let registryProgram: Program<TechHyContracts>;
+
+  const daoAuthority = Keypair.generate();
+  const treasury = Keypair.generate();
+  const escrowAuthority = Keypair.generate();
+
+  // Читает минт и TokenMetadata из TLV-данных самого минта
+  const fetchMintWithMetadata = async (mint: PublicKey) => {
+    const account = await context.banksClient.getAccount(mint);
+    assert.isNotNull(account, "Минт не создан");
+    const info = {
+      ...account!,
+      data: Buffer.from(account!.data),
+    };
+    const state = unpackMint(mint, info, TOKEN_2022_PROGRAM_ID);
+    const data = getExtensionData(ExtensionType.TokenMetadata, state.tlvData);
+    assert.isNotNull(data, "У минта нет расширения TokenMetadata");
+    return { owner: account!.owner, state, metadata: unpackTokenMetadata(data!) };
+  };
+
+  const additional = (metadata: { additionalMetadata: (readonly [string, string])[] }) =>
+    Object.fromEntries(metadata.additionalMetadata);
+
+  before("Подготовка bankrun и реестра", async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    vcProgram = new Program<VcToken>(vcIdl as VcToken, provider);
+    vgProgram = new Program<VgToken>(vgIdl as VgToken, provider);
+    registryProgram = new Program<TechHyContracts>(registryIdl as TechHyContracts, provider);
+
+    await initializeRegistry(context, registryProgram, {
+      vcMint: Keypair.generate().publicKey,
+      vgMint: Keypair.generate().publicKey,
+      vcTokenProgram: vcProgram.programId,
+      vgTokenProgram: vgProgram.programId,
+      burnAndEarnProgram: Keypair.generate().publicKey,
+      daoTreasury: treasury.publicKey,
+      feeCollector: Keypair.generate().publicKey,
+    });
+    // Авторитет DAO передаем отдельному ключу, чтобы отличать его от плательщика
+    await registryProgram.methods
+      .setRegistryAuthority(daoAuthority.publicKey)
+      .accounts({ authority: provider.wallet.publicKey })
+      .rpc();
+  });
+
+  it("VC: отклоняет инициализацию не авторитетом реестра", async () => {
+    const [mintPda] = PublicKey.findProgramAddressSync([Buffer.from("vc_token_mint")], vcProgram.programId);
+    const treasuryAta = getAssociatedTokenAddressSync(mintPda, treasury.publicKey, false, TOKEN_2022_PROGRAM_ID);
+    try {
+      await vcProgram.methods
+        .initializeWithTokenMetadata(
+          [{ recipient: treasury.publicKey, amount: new BN(VC_TOTAL_SUPPLY.toString()) }],
+          "Venture Club Token",
+          "VC",
+          "https://tech-hy.io/metadata/vc-token.json"
+        )
+        .accounts({ payer: provider.wallet.publicKey, authority: provider.wallet.publicKey })
+        .remainingAccounts([
+          { pubkey: treasury.publicKey, isSigner: false, isWritable: false },
+          { pubkey: treasuryAta, isSigner: false, isWritable: true },
+        ])
+        .rpc();
+      assert.fail("Инициализировать VC может только авторитет реестра");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized");
+    }
+  });
+
+  it("VC: отклоняет символ не из заглавных латинских букв и цифр", async () => {
+    const [mintPda] = PublicKey.findProgramAddressSync([Buffer.from("vc_token_mint")], vcProgram.programId);
+    const treasuryAta = getAssociatedTokenAddressSync(mintPda, treasury.publicKey, false, TOKEN_2022_PROGRAM_ID);
+    try {
+      await vcProgram.methods
+        .initializeWithTokenMetadata(
+          [{ recipient: treasury.publicKey, amount: new BN(VC_TOTAL_SUPPLY.toString()) }],
+          "Venture Club Token",
+          "vc",
+          "https://tech-hy.io/metadata/vc-token.json"
+        )
+        .accounts({ payer: provider.wallet.publicKey, authority: daoAuthority.publicKey })
+        .remainingAccounts([
+          { pubkey: treasury.publicKey, isSigner: false, isWritable: false },
+          { pubkey: treasuryAta, isSigner: false, isWritable: true },
+        ])
+        .signers([daoAuthority])
+        .rpc();
+      assert.fail("Метаданные VC проверяются так же, как у VG");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidMetadataSymbol");
+    }
+  });
+
+  it("VC: метаданные и поля экосистемы хранятся в минте Token-2022", async () => {
+    const [mintPda] = PublicKey.findProgramAddressSync([Buffer.from("vc_token_mint")], vcProgram.programId);
+    const treasuryAta = getAssociatedTokenAddressSync(mintPda, treasury.publicKey, false, TOKEN_2022_PROGRAM_ID);
+
+    await vcProgram.methods
+      .initializeWithTokenMetadata(
+        [{ recipient: treasury.publicKey, amount: new BN(VC_TOTAL_SUPPLY.toString()) }],
+        "Venture Club Token",
+        "VC",
+        "https://tech-hy.io/metadata/vc-token.json"
+      )
+      .accounts({ payer: provider.wallet.publicKey, authority: daoAuthority.publicKey })
+      .remainingAccounts([
+        { pubkey: treasury.publicKey, isSigner: false, isWritable: false },
+        { pubkey: treasuryAta, isSigner: false, isWritable: true },
+      ])
+      .signers([daoAuthority])
+      .rpc();
+
+    const { owner, state, metadata } = await fetchMintWithMetadata(mintPda);
+    assert.isTrue(owner.equals(TOKEN_2022_PROGRAM_ID));
+    assert.isNull(state.mintAuthority, "Mint authority должна быть отозвана");
+    assert.equal(state.supply, VC_TOTAL_SUPPLY);
+    assert.equal(metadata.symbol, "VC");
+    assert.isTrue(metadata.updateAuthority!.equals(daoAuthority.publicKey), "Update authority - авторитет реестра, а не плательщик");
+    assert.deepEqual(additional(metadata), {
+      ecosystem_role: "venture_club",
+      tax_rate_bps: "0",
+      total_supply: VC_TOTAL_SUPPLY.toString(),
+    });
+
+    const report = await vcProgram.methods.auditMint().accounts({ mint: mintPda }).view();
+    assert.isTrue(report.ownerProgramOk, "Аудит должен ожидать Token-2022 для этого пути");
+    assert.isTrue(report.passed);
+  });
+
+  it("VG: отклоняет update authority не из реестра", async () => {
+    try {
+      await vgProgram.methods
+        .initializeWithTokenMetadata("TECH-HY Governance", "VG", "https://tech-hy.io/metadata/vg-token.json")
+        .accounts({
+          payer: provider.wallet.publicKey,
+          daoAuthority: Keypair.generate().publicKey,
+          burnAndEarnEscrowPda: escrowAuthority.publicKey,
+        })
+        .rpc();
+      assert.fail("Update authority метаданных должен совпадать с авторитетом реестра");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized");
+    }
+  });
+
+  it("VG: метаданные в минте с TransferHook без параметров налога, authority - DAO", async () => {
+    const [mintPda] = PublicKey.findProgramAddressSync([Buffer.from("vg_token_mint")], vgProgram.programId);
+    const escrowAta = getAssociatedTokenAddressSync(mintPda, escrowAuthority.publicKey, false, TOKEN_2022_PROGRAM_ID);
+
+    await vgProgram.methods
+      .initializeWithTokenMetadata("TECH-HY Governance", "VG", "https://tech-hy.io/metadata/vg-token.json")
+      .accounts({
+        payer: provider.wallet.publicKey,
+        daoAuthority: daoAuthority.publicKey,
+        burnAndEarnEscrowPda: escrowAuthority.publicKey,
+      })
+      .rpc();
+
+    const { state, metadata } = await fetchMintWithMetadata(mintPda);
+    assert.isNull(state.mintAuthority);
+    assert.isNotNull(getExtensionData(ExtensionType.TransferHook, state.tlvData), "TransferHook должен сохраниться");
+    assert.isTrue(metadata.updateAuthority!.equals(daoAuthority.publicKey));
+
+    const fields = additional(metadata);
+    assert.equal(fields.ecosystem_role, "governance");
+    // Ставки налога живут только в TaxConfig, чтобы метаданные не расходились с ним после upsert_tax_config
+    assert.notProperty(fields, "tax_rate_bps");
+    assert.notProperty(fields, "dao_share_bps");
+    assert.notProperty(fields, "nft_holders_share_bps");
+    assert.equal(fields.total_supply, VG_TOTAL_SUPPLY.toString());
+
+    const escrow = await context.banksClient.getAccount(escrowAta);
+    assert.isNotNull(escrow, "Эскроу должен получить полную эмиссию");
+  });
+
+  it("VG: аудит минта подтверждает инварианты", async () => {
+    const [mintPda] = PublicKey.findProgramAddressSync([Buffer.from("vg_token_mint")], vgProgram.programId);
+
+    const report = await vgProgram.methods.auditMint().accounts({ mintAccount: mintPda }).view();
+
+    assert.equal(report.mint.toBase58(), mintPda.toBase58());
+    assert.equal(report.ownerProgram.toBase58(), TOKEN_2022_PROGRAM_ID.toBase58());
+    assert.isTrue(report.ownerProgramOk, "Минт VG должен принадлежать Token-2022");
+    assert.equal(report.supply.toString(), VG_TOTAL_SUPPLY.toString());
+    assert.equal(report.decimals, TOKEN_DECIMALS);
+    assert.isTrue(report.mintAuthorityRevoked, "Mint authority должна быть отозвана");
+    assert.isTrue(report.freezeAuthorityRevoked, "Freeze authority должна отсутствовать");
+    assert.isTrue(report.passed, "Аудит минта VG не пройден");
+  });
+});
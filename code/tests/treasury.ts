@@ -0,0 +1,394 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { startAnchor, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { assert } from "chai";
+import { Treasury } from "../target/types/treasury";
+import { TechHyContracts } from "../target/types/tech_hy_contracts";
+import { MockAmm } from "../target/types/mock_amm";
+import { BurnAndEarn } from "../target/types/burn_and_earn";
+import treasuryIdl from "../target/idl/treasury.json";
+import registryIdl from "../target/idl/tech_hy_contracts.json";
+import mockAmmIdl from "../target/idl/mock_amm.json";
+import burnAndEarnIdl from "../target/idl/burn_and_earn.json";
+import { createMint, mintToOwner, getTokenBalance, initializeRegistry } from "./utils/bankrun-utils";
+
+describe("Treasury Tests", () => {
+  const TOKEN_DECIMALS = 9;
+  const ONE_TOKEN = BigInt(10 ** TOKEN_DECIMALS);
+  const tokens = (amount: number) => BigInt(amount) * ONE_TOKEN;
+  const asBn = (amount: bigint) => new BN(amount.toString());
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Treasury>;
+  let ammProgram: Program<MockAmm>;
+
+  let vcMint: PublicKey;
+  let vgMint: PublicKey;
+  let solMint: PublicKey;
+  let treasury: PublicKey;
+  let treasuryVgAccount: PublicKey;
+  let vgPool: PublicKey;
+  let vcPool: PublicKey;
+  const executor = Keypair.generate();
+
+  const findPda = (seeds: Buffer[], programId: PublicKey) => PublicKey.findProgramAddressSync(seeds, programId)[0];
+  const ata = (mint: PublicKey, owner: PublicKey) => getAssociatedTokenAddressSync(mint, owner, true, TOKEN_PROGRAM_ID);
+
+  // Пул mock-amm: PDA [pool, mint_a, mint_b], резервы на ATA пула
+  const createPool = async (mintA: PublicKey, mintB: PublicKey, reserveA: bigint, reserveB: bigint) => {
+    const pool = findPda([Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()], ammProgram.programId);
+    await ammProgram.methods
+      .initializePool()
+      .accounts({ payer: provider.wallet.publicKey, mintA, mintB, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+    await mintToOwner(provider, mintA, pool, reserveA);
+    await mintToOwner(provider, mintB, pool, reserveB);
+    return pool;
+  };
+
+  // out = reserve_out * in / (reserve_in + in), как в mock-amm
+  const quoteSwap = async (pool: PublicKey, sourceMint: PublicKey, destinationMint: PublicKey, amountIn: bigint) => {
+    const reserveIn = await getTokenBalance(context, ata(sourceMint, pool));
+    const reserveOut = await getTokenBalance(context, ata(destinationMint, pool));
+    return (reserveOut * amountIn) / (reserveIn + amountIn);
+  };
+
+  const buyback = (
+    authority: Keypair | null,
+    vgAmount: bigint,
+    minIntermediateOut: bigint,
+    minVcOut: bigint,
+    pools: { vgPool?: PublicKey } = {}
+  ) => {
+    const pool = pools.vgPool ?? vgPool;
+    const builder = program.methods
+      .buybackAndBurnVc(asBn(vgAmount), asBn(minIntermediateOut), asBn(minVcOut))
+      .accounts({
+        daoAuthority: authority ? authority.publicKey : provider.wallet.publicKey,
+        vgMint,
+        vcMint,
+        intermediateMint: solMint,
+        ammProgram: ammProgram.programId,
+        vgPool: pool,
+        vgPoolVgVault: ata(vgMint, pool),
+        vgPoolIntermediateVault: ata(solMint, pool),
+        vcPool,
+        vcPoolIntermediateVault: ata(solMint, vcPool),
+        vcPoolVcVault: ata(vcMint, vcPool),
+        vgTokenProgram: TOKEN_PROGRAM_ID,
+        intermediateTokenProgram: TOKEN_PROGRAM_ID,
+        vcTokenProgram: TOKEN_PROGRAM_ID,
+      });
+    return authority ? builder.signers([authority]).rpc() : builder.rpc();
+  };
+
+  const buybackRoute = () => ({
+    ammProgram: ammProgram.programId,
+    intermediateMint: solMint,
+    vgPool,
+    vgPoolVgVault: ata(vgMint, vgPool),
+    vgPoolIntermediateVault: ata(solMint, vgPool),
+    vcPool,
+    vcPoolIntermediateVault: ata(solMint, vcPool),
+    vcPoolVcVault: ata(vcMint, vcPool),
+  });
+
+  const expectError = async (promise: Promise<unknown>, errorName: string) => {
+    try {
+      await promise;
+      assert.fail(`Ожидалась ошибка ${errorName}`);
+    } catch (error) {
+      assert.include(error.toString(), errorName);
+    }
+  };
+
+  before("Подготовка bankrun, реестра, пулов и казны", async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<Treasury>(treasuryIdl as Treasury, provider);
+    ammProgram = new Program<MockAmm>(mockAmmIdl as MockAmm, provider);
+    const registryProgram = new Program<TechHyContracts>(registryIdl as TechHyContracts, provider);
+
+    vcMint = await createMint(provider, TOKEN_DECIMALS);
+    vgMint = await createMint(provider, TOKEN_DECIMALS);
+    solMint = await createMint(provider, TOKEN_DECIMALS);
+    treasury = findPda([Buffer.from("treasury")], program.programId);
+
+    await initializeRegistry(context, registryProgram, {
+      vcMint,
+      vgMint,
+      vcTokenProgram: Keypair.generate().publicKey,
+      vgTokenProgram: Keypair.generate().publicKey,
+      burnAndEarnProgram: Keypair.generate().publicKey,
+      daoTreasury: treasury,
+      feeCollector: Keypair.generate().publicKey,
+    });
+
+    context.setAccount(executor.publicKey, {
+      lamports: 1_000_000_000,
+      data: Buffer.alloc(0),
+      owner: anchor.web3.SystemProgram.programId,
+      executable: false,
+    });
+    await program.methods
+      .initialize(executor.publicKey)
+      .accounts({ payer: provider.wallet.publicKey, daoAuthority: provider.wallet.publicKey })
+      .rpc();
+
+    // 1 SOL = 1,000 VG = 1,000 VC
+    vgPool = await createPool(vgMint, solMint, tokens(1_000_000), tokens(1_000));
+    vcPool = await createPool(solMint, vcMint, tokens(1_000), tokens(1_000_000));
+
+    // Налоговые поступления казны
+    treasuryVgAccount = await mintToOwner(provider, vgMint, treasury, tokens(50_000));
+  });
+
+  it("Отклоняет маршрут выкупа не от DAO", async () => {
+    const stranger = Keypair.generate();
+    await expectError(
+      program.methods
+        .setBuybackRoute(buybackRoute())
+        .accounts({ daoAuthority: stranger.publicKey })
+        .signers([stranger])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+
+  it("DAO задает маршрут выкупа", async () => {
+    await program.methods.setBuybackRoute(buybackRoute()).accounts({ daoAuthority: provider.wallet.publicKey }).rpc();
+
+    const route = await program.account.buybackRoute.fetch(findPda([Buffer.from("treasury_buyback_route")], program.programId));
+    assert.equal(route.route.ammProgram.toBase58(), ammProgram.programId.toBase58());
+    assert.equal(route.route.vgPool.toBase58(), vgPool.toBase58());
+    assert.equal(route.route.vcPoolVcVault.toBase58(), ata(vcMint, vcPool).toBase58());
+  });
+
+  it("Отклоняет выкуп через пул вне маршрута", async () => {
+    // Пул с теми же минтами, но не утвержденный DAO: адаптер принял бы его, маршрут - нет
+    const rogueQuoteMint = await createMint(provider, TOKEN_DECIMALS);
+    const roguePool = await createPool(vgMint, rogueQuoteMint, tokens(1_000), tokens(1_000));
+    await expectError(buyback(null, tokens(1_000), BigInt(0), BigInt(0), { vgPool: roguePool }), "InvalidAmmAccount");
+    assert.equal(await getTokenBalance(context, treasuryVgAccount), tokens(50_000));
+  });
+
+  it("Отклоняет выкуп без подписи DAO", async () => {
+    await expectError(buyback(Keypair.generate(), tokens(1_000), BigInt(0), BigInt(0)), "Unauthorized");
+  });
+
+  it("Отклоняет нулевую сумму", async () => {
+    await expectError(buyback(null, BigInt(0), BigInt(0), BigInt(0)), "InvalidAmount");
+  });
+
+  it("Отклоняет выкуп при проскальзывании первого плеча", async () => {
+    const intermediateOut = await quoteSwap(vgPool, vgMint, solMint, tokens(10_000));
+    await expectError(buyback(null, tokens(10_000), intermediateOut + BigInt(1), BigInt(0)), "SlippageExceeded");
+  });
+
+  it("Отклоняет выкуп при проскальзывании второго плеча", async () => {
+    const intermediateOut = await quoteSwap(vgPool, vgMint, solMint, tokens(10_000));
+    const vcOut = await quoteSwap(vcPool, solMint, vcMint, intermediateOut);
+    await expectError(buyback(null, tokens(10_000), intermediateOut, vcOut + BigInt(1)), "SlippageExceeded");
+    assert.equal(await getTokenBalance(context, treasuryVgAccount), tokens(50_000));
+  });
+
+  it("Обменивает VG казны на VC и сжигает весь выкупленный VC", async () => {
+    const vgAmount = tokens(10_000);
+    const intermediateOut = await quoteSwap(vgPool, vgMint, solMint, vgAmount);
+    const vcOut = await quoteSwap(vcPool, solMint, vcMint, intermediateOut);
+    const vcPoolReserveBefore = await getTokenBalance(context, ata(vcMint, vcPool));
+
+    await buyback(null, vgAmount, intermediateOut, vcOut);
+
+    assert.equal(await getTokenBalance(context, treasuryVgAccount), tokens(40_000));
+    assert.equal(await getTokenBalance(context, ata(solMint, treasury)), BigInt(0));
+    assert.equal(await getTokenBalance(context, ata(vcMint, treasury)), BigInt(0));
+    assert.equal(await getTokenBalance(context, ata(vcMint, vcPool)), vcPoolReserveBefore - vcOut);
+
+    const state = await program.account.treasuryState.fetch(findPda([Buffer.from("treasury_state")], program.programId));
+    assert.equal(state.totalVgSwapped.toString(), vgAmount.toString());
+    assert.equal(state.totalVcBurned.toString(), vcOut.toString());
+  });
+
+  it("Накапливает объем сожженного VC", async () => {
+    const statePda = findPda([Buffer.from("treasury_state")], program.programId);
+    const before = await program.account.treasuryState.fetch(statePda);
+    const intermediateOut = await quoteSwap(vgPool, vgMint, solMint, tokens(5_000));
+    const vcOut = await quoteSwap(vcPool, solMint, vcMint, intermediateOut);
+
+    await buyback(null, tokens(5_000), BigInt(0), BigInt(0));
+
+    const after = await program.account.treasuryState.fetch(statePda);
+    assert.equal(after.totalVcBurned.sub(before.totalVcBurned).toString(), vcOut.toString());
+  });
+
+  it("get_vc_burned суммирует выкуп казны и сжигание Burn and Earn", async () => {
+    // Состояние Burn and Earn записывается напрямую: здесь важен только его счетчик
+    const burnAndEarnProgram = new Program<BurnAndEarn>(burnAndEarnIdl as BurnAndEarn, provider);
+    const [statePda, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("burn_and_earn_state")],
+      burnAndEarnProgram.programId
+    );
+    const burnAndEarnVcBurned = tokens(7_000);
+    const encoded = await burnAndEarnProgram.coder.accounts.encode("burnAndEarnState", {
+      version: 1,
+      authority: provider.wallet.publicKey,
+      vcMint,
+      vgMint,
+      totalLockedLp: new BN(0),
+      totalVgMinted: new BN(0),
+      totalVcBurned: asBn(burnAndEarnVcBurned),
+      bump,
+      escrowRemaining: new BN(0),
+      epochVgBudget: new BN(0),
+      currentEpoch: new BN(0),
+      epochVgEmitted: new BN(0),
+      emissionDecayBps: 0,
+      proRateOverCap: false,
+      accFeePerLp: new BN(0),
+      totalLpFeesHarvested: new BN(0),
+    });
+    const lamports = (await context.banksClient.getRent()).minimumBalance(BigInt(encoded.length));
+    context.setAccount(statePda, {
+      lamports: Number(lamports),
+      data: encoded,
+      owner: burnAndEarnProgram.programId,
+      executable: false,
+    });
+
+    const treasuryState = await program.account.treasuryState.fetch(
+      findPda([Buffer.from("treasury_state")], program.programId)
+    );
+    const report = await program.methods.getVcBurned().accounts({}).view();
+
+    assert.equal(report.treasuryVcBurned.toString(), treasuryState.totalVcBurned.toString());
+    assert.equal(report.burnAndEarnVcBurned.toString(), burnAndEarnVcBurned.toString());
+    assert.equal(
+      report.totalVcBurned.toString(),
+      treasuryState.totalVcBurned.add(asBn(burnAndEarnVcBurned)).toString()
+    );
+  });
+
+
+    type BudgetCategory = { development: {} } | { marketing: {} } | { liquidity: {} };
+    const SOL_ASSET = PublicKey.default;
+    const LAMPORTS_PER_SOL = BigInt(1_000_000_000);
+    const DEVELOPMENT: BudgetCategory = { development: {} };
+    const MARKETING: BudgetCategory = { marketing: {} };
+    const recipient = Keypair.generate();
+    let recipientVgAccount: PublicKey;
+
+    const findBudget = (categoryIndex: number, asset: PublicKey) =>
+      findPda([Buffer.from("treasury_budget"), Buffer.from([categoryIndex]), asset.toBuffer()], program.programId);
+
+    const setBudget = (signer: Keypair, category: BudgetCategory, categoryIndex: number, asset: PublicKey, allocated: bigint) =>
+      program.methods
+        .setCategoryBudget(category, asset, asBn(allocated))
+        .accounts({ daoAuthority: signer.publicKey, categoryBudget: findBudget(categoryIndex, asset) })
+        .signers([signer])
+        .rpc();
+
+    const spendVg = (signer: Keypair, amount: bigint) =>
+      program.methods
+        .spendTokens(DEVELOPMENT, asBn(amount))
+        .accounts({
+          governanceExecutor: signer.publicKey,
+          categoryBudget: findBudget(0, vgMint),
+          mint: vgMint,
+          recipientTokenAccount: recipientVgAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([signer])
+        .rpc();
+
+    const spendSol = (signer: Keypair, amount: bigint) =>
+      program.methods
+        .spendSol(MARKETING, asBn(amount))
+        .accounts({
+          governanceExecutor: signer.publicKey,
+          categoryBudget: findBudget(1, SOL_ASSET),
+          recipient: recipient.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+
+    // Бюджеты задает DAO (кошелек провайдера - авторитет реестра), тратит исполнитель
+    const dao = () => (provider.wallet as anchor.Wallet).payer;
+
+    const lamportsOf = async (address: PublicKey) => {
+      const account = await context.banksClient.getAccount(address);
+      return account === null ? BigInt(0) : BigInt(account.lamports);
+    };
+
+    before(async () => {
+      recipientVgAccount = await mintToOwner(provider, vgMint, recipient.publicKey, BigInt(0));
+      // SOL казны хранится на системном PDA
+      context.setAccount(treasury, {
+        lamports: Number(BigInt(10) * LAMPORTS_PER_SOL),
+        data: Buffer.alloc(0),
+        owner: anchor.web3.SystemProgram.programId,
+        executable: false,
+      });
+    });
+
+    it("Отклоняет установку бюджета не DAO, даже исполнителем governance", async () => {
+      await expectError(setBudget(executor, DEVELOPMENT, 0, vgMint, tokens(1_000)), "Unauthorized");
+    });
+
+    it("Отклоняет бюджет в активе, которого нет в казне", async () => {
+      await expectError(setBudget(dao(), DEVELOPMENT, 0, solMint, tokens(1_000)), "InvalidAsset");
+    });
+
+    it("Исполнитель тратит VG в рамках бюджета категории", async () => {
+      await setBudget(dao(), DEVELOPMENT, 0, vgMint, tokens(1_000));
+      const treasuryBefore = await getTokenBalance(context, treasuryVgAccount);
+
+      await spendVg(executor, tokens(400));
+
+      assert.equal(await getTokenBalance(context, recipientVgAccount), tokens(400));
+      assert.equal(await getTokenBalance(context, treasuryVgAccount), treasuryBefore - tokens(400));
+      const budget = await program.account.categoryBudget.fetch(findBudget(0, vgMint));
+      assert.equal(budget.spent.toString(), tokens(400).toString());
+    });
+
+    it("Отклоняет расход сверх бюджета категории", async () => {
+      await expectError(spendVg(executor, tokens(601)), "BudgetExceeded");
+    });
+
+    it("Отклоняет расход не исполнителем, даже DAO", async () => {
+      await expectError(spendVg(dao(), tokens(1)), "Unauthorized");
+    });
+
+    it("Отклоняет бюджет меньше уже потраченного", async () => {
+      await expectError(setBudget(dao(), DEVELOPMENT, 0, vgMint, tokens(399)), "InvalidBudget");
+    });
+
+    it("Исполнитель тратит SOL в рамках бюджета категории", async () => {
+      await setBudget(dao(), MARKETING, 1, SOL_ASSET, BigInt(2) * LAMPORTS_PER_SOL);
+      const treasuryBefore = await lamportsOf(treasury);
+
+      await spendSol(executor, LAMPORTS_PER_SOL);
+
+      assert.equal(await lamportsOf(recipient.publicKey), LAMPORTS_PER_SOL);
+      assert.equal(await lamportsOf(treasury), treasuryBefore - LAMPORTS_PER_SOL);
+      await expectError(spendSol(executor, LAMPORTS_PER_SOL + BigInt(1)), "BudgetExceeded");
+    });
+
+    it("DAO меняет исполнителя governance", async () => {
+      const newExecutor = Keypair.generate();
+      await program.methods
+        .setGovernanceExecutor(newExecutor.publicKey)
+        .accounts({ daoAuthority: provider.wallet.publicKey })
+        .rpc();
+
+      await expectError(spendVg(executor, tokens(1)), "Unauthorized");
+      const state = await program.account.treasuryState.fetch(findPda([Buffer.from("treasury_state")], program.programId));
+      assert.equal(state.governanceExecutor.toBase58(), newExecutor.publicKey.toBase58());
+    });
+  });
+});
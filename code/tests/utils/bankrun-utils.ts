@@ -0,0 +1,231 @@
+import { ProgramTestContext, Clock } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { BN, Program } from "@coral-xyz/anchor";
+import { AccountMeta, Keypair, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  MINT_SIZE,
+  AccountLayout,
+  createInitializeMint2Instruction,
+  createAssociatedTokenAccountIdempotentInstruction,
+  createMintToInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { VgToken } from "../../target/types/vg_token";
+import { TechHyContracts } from "../../target/types/tech_hy_contracts";
+
+// Хелперы для тестов на bankrun: управление временем и минимальные операции с токенами
+
+/** Текущее время кластера (unix timestamp) */
+export async function getUnixTimestamp(context: ProgramTestContext): Promise<number> {
+  const clock = await context.banksClient.getClock();
+  return Number(clock.unixTimestamp);
+}
+
+/** Переводит часы кластера на заданный unix timestamp (слот и эпоха не меняются) */
+export async function warpToTimestamp(context: ProgramTestContext, unixTimestamp: number): Promise<void> {
+  const clock = await context.banksClient.getClock();
+  context.setClock(
+    new Clock(
+      clock.slot,
+      clock.epochStartTimestamp,
+      clock.epoch,
+      clock.leaderScheduleEpoch,
+      BigInt(unixTimestamp)
+    )
+  );
+}
+
+/** Переводит кластер в заданную эпоху, сохраняя остальные поля часов */
+export async function warpToEpoch(context: ProgramTestContext, epoch: number): Promise<void> {
+  const clock = await context.banksClient.getClock();
+  context.setClock(
+    new Clock(
+      clock.slot,
+      clock.epochStartTimestamp,
+      BigInt(epoch),
+      clock.leaderScheduleEpoch,
+      clock.unixTimestamp
+    )
+  );
+}
+
+/** Переводит кластер на заданный слот */
+export function warpToSlot(context: ProgramTestContext, slot: number): void {
+  context.warpToSlot(BigInt(slot));
+}
+
+/** Создает минт, authority которого - кошелек провайдера */
+export async function createMint(
+  provider: BankrunProvider,
+  decimals = 9,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+): Promise<PublicKey> {
+  const mint = Keypair.generate();
+  const payer = provider.wallet.publicKey;
+  const lamports = Number(
+    (await provider.context.banksClient.getRent()).minimumBalance(BigInt(MINT_SIZE))
+  );
+  const tx = new Transaction().add(
+    SystemProgram.createAccount({
+      fromPubkey: payer,
+      newAccountPubkey: mint.publicKey,
+      space: MINT_SIZE,
+      lamports,
+      programId: tokenProgram,
+    }),
+    createInitializeMint2Instruction(mint.publicKey, decimals, payer, null, tokenProgram)
+  );
+  await provider.sendAndConfirm!(tx, [mint]);
+  return mint.publicKey;
+}
+
+/** Создает ATA владельца (если нужно) и минтит на него `amount` токенов */
+export async function mintToOwner(
+  provider: BankrunProvider,
+  mint: PublicKey,
+  owner: PublicKey,
+  amount: bigint,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+): Promise<PublicKey> {
+  const payer = provider.wallet.publicKey;
+  const ata = getAssociatedTokenAddressSync(mint, owner, true, tokenProgram);
+  const tx = new Transaction().add(
+    createAssociatedTokenAccountIdempotentInstruction(payer, ata, owner, mint, tokenProgram),
+    createMintToInstruction(mint, ata, payer, amount, [], tokenProgram)
+  );
+  await provider.sendAndConfirm!(tx);
+  return ata;
+}
+
+/** Баланс токен-аккаунта; 0, если аккаунт не существует */
+export async function getTokenBalance(context: ProgramTestContext, tokenAccount: PublicKey): Promise<bigint> {
+  const account = await context.banksClient.getAccount(tokenAccount);
+  if (account === null) {
+    return BigInt(0);
+  }
+  return AccountLayout.decode(Buffer.from(account.data)).amount;
+}
+
+/** Адреса, записываемые в реестр экосистемы */
+export type RegistryParams = {
+  vcMint: PublicKey;
+  vgMint: PublicKey;
+  vcTokenProgram: PublicKey;
+  vgTokenProgram: PublicKey;
+  burnAndEarnProgram: PublicKey;
+  daoTreasury: PublicKey;
+  feeCollector: PublicKey;
+};
+
+/** Размер аккаунта реестра: 8 + EcosystemRegistry::INIT_SPACE */
+const ECOSYSTEM_REGISTRY_SPACE = 8 + 399;
+
+/**
+ * Записывает реестр экосистемы напрямую в bankrun. `initialize_registry` доступен только
+ * upgrade authority, а `startAnchor` загружает программы без ProgramData, поэтому
+ * инструкцию здесь не вызвать. Авторитет реестра по умолчанию - кошелек провайдера.
+ */
+export async function initializeRegistry(
+  context: ProgramTestContext,
+  registryProgram: Program<TechHyContracts>,
+  params: RegistryParams,
+  authority: PublicKey = registryProgram.provider.publicKey!
+): Promise<PublicKey> {
+  const [registry, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from("ecosystem_registry")],
+    registryProgram.programId
+  );
+  const encoded = await registryProgram.coder.accounts.encode("ecosystemRegistry", {
+    version: 1,
+    authority,
+    ...params,
+    paused: false,
+    pauseReason: "",
+    pausedAt: new BN(0),
+    bump,
+  });
+  const data = Buffer.alloc(ECOSYSTEM_REGISTRY_SPACE);
+  encoded.copy(data);
+  const lamports = (await context.banksClient.getRent()).minimumBalance(BigInt(data.length));
+  context.setAccount(registry, {
+    lamports: Number(lamports),
+    data,
+    owner: registryProgram.programId,
+    executable: false,
+  });
+  return registry;
+}
+
+/**
+ * Переключает аварийную паузу реестра напрямую, минуя совет: для тестов программ,
+ * которые только читают `registry.paused`. Сам совет проверяется в emergency-council.ts.
+ */
+export async function setRegistryPaused(
+  context: ProgramTestContext,
+  registryProgram: Program<TechHyContracts>,
+  paused: boolean
+): Promise<void> {
+  const [registry] = PublicKey.findProgramAddressSync([Buffer.from("ecosystem_registry")], registryProgram.programId);
+  const account = await context.banksClient.getAccount(registry);
+  const state = registryProgram.coder.accounts.decode("ecosystemRegistry", Buffer.from(account!.data));
+  const encoded = await registryProgram.coder.accounts.encode("ecosystemRegistry", {
+    ...state,
+    paused,
+    pauseReason: paused ? "test" : "",
+  });
+  const data = Buffer.alloc(account!.data.length);
+  encoded.copy(data);
+  context.setAccount(registry, { ...account!, data });
+}
+
+/** Программа-хук VG для localnet; в bankrun по этому адресу загружается mock-transfer-hook */
+export const VG_TRANSFER_HOOK_PROGRAM_ID = new PublicKey("Hkmi3BdVG9SyhxMzmeWSVj1MXUyKnqRtuPmiDuC2fFvk");
+
+/** Аккаунты Transfer Hook для remaining_accounts переводов VG (заглушке хватает самой программы) */
+export const vgHookAccounts = (): AccountMeta[] => [
+  { pubkey: VG_TRANSFER_HOOK_PROGRAM_ID, isSigner: false, isWritable: false },
+];
+
+/**
+ * Создает канонический минт VG (Token-2022 с TransferHook) через vg-token `initialize`:
+ * вся эмиссия поступает на ATA `holder`, который в тестах подписывает переводы вместо
+ * эскроу Burn and Earn. Налог - 10%, поровну казне DAO и держателям NFT Fee Key.
+ * Реестр с `vgMint` = PDA минта должен быть создан заранее.
+ */
+export async function initializeVgToken(
+  provider: BankrunProvider,
+  program: Program<VgToken>,
+  holder: PublicKey
+): Promise<PublicKey> {
+  const [mint] = PublicKey.findProgramAddressSync([Buffer.from("vg_token_mint")], program.programId);
+  await program.methods
+    .initialize()
+    .accounts({
+      payer: provider.wallet.publicKey,
+      burnAndEarnEscrowPda: holder,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    })
+    .rpc();
+  await program.methods
+    .upsertTaxConfig(1000, 5000, 5000)
+    .accounts({ payer: provider.wallet.publicKey, authority: provider.wallet.publicKey })
+    .rpc();
+  return mint;
+}
+
+/** Создает ATA владельца для минта, если его нет */
+export async function createTokenAccount(
+  provider: BankrunProvider,
+  mint: PublicKey,
+  owner: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+): Promise<PublicKey> {
+  const payer = provider.wallet.publicKey;
+  const ata = getAssociatedTokenAddressSync(mint, owner, true, tokenProgram);
+  await provider.sendAndConfirm!(
+    new Transaction().add(createAssociatedTokenAccountIdempotentInstruction(payer, ata, owner, mint, tokenProgram))
+  );
+  return ata;
+}
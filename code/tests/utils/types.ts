@@ -147,10 +147,18 @@ export interface BurnAndEarnIdl extends Idl {
         { name: "tokenProgram"; isMut: false; isSigner: false },
         { name: "associatedTokenProgram"; isMut: false; isSigner: false },
         { name: "systemProgram"; isMut: false; isSigner: false },
-        { name: "clock"; isMut: false; isSigner: false }
+        { name: "clock"; isMut: false; isSigner: false },
+        { name: "referralLink"; isMut: true; isSigner: false },
+        { name: "referralConfig"; isMut: false; isSigner: false; isOptional: true },
+        { name: "referrerLpRecord"; isMut: false; isSigner: false; isOptional: true },
+        { name: "referrerStats"; isMut: true; isSigner: false; isOptional: true }
       ];
       args: [
-        { name: "vcAmount"; type: "u64" }
+        { name: "vcAmount"; type: "u64" },
+        { name: "minLpOut"; type: "u64" },
+        { name: "minVgOut"; type: "u64" },
+        { name: "deadline"; type: "i64" },
+        { name: "referrer"; type: { option: "publicKey" } }
       ];
     },
     {
@@ -1,8 +1,9 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { VcToken } from "../target/types/vc_token";
-import { PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY, LAMPORTS_PER_SOL } from "@solana/web3.js";
-import { TOKEN_PROGRAM_ID, getMinimumBalanceForRentExemptMint, MINT_SIZE, createInitializeMintInstruction, TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddress, getAccount, getMint, createAssociatedTokenAccountInstruction, transferCheckedInstruction, createTransferCheckedInstruction } from "@solana/spl-token";
+import { TechHyContracts } from "../target/types/tech_hy_contracts";
+import { PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY, LAMPORTS_PER_SOL, BPF_LOADER_UPGRADEABLE_PROGRAM_ID } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, getMinimumBalanceForRentExemptMint, MINT_SIZE, createInitializeMintInstruction, TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddress, getAccount, getMint, createAssociatedTokenAccountInstruction, transferCheckedInstruction, createTransferCheckedInstruction, getAssociatedTokenAddressSync } from "@solana/spl-token";
 import { assert } from "chai";
 import * as mpl from '@metaplex-foundation/mpl-token-metadata';
 import { PROGRAM_ID as METADATA_PROGRAM_ID } from '@metaplex-foundation/mpl-token-metadata';
@@ -12,6 +13,7 @@ describe("vc-token", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
   const provider = anchor.getProvider() as anchor.AnchorProvider;
   const program = anchor.workspace.VcToken as Program<VcToken>;
+  const registryProgram = anchor.workspace.TechHyContracts as Program<TechHyContracts>;
   
   // Генерируем keypair для теста
   const mintKeypair = Keypair.generate();
@@ -26,11 +28,32 @@ describe("vc-token", () => {
   // Создаём казначейский кошелёк для тестов
   const treasuryWallet = Keypair.generate();
   const recipientWallet = Keypair.generate();
+  const liquidityWallet = Keypair.generate();
   
   // Константы из программы
   const VC_TOKEN_MINT_SEED = Buffer.from("vc_token_mint");
   const TOKEN_DECIMALS = 9;
   const TOTAL_SUPPLY = BigInt(5_000_000_000) * BigInt(10 ** TOKEN_DECIMALS);
+  // VC - базовый SPL токен (классическая программа Token, не Token-2022)
+  const VC_TOKEN_PROGRAM_ID = TOKEN_PROGRAM_ID;
+
+  // Таблица распределения эмиссии: 4 млрд в казну DAO, 1 млрд в пул ликвидности
+  const TREASURY_SHARE = BigInt(4_000_000_000) * BigInt(10 ** TOKEN_DECIMALS);
+  const distribution = [
+    { recipient: treasuryWallet.publicKey, amount: new anchor.BN(TREASURY_SHARE.toString()) },
+    { recipient: liquidityWallet.publicKey, amount: new anchor.BN((TOTAL_SUPPLY - TREASURY_SHARE).toString()) },
+  ];
+
+  // Пары [владелец, ATA] для каждой записи передаются через remaining_accounts
+  const distributionAccounts = (entries: { recipient: PublicKey }[]) =>
+    entries.flatMap(({ recipient }) => [
+      { pubkey: recipient, isSigner: false, isWritable: false },
+      {
+        pubkey: getAssociatedTokenAddressSync(mintPda, recipient, false, VC_TOKEN_PROGRAM_ID),
+        isSigner: false,
+        isWritable: true,
+      },
+    ]);
   
   // Находим PDA для минта
   const [mintPda] = PublicKey.findProgramAddressSync(
@@ -48,6 +71,33 @@ describe("vc-token", () => {
     METADATA_PROGRAM_ID
   );
   
+  // Реестр экосистемы: initialize доступна только его авторитету (DAO)
+  const [registryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("ecosystem_registry")],
+    registryProgram.programId
+  );
+  // ProgramData реестра: initialize_registry доступен только upgrade authority (кошелек провайдера)
+  const [registryProgramData] = PublicKey.findProgramAddressSync(
+    [registryProgram.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  );
+
+  const uri = "https://tech-hy.io/metadata/vc-token.json";
+
+  // set_metadata обязана идти в одной транзакции с initialize
+  const setMetadataIx = () =>
+    program.methods
+      .setMetadata(name, symbol, uri)
+      .accounts({
+        authority: provider.publicKey,
+        mint: mintPda,
+        metadata: metadataPda,
+        payer: provider.publicKey,
+        metadataProgram: METADATA_PROGRAM_ID,
+        tokenProgram: VC_TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
   before("Подготовка тестового окружения", async () => {
     // Финансируем тестовый казначейский кошелёк
     const tx = await provider.connection.requestAirdrop(
@@ -55,8 +105,105 @@ describe("vc-token", () => {
       1 * LAMPORTS_PER_SOL
     );
     await provider.connection.confirmTransaction(tx);
+
+    // Авторитет реестра - кошелек провайдера: он подписывает initialize и set_metadata
+    const registry = await registryProgram.account.ecosystemRegistry.fetchNullable(registryPda);
+    if (registry === null) {
+      await registryProgram.methods
+        .initializeRegistry({
+          vcMint: mintPda,
+          vgMint: Keypair.generate().publicKey,
+          vcTokenProgram: program.programId,
+          vgTokenProgram: Keypair.generate().publicKey,
+          burnAndEarnProgram: Keypair.generate().publicKey,
+          daoTreasury: treasuryWallet.publicKey,
+          feeCollector: Keypair.generate().publicKey,
+        })
+        .accounts({ authority: provider.publicKey, programData: registryProgramData })
+        .rpc();
+    }
   });
   
+  it("Отклоняет таблицу распределения с суммой, не равной эмиссии", async () => {
+    const shortDistribution = [
+      { recipient: treasuryWallet.publicKey, amount: new anchor.BN(TREASURY_SHARE.toString()) },
+    ];
+    try {
+      await program.methods
+        .initialize(shortDistribution)
+        .accountsPartial({
+          payer: provider.publicKey,
+          authority: provider.publicKey,
+          mint: mintPda,
+          tokenProgram: VC_TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(distributionAccounts(shortDistribution))
+        .postInstructions([await setMetadataIx()])
+        .rpc();
+      assert.fail("Инициализация должна требовать полного распределения эмиссии");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidDistribution");
+    }
+  });
+
+  it("Отклоняет initialize не от авторитета реестра", async () => {
+    const stranger = Keypair.generate();
+    try {
+      await program.methods
+        .initialize(distribution)
+        .accountsPartial({
+          payer: provider.publicKey,
+          authority: stranger.publicKey,
+          mint: mintPda,
+          tokenProgram: VC_TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(distributionAccounts(distribution))
+        .postInstructions([await setMetadataIx()])
+        .signers([stranger])
+        .rpc();
+      assert.fail("Посторонний не должен становиться авторитетом VC и выбирать таблицу распределения");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized");
+    }
+  });
+
+  it("Отклоняет initialize без set_metadata в той же транзакции", async () => {
+    try {
+      await program.methods
+        .initialize(distribution)
+        .accountsPartial({
+          payer: provider.publicKey,
+          authority: provider.publicKey,
+          mint: mintPda,
+          tokenProgram: VC_TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(distributionAccounts(distribution))
+        .rpc();
+      assert.fail("initialize без set_metadata оставила бы живую mint authority");
+    } catch (error) {
+      assert.include(error.toString(), "MetadataInstructionMissing");
+    }
+  });
+
+  it("Отклоняет initialize с Token-2022 вместо SPL Token", async () => {
+    try {
+      await program.methods
+        .initialize(distribution)
+        .accountsPartial({
+          payer: provider.publicKey,
+          authority: provider.publicKey,
+          mint: mintPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts(distributionAccounts(distribution))
+        .postInstructions([await setMetadataIx()])
+        .rpc();
+      assert.fail("VC должен создаваться только в классическом SPL Token");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidProgramId");
+    }
+  });
+
   it("Должен инициализировать VC токен", async () => {
     console.log("Минт PDA:", mintPda.toString());
     console.log("Казначейский кошелек:", treasuryWallet.publicKey.toString());
@@ -66,20 +213,29 @@ describe("vc-token", () => {
       mintPda,
       treasuryWallet.publicKey,
       false,
-      TOKEN_2022_PROGRAM_ID
+      VC_TOKEN_PROGRAM_ID
     );
     console.log("Токен-аккаунт казначейства:", treasuryTokenAccount.toString());
     
-    // Инициализируем токен
+    const liquidityTokenAccount = await getAssociatedTokenAddress(
+      mintPda,
+      liquidityWallet.publicKey,
+      false,
+      VC_TOKEN_PROGRAM_ID
+    );
+
+    // Инициализируем токен: казна DAO и пул ликвидности получают свои доли эмиссии,
+    // метаданные создаются и mint authority отзывается в той же транзакции
     const tx = await program.methods
-      .initialize()
-      .accounts({
+      .initialize(distribution)
+      .accountsPartial({
         payer: provider.publicKey,
-        treasury: treasuryWallet.publicKey,
+        authority: provider.publicKey,
         mint: mintPda,
-        treasuryTokenAccount: treasuryTokenAccount,
-        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        tokenProgram: VC_TOKEN_PROGRAM_ID,
       })
+      .remainingAccounts(distributionAccounts(distribution))
+      .postInstructions([await setMetadataIx()])
       .rpc();
     
     console.log("Транзакция:", tx);
@@ -89,7 +245,7 @@ describe("vc-token", () => {
       provider.connection,
       mintPda,
       "confirmed",
-      TOKEN_2022_PROGRAM_ID
+      VC_TOKEN_PROGRAM_ID
     );
     
     console.log("Информация о минте:", mintInfo);
@@ -116,32 +272,32 @@ describe("vc-token", () => {
       provider.connection,
       treasuryTokenAccount,
       "confirmed",
-      TOKEN_2022_PROGRAM_ID
+      VC_TOKEN_PROGRAM_ID
     );
     
     assert.equal(
       treasuryAccount.amount.toString(),
-      TOTAL_SUPPLY.toString(),
-      "Казначейство должно иметь все выпущенные токены"
+      TREASURY_SHARE.toString(),
+      "Казначейство должно получить свою долю эмиссии"
+    );
+
+    const liquidityAccount = await getAccount(
+      provider.connection,
+      liquidityTokenAccount,
+      "confirmed",
+      VC_TOKEN_PROGRAM_ID
+    );
+
+    assert.equal(
+      liquidityAccount.amount.toString(),
+      (TOTAL_SUPPLY - TREASURY_SHARE).toString(),
+      "Пул ликвидности должен получить остаток эмиссии"
     );
   });
   
-  it("Устанавливает метаданные токена", async () => {
-    const uri = "https://tech-hy.io/metadata/vc-token.json";
-    
+  it("Устанавливает метаданные токена вместе с initialize", async () => {
     console.log("Metadata PDA:", metadataPda.toString());
-    
-    await program.methods
-      .setMetadata(name, symbol, uri)
-      .accounts({
-        authority: provider.publicKey,
-        mint: mintPda,
-        metadata: metadataPda,
-        payer: provider.publicKey,
-        metadataProgram: METADATA_PROGRAM_ID,
-      })
-      .rpc();
-    
+
     // Получение и проверка метаданных
     try {
       const metadataAccount = await mpl.accounts.Metadata.fromAccountAddress(
@@ -164,6 +320,40 @@ describe("vc-token", () => {
     }
   });
   
+  it("Отклоняет set_metadata после инициализации", async () => {
+    try {
+      await program.methods
+        .setMetadata(name, symbol, uri)
+        .accounts({
+          authority: provider.publicKey,
+          mint: mintPda,
+          metadata: metadataPda,
+          payer: provider.publicKey,
+          metadataProgram: METADATA_PROGRAM_ID,
+          tokenProgram: VC_TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("Метаданные нельзя менять после инициализации");
+    } catch (error) {
+      assert.include(error.toString(), "MetadataWindowClosed");
+    }
+  });
+
+  it("Аудит минта подтверждает инварианты VC", async () => {
+    const report = await program.methods
+      .auditMint()
+      .accounts({ mint: mintPda })
+      .view();
+
+    assert.equal(report.mint.toBase58(), mintPda.toBase58());
+    assert.equal(report.ownerProgram.toBase58(), VC_TOKEN_PROGRAM_ID.toBase58());
+    assert.equal(report.supply.toString(), TOTAL_SUPPLY.toString());
+    assert.equal(report.decimals, TOKEN_DECIMALS);
+    assert.isTrue(report.mintAuthorityRevoked, "Mint authority должна быть отозвана");
+    assert.isTrue(report.freezeAuthorityRevoked, "Freeze authority должна отсутствовать");
+    assert.isTrue(report.passed, "Аудит минта VC не пройден");
+  });
+
   // Новые тесты для крайних случаев
   it("Проверяет попытку передачи токенов при недостаточном балансе", async () => {
     // Создаем новый кошелек без токенов
@@ -174,7 +364,7 @@ describe("vc-token", () => {
       mintPda,
       emptyWallet.publicKey,
       false,
-      TOKEN_2022_PROGRAM_ID
+      VC_TOKEN_PROGRAM_ID
     );
     
     // Инициализируем токен-аккаунт (без токенов)
@@ -185,7 +375,7 @@ describe("vc-token", () => {
           emptyTokenAccount,
           emptyWallet.publicKey,
           mintPda,
-          TOKEN_2022_PROGRAM_ID
+          VC_TOKEN_PROGRAM_ID
         )
       )
     );
@@ -195,7 +385,7 @@ describe("vc-token", () => {
       provider.connection,
       emptyTokenAccount,
       "confirmed",
-      TOKEN_2022_PROGRAM_ID
+      VC_TOKEN_PROGRAM_ID
     );
     assert.equal(accountBefore.amount.toString(), "0", "Начальный баланс должен быть нулевым");
     
@@ -205,7 +395,7 @@ describe("vc-token", () => {
         mintPda,
         recipientWallet.publicKey,
         false,
-        TOKEN_2022_PROGRAM_ID
+        VC_TOKEN_PROGRAM_ID
       );
       
       const transferTx = new anchor.web3.Transaction().add(
@@ -217,7 +407,7 @@ describe("vc-token", () => {
           100 * 10**decimals,
           decimals,
           [],
-          TOKEN_2022_PROGRAM_ID
+          VC_TOKEN_PROGRAM_ID
         )
       );
       
@@ -235,7 +425,7 @@ describe("vc-token", () => {
       mintPda,
       treasuryWallet.publicKey,
       false,
-      TOKEN_2022_PROGRAM_ID
+      VC_TOKEN_PROGRAM_ID
     );
     
     // Проверяем текущий баланс казначейства
@@ -243,7 +433,7 @@ describe("vc-token", () => {
       provider.connection,
       treasuryTokenAccount,
       "confirmed",
-      TOKEN_2022_PROGRAM_ID
+      VC_TOKEN_PROGRAM_ID
     );
     
     // Получаем целевой токен-аккаунт для тестового перевода
@@ -252,7 +442,7 @@ describe("vc-token", () => {
       mintPda,
       testRecipient.publicKey,
       false,
-      TOKEN_2022_PROGRAM_ID
+      VC_TOKEN_PROGRAM_ID
     );
     
     // Создаем токен-аккаунт для получателя
@@ -263,7 +453,7 @@ describe("vc-token", () => {
           testRecipientTokenAccount,
           testRecipient.publicKey,
           mintPda,
-          TOKEN_2022_PROGRAM_ID
+          VC_TOKEN_PROGRAM_ID
         )
       )
     );
@@ -281,7 +471,7 @@ describe("vc-token", () => {
         Number(testAmount),
         decimals,
         [],
-        TOKEN_2022_PROGRAM_ID
+        VC_TOKEN_PROGRAM_ID
       )
     );
     
@@ -292,7 +482,7 @@ describe("vc-token", () => {
       provider.connection,
       testRecipientTokenAccount,
       "confirmed",
-      TOKEN_2022_PROGRAM_ID
+      VC_TOKEN_PROGRAM_ID
     );
     
     assert.equal(
@@ -306,7 +496,7 @@ describe("vc-token", () => {
       provider.connection,
       treasuryTokenAccount,
       "confirmed",
-      TOKEN_2022_PROGRAM_ID
+      VC_TOKEN_PROGRAM_ID
     );
     
     const expectedTreasuryBalance = BigInt(treasuryAccount.amount.toString()) - testAmount;
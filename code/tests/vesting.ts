@@ -0,0 +1,243 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { startAnchor, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { assert } from "chai";
+import { TechHyVesting } from "../target/types/tech_hy_vesting";
+import { TechHyContracts } from "../target/types/tech_hy_contracts";
+import vestingIdl from "../target/idl/tech_hy_vesting.json";
+import registryIdl from "../target/idl/tech_hy_contracts.json";
+import {
+  createMint,
+  mintToOwner,
+  getTokenBalance,
+  getUnixTimestamp,
+  warpToTimestamp,
+  initializeRegistry,
+} from "./utils/bankrun-utils";
+
+describe("Vesting Tests", () => {
+  const TOKEN_DECIMALS = 9;
+  const TOTAL = BigInt(1_000) * BigInt(10 ** TOKEN_DECIMALS);
+  const CLIFF_SECONDS = 100;
+  const DURATION_SECONDS = 1_000;
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<TechHyVesting>;
+  let registryProgram: Program<TechHyContracts>;
+
+  const beneficiary = Keypair.generate();
+  const daoTreasury = Keypair.generate();
+  let vcMint: PublicKey;
+  let funderTokenAccount: PublicKey;
+  let startTs: number;
+
+  const findIndex = (owner: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vesting_index"), owner.toBuffer()],
+      program.programId
+    )[0];
+
+  const findSchedule = (owner: PublicKey, index: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vesting_schedule"), owner.toBuffer(), new BN(index).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  const ata = (owner: PublicKey) => getAssociatedTokenAddressSync(vcMint, owner, true, TOKEN_PROGRAM_ID);
+
+  const createSchedule = (amount: bigint, start: number, cliff: number, end: number, revocable = true) =>
+    program.methods
+      .createSchedule({
+        totalAmount: new BN(amount.toString()),
+        startTs: new BN(start),
+        cliffTs: new BN(cliff),
+        endTs: new BN(end),
+        revocable,
+      })
+      .accounts({
+        funder: provider.wallet.publicKey,
+        beneficiary: beneficiary.publicKey,
+        mint: vcMint,
+        funderTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  const claim = (index: number) =>
+    program.methods
+      .claimVested()
+      .accounts({
+        beneficiary: beneficiary.publicKey,
+        vestingSchedule: findSchedule(beneficiary.publicKey, index),
+        mint: vcMint,
+        vault: ata(findSchedule(beneficiary.publicKey, index)),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([beneficiary])
+      .rpc();
+
+  before("Подготовка bankrun, реестра и минта VC", async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<TechHyVesting>(vestingIdl as TechHyVesting, provider);
+    registryProgram = new Program<TechHyContracts>(registryIdl as TechHyContracts, provider);
+
+    vcMint = await createMint(provider, TOKEN_DECIMALS);
+    funderTokenAccount = await mintToOwner(provider, vcMint, provider.wallet.publicKey, TOTAL * BigInt(3));
+
+    // Реестр: вестинг принимает только VC/VG минты из него, а DAO - его авторитет
+    await initializeRegistry(context, registryProgram, {
+      vcMint,
+      vgMint: Keypair.generate().publicKey,
+      vcTokenProgram: Keypair.generate().publicKey,
+      vgTokenProgram: Keypair.generate().publicKey,
+      burnAndEarnProgram: Keypair.generate().publicKey,
+      daoTreasury: daoTreasury.publicKey,
+      feeCollector: Keypair.generate().publicKey,
+    });
+
+    // Бенефициару нужны лампорты для создания своего ATA при получении
+    context.setAccount(beneficiary.publicKey, {
+      lamports: 1_000_000_000,
+      data: Buffer.alloc(0),
+      owner: anchor.web3.SystemProgram.programId,
+      executable: false,
+    });
+
+    startTs = await getUnixTimestamp(context);
+  });
+
+  it("Отклоняет график с клиффом после окончания", async () => {
+    try {
+      await createSchedule(TOTAL, startTs, startTs + DURATION_SECONDS + 1, startTs + DURATION_SECONDS);
+      assert.fail("График с cliff > end должен быть отклонен");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidSchedule");
+    }
+  });
+
+  it("Отклоняет минт, которого нет в реестре", async () => {
+    const foreignMint = await createMint(provider, TOKEN_DECIMALS);
+    const foreignAccount = await mintToOwner(provider, foreignMint, provider.wallet.publicKey, TOTAL);
+    try {
+      await program.methods
+        .createSchedule({
+          totalAmount: new BN(TOTAL.toString()),
+          startTs: new BN(startTs),
+          cliffTs: new BN(startTs + CLIFF_SECONDS),
+          endTs: new BN(startTs + DURATION_SECONDS),
+          revocable: true,
+        })
+        .accounts({
+          funder: provider.wallet.publicKey,
+          beneficiary: beneficiary.publicKey,
+          mint: foreignMint,
+          funderTokenAccount: foreignAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("Вестинг должен принимать только VC и VG");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidMint");
+    }
+  });
+
+  it("Создает график вестинга и блокирует сумму в хранилище", async () => {
+    await createSchedule(TOTAL, startTs, startTs + CLIFF_SECONDS, startTs + DURATION_SECONDS);
+
+    const schedulePda = findSchedule(beneficiary.publicKey, 0);
+    const schedule = await program.account.vestingSchedule.fetch(schedulePda);
+    assert.equal(schedule.index.toNumber(), 0);
+    assert.equal(schedule.totalAmount.toString(), TOTAL.toString());
+    assert.equal(await getTokenBalance(context, ata(schedulePda)), TOTAL);
+  });
+
+  it("Ничего не выдает до клиффа", async () => {
+    await warpToTimestamp(context, startTs + CLIFF_SECONDS - 1);
+    try {
+      await claim(0);
+      assert.fail("До клиффа получать нечего");
+    } catch (error) {
+      assert.include(error.toString(), "NothingToClaim");
+    }
+  });
+
+  it("Выдает линейно разблокированную часть после клиффа", async () => {
+    await warpToTimestamp(context, startTs + DURATION_SECONDS / 2);
+    await claim(0);
+
+    assert.equal(await getTokenBalance(context, ata(beneficiary.publicKey)), TOTAL / BigInt(2));
+  });
+
+  it("Отклоняет отзыв не-DAO", async () => {
+    const stranger = Keypair.generate();
+    const schedulePda = findSchedule(beneficiary.publicKey, 0);
+    try {
+      await program.methods
+        .revokeUnvested()
+        .accounts({
+          authority: stranger.publicKey,
+          vestingSchedule: schedulePda,
+          mint: vcMint,
+          vault: ata(schedulePda),
+          funder: provider.wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Отзыв доступен только DAO");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized");
+    }
+  });
+
+  it("DAO отзывает неразблокированную часть фандеру, разблокированная остается бенефициару", async () => {
+    const schedulePda = findSchedule(beneficiary.publicKey, 0);
+    await warpToTimestamp(context, startTs + (DURATION_SECONDS * 3) / 4);
+    const funderBalanceBefore = await getTokenBalance(context, funderTokenAccount);
+
+    await program.methods
+      .revokeUnvested()
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vestingSchedule: schedulePda,
+        mint: vcMint,
+        vault: ata(schedulePda),
+        funder: provider.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    // Неразблокированная четверть возвращается фандеру, а не в казну DAO
+    assert.equal(await getTokenBalance(context, funderTokenAccount), funderBalanceBefore + TOTAL / BigInt(4));
+    assert.isNull(await context.banksClient.getAccount(ata(daoTreasury.publicKey)));
+
+    // После отзыва время больше не влияет: доступна только часть, разблокированная на момент отзыва
+    await warpToTimestamp(context, startTs + DURATION_SECONDS * 2);
+    await claim(0);
+    assert.equal(await getTokenBalance(context, ata(beneficiary.publicKey)), (TOTAL * BigInt(3)) / BigInt(4));
+    assert.equal(await getTokenBalance(context, ata(schedulePda)), BigInt(0));
+  });
+
+  it("Перечисляет графики бенефициара по счетчику", async () => {
+    const now = await getUnixTimestamp(context);
+    await createSchedule(TOTAL, now, now, now + DURATION_SECONDS, false);
+
+    const index = await program.account.vestingIndex.fetch(findIndex(beneficiary.publicKey));
+    assert.equal(index.scheduleCount.toNumber(), 2);
+
+    const schedules = await Promise.all(
+      [...Array(index.scheduleCount.toNumber()).keys()].map((i) =>
+        program.account.vestingSchedule.fetch(findSchedule(beneficiary.publicKey, i))
+      )
+    );
+    assert.deepEqual(schedules.map((s) => s.index.toNumber()), [0, 1]);
+    assert.isTrue(schedules[0].revoked);
+    assert.isFalse(schedules[1].revocable);
+  });
+});
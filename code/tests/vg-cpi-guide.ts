@@ -0,0 +1,150 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, Transaction } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  createTransferCheckedInstruction,
+} from "@solana/spl-token";
+import { startAnchor, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { assert } from "chai";
+import { VgCpiGuide } from "../target/types/vg_cpi_guide";
+import { VgToken } from "../target/types/vg_token";
+import { TechHyContracts } from "../target/types/tech_hy_contracts";
+import guideIdl from "../target/idl/vg_cpi_guide.json";
+import vgTokenIdl from "../target/idl/vg_token.json";
+import registryIdl from "../target/idl/tech_hy_contracts.json";
+import {
+  createTokenAccount,
+  getTokenBalance,
+  initializeRegistry,
+  initializeVgToken,
+  vgHookAccounts,
+} from "./utils/bankrun-utils";
+
+describe("VG CPI Guide Tests", () => {
+  const TOKEN_DECIMALS = 9;
+  const ONE_TOKEN = BigInt(10 ** TOKEN_DECIMALS);
+  const tokens = (amount: number) => BigInt(amount) * ONE_TOKEN;
+  // Налог 10%, доли DAO и держателей NFT по 50% (см. initializeVgToken)
+  const taxOf = (amount: bigint) => amount / BigInt(10);
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<VgCpiGuide>;
+  let vgTokenProgram: Program<VgToken>;
+
+  const holder = Keypair.generate();
+  const owner = Keypair.generate();
+  const daoTreasury = Keypair.generate();
+  const feeCollector = Keypair.generate();
+  let vgMint: PublicKey;
+  let vault: PublicKey;
+
+  const ata = (wallet: PublicKey) => getAssociatedTokenAddressSync(vgMint, wallet, true, TOKEN_2022_PROGRAM_ID);
+
+  // ATA получателя, казны и сборщика налога создает vg-token за счет лампортов хранилища
+  const payFromVault = (signer: Keypair, recipient: PublicKey, amount: bigint) =>
+    program.methods
+      .payFromVault(new BN(amount.toString()))
+      .accountsPartial({
+        owner: signer.publicKey,
+        vault,
+        mintAccount: vgMint,
+        vaultTokenAccount: ata(vault),
+        recipientAuthority: recipient,
+        recipientTokenAccount: ata(recipient),
+        daoTreasuryAuthority: daoTreasury.publicKey,
+        daoTreasuryTokenAccount: ata(daoTreasury.publicKey),
+        feeCollectorAuthority: feeCollector.publicKey,
+        feeCollectorTokenAccount: ata(feeCollector.publicKey),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(vgHookAccounts())
+      .signers([signer])
+      .rpc();
+
+  const expectError = async (promise: Promise<unknown>, errorName: string) => {
+    try {
+      await promise;
+      assert.fail(`Ожидалась ошибка ${errorName}`);
+    } catch (error) {
+      assert.include(error.toString(), errorName);
+    }
+  };
+
+  before("Подготовка bankrun, реестра, минта VG и хранилища", async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<VgCpiGuide>(guideIdl as VgCpiGuide, provider);
+    vgTokenProgram = new Program<VgToken>(vgTokenIdl as VgToken, provider);
+    const registryProgram = new Program<TechHyContracts>(registryIdl as TechHyContracts, provider);
+
+    const [mintPda] = PublicKey.findProgramAddressSync([Buffer.from("vg_token_mint")], vgTokenProgram.programId);
+    await initializeRegistry(context, registryProgram, {
+      vcMint: Keypair.generate().publicKey,
+      vgMint: mintPda,
+      vcTokenProgram: Keypair.generate().publicKey,
+      vgTokenProgram: TOKEN_2022_PROGRAM_ID,
+      burnAndEarnProgram: Keypair.generate().publicKey,
+      daoTreasury: daoTreasury.publicKey,
+      feeCollector: feeCollector.publicKey,
+    });
+
+    vgMint = await initializeVgToken(provider, vgTokenProgram, holder.publicKey);
+    [vault] = PublicKey.findProgramAddressSync([Buffer.from("vault"), owner.publicKey.toBuffer()], program.programId);
+
+    // Хранилище - системный аккаунт PDA: лампорты на создание ATA и VG на его ATA
+    context.setAccount(vault, {
+      lamports: 1_000_000_000,
+      data: Buffer.alloc(0),
+      owner: anchor.web3.SystemProgram.programId,
+      executable: false,
+    });
+    await createTokenAccount(provider, vgMint, vault, TOKEN_2022_PROGRAM_ID);
+    const transfer = createTransferCheckedInstruction(
+      ata(holder.publicKey),
+      vgMint,
+      ata(vault),
+      holder.publicKey,
+      tokens(1_000),
+      TOKEN_DECIMALS,
+      [],
+      TOKEN_2022_PROGRAM_ID
+    );
+    transfer.keys.push(...vgHookAccounts());
+    await provider.sendAndConfirm!(new Transaction().add(transfer), [holder]);
+  });
+
+  it("Выплачивает из хранилища через CPI в transfer_with_tax с подписью PDA", async () => {
+    const recipient = Keypair.generate().publicKey;
+
+    await payFromVault(owner, recipient, tokens(200));
+
+    assert.equal(await getTokenBalance(context, ata(recipient)), tokens(200) - taxOf(tokens(200)));
+    assert.equal(await getTokenBalance(context, ata(daoTreasury.publicKey)), taxOf(tokens(200)) / BigInt(2));
+    assert.equal(await getTokenBalance(context, ata(feeCollector.publicKey)), taxOf(tokens(200)) / BigInt(2));
+    assert.equal(await getTokenBalance(context, ata(vault)), tokens(800));
+  });
+
+  it("Отклоняет выплату не владельцем хранилища", async () => {
+    await expectError(payFromVault(Keypair.generate(), Keypair.generate().publicKey, tokens(10)), "ConstraintSeeds");
+  });
+
+  it("Выплачивает всю сумму без налога при нулевой ставке", async () => {
+    await vgTokenProgram.methods
+      .upsertTaxConfig(0, 5000, 5000)
+      .accounts({ payer: provider.wallet.publicKey, authority: provider.wallet.publicKey })
+      .rpc();
+    const recipient = Keypair.generate().publicKey;
+    const daoBefore = await getTokenBalance(context, ata(daoTreasury.publicKey));
+
+    await payFromVault(owner, recipient, tokens(100));
+
+    assert.equal(await getTokenBalance(context, ata(recipient)), tokens(100));
+    assert.equal(await getTokenBalance(context, ata(daoTreasury.publicKey)), daoBefore);
+    assert.equal(await getTokenBalance(context, ata(vault)), tokens(700));
+  });
+});
@@ -0,0 +1,179 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, Transaction, ComputeBudgetProgram } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { startAnchor, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { assert } from "chai";
+import { VgToken } from "../target/types/vg_token";
+import { TechHyContracts } from "../target/types/tech_hy_contracts";
+import vgTokenIdl from "../target/idl/vg_token.json";
+import registryIdl from "../target/idl/tech_hy_contracts.json";
+import {
+  createTokenAccount,
+  getTokenBalance,
+  initializeVgToken,
+  vgHookAccounts,
+  initializeRegistry,
+} from "./utils/bankrun-utils";
+
+describe("VG Token Batch Transfer Tests", () => {
+  const ONE_TOKEN = BigInt(10 ** 9);
+  const tokens = (amount: number) => BigInt(amount) * ONE_TOKEN;
+  // Налог 10%, доли DAO и держателей NFT по 50% (см. initializeVgToken)
+  const taxOf = (amount: bigint) => amount / BigInt(10);
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<VgToken>;
+
+  const holder = Keypair.generate();
+  const daoTreasury = Keypair.generate();
+  const feeCollector = Keypair.generate();
+  let vgMint: PublicKey;
+  let holderAccount: PublicKey;
+  let daoTreasuryAccount: PublicKey;
+  let feeCollectorAccount: PublicKey;
+
+  const ata = (owner: PublicKey) => getAssociatedTokenAddressSync(vgMint, owner, true, TOKEN_2022_PROGRAM_ID);
+
+  // Все аккаунты передаются явно: ATA Token-2022 не выводятся клиентом из IDL
+  const taxAccounts = () => ({
+    senderAuthority: holder.publicKey,
+    mintAccount: vgMint,
+    senderTokenAccount: holderAccount,
+    daoTreasuryAuthority: daoTreasury.publicKey,
+    daoTreasuryTokenAccount: daoTreasuryAccount,
+    feeCollectorAuthority: feeCollector.publicKey,
+    feeCollectorTokenAccount: feeCollectorAccount,
+    tokenProgram: TOKEN_2022_PROGRAM_ID,
+  });
+
+  const batchTransfer = (amounts: bigint[], recipients: PublicKey[]) =>
+    program.methods
+      .batchTransferWithTax(amounts.map((amount) => new BN(amount.toString())))
+      .accountsPartial(taxAccounts())
+      .remainingAccounts([
+        ...recipients.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+        ...vgHookAccounts(),
+      ])
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+      .signers([holder]);
+
+  const singleTransfer = (amount: bigint, recipient: PublicKey) =>
+    program.methods
+      .transferWithTax(new BN(amount.toString()))
+      .accountsPartial({
+        ...taxAccounts(),
+        recipientAuthority: recipient,
+        recipientTokenAccount: ata(recipient),
+      })
+      .remainingAccounts(vgHookAccounts())
+      .signers([holder]);
+
+  // Отправляет транзакцию, подписанную держателем VG, и возвращает потраченные CU
+  const sendMeasured = async (tx: Transaction) => {
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = context.payer.publicKey;
+    tx.sign(context.payer, holder);
+    const meta = await context.banksClient.processTransaction(tx);
+    return Number(meta.computeUnitsConsumed);
+  };
+
+  const expectError = async (promise: Promise<unknown>, errorName: string) => {
+    try {
+      await promise;
+      assert.fail(`Ожидалась ошибка ${errorName}`);
+    } catch (error) {
+      assert.include(error.toString(), errorName);
+    }
+  };
+
+  const createRecipients = async (count: number) => {
+    const recipients = Array.from({ length: count }, () => Keypair.generate().publicKey);
+    for (const recipient of recipients) {
+      await createTokenAccount(provider, vgMint, recipient, TOKEN_2022_PROGRAM_ID);
+    }
+    return recipients;
+  };
+
+  before("Подготовка bankrun, реестра и канонического минта VG", async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<VgToken>(vgTokenIdl as VgToken, provider);
+    const registryProgram = new Program<TechHyContracts>(registryIdl as TechHyContracts, provider);
+
+    const [mintPda] = PublicKey.findProgramAddressSync([Buffer.from("vg_token_mint")], program.programId);
+    await initializeRegistry(context, registryProgram, {
+      vcMint: Keypair.generate().publicKey,
+      vgMint: mintPda,
+      vcTokenProgram: Keypair.generate().publicKey,
+      vgTokenProgram: TOKEN_2022_PROGRAM_ID,
+      burnAndEarnProgram: Keypair.generate().publicKey,
+      daoTreasury: daoTreasury.publicKey,
+      feeCollector: feeCollector.publicKey,
+    });
+
+    vgMint = await initializeVgToken(provider, program, holder.publicKey);
+    holderAccount = ata(holder.publicKey);
+    daoTreasuryAccount = await createTokenAccount(provider, vgMint, daoTreasury.publicKey, TOKEN_2022_PROGRAM_ID);
+    feeCollectorAccount = await createTokenAccount(provider, vgMint, feeCollector.publicKey, TOKEN_2022_PROGRAM_ID);
+
+    // Держатель VG платит за создание ATA в transfer_with_tax
+    context.setAccount(holder.publicKey, {
+      lamports: 1_000_000_000,
+      data: Buffer.alloc(0),
+      owner: anchor.web3.SystemProgram.programId,
+      executable: false,
+    });
+  });
+
+  it("Переводит нескольким получателям с налогом по каждому переводу", async () => {
+    const amounts = [tokens(100), tokens(250), tokens(1_000)];
+    const recipients = await createRecipients(amounts.length);
+    const total = amounts.reduce((sum, amount) => sum + amount, BigInt(0));
+    const totalTax = amounts.reduce((sum, amount) => sum + taxOf(amount), BigInt(0));
+    const holderBefore = await getTokenBalance(context, holderAccount);
+
+    await batchTransfer(amounts, recipients).rpc();
+
+    for (let i = 0; i < recipients.length; i++) {
+      assert.equal(await getTokenBalance(context, ata(recipients[i])), amounts[i] - taxOf(amounts[i]));
+    }
+    assert.equal(await getTokenBalance(context, daoTreasuryAccount), totalTax / BigInt(2));
+    assert.equal(await getTokenBalance(context, feeCollectorAccount), totalTax - totalTax / BigInt(2));
+    assert.equal(await getTokenBalance(context, holderAccount), holderBefore - total);
+  });
+
+  it("Отклоняет пустой пакет и получателей меньше, чем сумм", async () => {
+    const [recipient] = await createRecipients(1);
+    await expectError(batchTransfer([], []).rpc(), "InvalidBatch");
+    await expectError(batchTransfer([tokens(1), tokens(2), tokens(3)], [recipient]).rpc(), "InvalidBatch");
+  });
+
+  it("Отклоняет получателя, который не является токен-аккаунтом VG", async () => {
+    const [recipient] = await createRecipients(1);
+    const wallet = Keypair.generate().publicKey;
+    await expectError(batchTransfer([tokens(1), tokens(1)], [recipient, wallet]).rpc(), "InvalidRecipient");
+  });
+
+  it("Бенчмарк CU: пакет против отдельных transfer_with_tax", async () => {
+    const count = 8;
+    const amount = tokens(10);
+    const singleRecipients = await createRecipients(count);
+    const batchRecipients = await createRecipients(count);
+
+    let singleTotal = 0;
+    for (const recipient of singleRecipients) {
+      singleTotal += await sendMeasured(await singleTransfer(amount, recipient).transaction());
+    }
+    const batch = await sendMeasured(await batchTransfer(Array(count).fill(amount), batchRecipients).transaction());
+
+    console.log(`CU: ${count} x transfer_with_tax = ${singleTotal}, batch_transfer_with_tax = ${batch}`);
+    for (const recipient of batchRecipients) {
+      assert.equal(await getTokenBalance(context, ata(recipient)), amount - taxOf(amount));
+    }
+    assert.isBelow(batch, singleTotal);
+  });
+});
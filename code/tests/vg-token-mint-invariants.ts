@@ -0,0 +1,166 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, BPF_LOADER_UPGRADEABLE_PROGRAM_ID } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  AuthorityType,
+  createMint,
+  setAuthority,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { VgToken } from "../target/types/vg_token";
+import { TechHyContracts } from "../target/types/tech_hy_contracts";
+
+describe("VG Token Mint Invariants", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.VgToken as Program<VgToken>;
+  const registryProgram = anchor.workspace.TechHyContracts as Program<TechHyContracts>;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  const TOKEN_DECIMALS = 9;
+  const recipient = Keypair.generate();
+
+  const [registryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("ecosystem_registry")],
+    registryProgram.programId
+  );
+  // ProgramData реестра: initialize_registry доступен только upgrade authority (кошелек провайдера)
+  const [registryProgramData] = PublicKey.findProgramAddressSync(
+    [registryProgram.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  );
+  const [taxConfigPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("tax_config_seed")],
+    program.programId
+  );
+
+  let daoTreasury: PublicKey;
+  let feeCollector: PublicKey;
+
+  before("Подготовка реестра и конфигурации налога", async () => {
+    let registry = await registryProgram.account.ecosystemRegistry.fetchNullable(registryPda);
+    if (registry === null) {
+      await registryProgram.methods
+        .initializeRegistry({
+          vcMint: Keypair.generate().publicKey,
+          vgMint: Keypair.generate().publicKey,
+          vcTokenProgram: Keypair.generate().publicKey,
+          vgTokenProgram: program.programId,
+          burnAndEarnProgram: Keypair.generate().publicKey,
+          daoTreasury: Keypair.generate().publicKey,
+          feeCollector: Keypair.generate().publicKey,
+        })
+        .accounts({ authority: provider.publicKey, programData: registryProgramData })
+        .rpc();
+      registry = await registryProgram.account.ecosystemRegistry.fetch(registryPda);
+    }
+    daoTreasury = registry.daoTreasury;
+    feeCollector = registry.feeCollector;
+
+    const taxConfig = await program.account.taxConfig.fetchNullable(taxConfigPda);
+    if (taxConfig === null) {
+      await program.methods
+        .upsertTaxConfig(1000, 5000, 5000)
+        .accounts({ payer: provider.publicKey, authority: provider.publicKey })
+        .rpc();
+    }
+  });
+
+  // Создает посторонний минт Token-2022 с заданными авторитетами
+  const createTestMint = async (keepMintAuthority: boolean, freezeAuthority: PublicKey | null) => {
+    const mint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      freezeAuthority,
+      TOKEN_DECIMALS,
+      Keypair.generate(),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    if (!keepMintAuthority) {
+      await setAuthority(
+        provider.connection,
+        payer,
+        mint,
+        payer.publicKey,
+        AuthorityType.MintTokens,
+        null,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+    }
+    return mint;
+  };
+
+  const transferWithMint = (mint: PublicKey) => {
+    const ata = (owner: PublicKey) =>
+      getAssociatedTokenAddressSync(mint, owner, true, TOKEN_2022_PROGRAM_ID);
+    return program.methods
+      .transferWithTax(new anchor.BN(1_000 * 10 ** TOKEN_DECIMALS))
+      .accounts({
+        senderAuthority: provider.publicKey,
+        taxConfig: taxConfigPda,
+        registry: registryPda,
+        mintAccount: mint,
+        senderTokenAccount: ata(provider.publicKey),
+        recipientAuthority: recipient.publicKey,
+        recipientTokenAccount: ata(recipient.publicKey),
+        daoTreasuryAuthority: daoTreasury,
+        daoTreasuryTokenAccount: ata(daoTreasury),
+        feeCollectorAuthority: feeCollector,
+        feeCollectorTokenAccount: ata(feeCollector),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+  };
+
+  const expectMintError = async (mint: PublicKey, expectedLog: string) => {
+    try {
+      await transferWithMint(mint);
+      assert.fail("Перевод с неканоническим минтом должен быть отклонен");
+    } catch (error) {
+      assert.include(error.toString(), "MintError");
+      assert.isTrue(
+        (error.logs ?? []).some((log: string) => log.includes(expectedLog)),
+        `В логах нет причины отказа: ${expectedLog}`
+      );
+    }
+  };
+
+  it("Отклоняет перевод с посторонним минтом", async () => {
+    // Авторитеты отозваны, но у минта нет хука VG и адрес не совпадает с PDA
+    const mint = await createTestMint(false, null);
+    await expectMintError(mint, "TransferHook");
+  });
+
+  it("Отклоняет перевод с минтом, у которого жива mint authority", async () => {
+    const mint = await createTestMint(true, null);
+    await expectMintError(mint, "mint authority");
+  });
+
+  it("Отклоняет перевод с минтом, у которого есть freeze authority", async () => {
+    const mint = await createTestMint(false, payer.publicKey);
+    await expectMintError(mint, "freeze authority");
+  });
+
+  it("Отклоняет конфигурацию налога, где доли DAO и NFT не дают в сумме 10000 bps", async () => {
+    for (const [daoShare, nftShare] of [[5000, 4000], [6000, 5000]]) {
+      try {
+        await program.methods
+          .upsertTaxConfig(1000, daoShare, nftShare)
+          .accounts({ payer: provider.publicKey, authority: provider.publicKey })
+          .rpc();
+        assert.fail("Доли налога должны в сумме давать 10000 bps");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidTaxCalculation");
+      }
+    }
+    const taxConfig = await program.account.taxConfig.fetch(taxConfigPda);
+    assert.equal(taxConfig.daoShareBps + taxConfig.nftHoldersShareBps, 10_000);
+  });
+});
@@ -0,0 +1,182 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, Transaction } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  ExtensionType,
+  getAccountLen,
+  getAssociatedTokenAddressSync,
+  createInitializeAccount3Instruction,
+  createApproveCheckedInstruction,
+  createTransferCheckedInstruction,
+} from "@solana/spl-token";
+import { startAnchor, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { assert } from "chai";
+import { VgToken } from "../target/types/vg_token";
+import { TechHyContracts } from "../target/types/tech_hy_contracts";
+import vgTokenIdl from "../target/idl/vg_token.json";
+import registryIdl from "../target/idl/tech_hy_contracts.json";
+import {
+  createTokenAccount,
+  getTokenBalance,
+  initializeVgToken,
+  vgHookAccounts,
+  initializeRegistry,
+} from "./utils/bankrun-utils";
+
+describe("VG Token Delegated Transfer Tests", () => {
+  const TOKEN_DECIMALS = 9;
+  const ONE_TOKEN = BigInt(10 ** TOKEN_DECIMALS);
+  const tokens = (amount: number) => BigInt(amount) * ONE_TOKEN;
+  // Налог 10%, доли DAO и держателей NFT по 50% (см. initializeVgToken)
+  const taxOf = (amount: bigint) => amount / BigInt(10);
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<VgToken>;
+
+  const holder = Keypair.generate();
+  const owner = Keypair.generate();
+  const router = Keypair.generate();
+  const daoTreasury = Keypair.generate();
+  const feeCollector = Keypair.generate();
+  let vgMint: PublicKey;
+  let ownerAccount: PublicKey;
+  let recipientAccount: PublicKey;
+
+  const ata = (wallet: PublicKey) => getAssociatedTokenAddressSync(vgMint, wallet, true, TOKEN_2022_PROGRAM_ID);
+
+  // Токен-аккаунт VG по адресу ключевой пары (не ATA) с расширением TransferHookAccount
+  const createPlainTokenAccount = async (accountOwner: PublicKey) => {
+    const account = Keypair.generate();
+    const space = getAccountLen([ExtensionType.TransferHookAccount]);
+    const lamports = Number((await context.banksClient.getRent()).minimumBalance(BigInt(space)));
+    const tx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: provider.wallet.publicKey,
+        newAccountPubkey: account.publicKey,
+        space,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeAccount3Instruction(account.publicKey, vgMint, accountOwner, TOKEN_2022_PROGRAM_ID)
+    );
+    await provider.sendAndConfirm!(tx, [account]);
+    return account.publicKey;
+  };
+
+  // Перевод VG без налога напрямую через Token-2022 (для подготовки балансов)
+  const fundFromHolder = async (destination: PublicKey, amount: bigint) => {
+    const transfer = createTransferCheckedInstruction(
+      ata(holder.publicKey),
+      vgMint,
+      destination,
+      holder.publicKey,
+      amount,
+      TOKEN_DECIMALS,
+      [],
+      TOKEN_2022_PROGRAM_ID
+    );
+    transfer.keys.push(...vgHookAccounts());
+    await provider.sendAndConfirm!(new Transaction().add(transfer), [holder]);
+  };
+
+  const approve = async (delegate: PublicKey, amount: bigint) => {
+    const tx = new Transaction().add(
+      createApproveCheckedInstruction(
+        ownerAccount,
+        vgMint,
+        delegate,
+        owner.publicKey,
+        amount,
+        TOKEN_DECIMALS,
+        [],
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    await provider.sendAndConfirm!(tx, [owner]);
+  };
+
+  const transferFrom = (authority: Keypair, amount: bigint) =>
+    program.methods
+      .transferFromWithTax(new BN(amount.toString()))
+      .accountsPartial({
+        authority: authority.publicKey,
+        payer: provider.wallet.publicKey,
+        mintAccount: vgMint,
+        senderTokenAccount: ownerAccount,
+        recipientTokenAccount: recipientAccount,
+        daoTreasuryAuthority: daoTreasury.publicKey,
+        daoTreasuryTokenAccount: ata(daoTreasury.publicKey),
+        feeCollectorAuthority: feeCollector.publicKey,
+        feeCollectorTokenAccount: ata(feeCollector.publicKey),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(vgHookAccounts())
+      .signers([authority])
+      .rpc();
+
+  const expectError = async (promise: Promise<unknown>, errorName: string) => {
+    try {
+      await promise;
+      assert.fail(`Ожидалась ошибка ${errorName}`);
+    } catch (error) {
+      assert.include(error.toString(), errorName);
+    }
+  };
+
+  before("Подготовка bankrun, реестра, минта VG и аккаунтов отправителя", async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<VgToken>(vgTokenIdl as VgToken, provider);
+    const registryProgram = new Program<TechHyContracts>(registryIdl as TechHyContracts, provider);
+
+    const [mintPda] = PublicKey.findProgramAddressSync([Buffer.from("vg_token_mint")], program.programId);
+    await initializeRegistry(context, registryProgram, {
+      vcMint: Keypair.generate().publicKey,
+      vgMint: mintPda,
+      vcTokenProgram: Keypair.generate().publicKey,
+      vgTokenProgram: TOKEN_2022_PROGRAM_ID,
+      burnAndEarnProgram: Keypair.generate().publicKey,
+      daoTreasury: daoTreasury.publicKey,
+      feeCollector: feeCollector.publicKey,
+    });
+
+    vgMint = await initializeVgToken(provider, program, holder.publicKey);
+    ownerAccount = await createPlainTokenAccount(owner.publicKey);
+    recipientAccount = await createPlainTokenAccount(Keypair.generate().publicKey);
+    await createTokenAccount(provider, vgMint, daoTreasury.publicKey, TOKEN_2022_PROGRAM_ID);
+    await createTokenAccount(provider, vgMint, feeCollector.publicKey, TOKEN_2022_PROGRAM_ID);
+    await fundFromHolder(ownerAccount, tokens(1_000));
+  });
+
+  it("Делегат переводит с налогом с токен-аккаунта, который не является ATA", async () => {
+    await approve(router.publicKey, tokens(300));
+
+    await transferFrom(router, tokens(200));
+
+    assert.equal(await getTokenBalance(context, recipientAccount), tokens(200) - taxOf(tokens(200)));
+    assert.equal(await getTokenBalance(context, ata(daoTreasury.publicKey)), taxOf(tokens(200)) / BigInt(2));
+    assert.equal(await getTokenBalance(context, ata(feeCollector.publicKey)), taxOf(tokens(200)) / BigInt(2));
+    assert.equal(await getTokenBalance(context, ownerAccount), tokens(800));
+  });
+
+  it("Отклоняет перевод сверх оставшегося одобрения", async () => {
+    // После перевода 200 из 300 у делегата осталось 100
+    await expectError(transferFrom(router, tokens(150)), "InsufficientDelegation");
+  });
+
+  it("Отклоняет подпись без одобрения", async () => {
+    await expectError(transferFrom(Keypair.generate(), tokens(10)), "Unauthorized");
+  });
+
+  it("Владелец переводит со своего аккаунта без одобрения", async () => {
+    const before = await getTokenBalance(context, recipientAccount);
+    await transferFrom(owner, tokens(100));
+
+    assert.equal(await getTokenBalance(context, recipientAccount), before + tokens(100) - taxOf(tokens(100)));
+    assert.equal(await getTokenBalance(context, ownerAccount), tokens(700));
+  });
+});
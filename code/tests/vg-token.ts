@@ -617,4 +617,48 @@ describe("VG Token Tests", () => {
     
     console.log("Имитация NFT Fee Key успешно протестирована");
   });
+  describe("Обновление метаданных VG через DAO", () => {
+    const [registryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("ecosystem_registry")],
+      new PublicKey("3oX2qbPpcWeHGfQknX8r1mwASpHCcZQLsoz2edNoHraV")
+    );
+
+    const updateMetadata = (authority: Keypair | null, symbol: string, uri: string) => {
+      const builder = program.methods
+        .updateVgMetadata("VG Governance Token", symbol, uri)
+        .accounts({
+          daoAuthority: authority ? authority.publicKey : provider.publicKey,
+          registry: registryPda,
+          mintAccount: mintPda,
+          metadataAccount: metadataPda,
+          metadataProgram: METADATA_PROGRAM_ID,
+        });
+      return authority ? builder.signers([authority]).rpc() : builder.rpc();
+    };
+
+    it("Отклоняет обновление не исполнителем DAO", async () => {
+      try {
+        await updateMetadata(Keypair.generate(), "VG", "https://tech-hy.io/metadata/vg-token.json");
+        assert.fail("Обновлять метаданные может только исполнитель DAO");
+      } catch (error) {
+        assert.include(error.toString(), "Unauthorized");
+      }
+    });
+
+    it("Отклоняет URI без https и неверный символ", async () => {
+      try {
+        await updateMetadata(null, "VG", "http://tech-hy.io/metadata/vg-token.json");
+        assert.fail("URI без https:// должен быть отклонен");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidMetadataUri");
+      }
+
+      try {
+        await updateMetadata(null, "vg-token", "https://tech-hy.io/metadata/vg-token.json");
+        assert.fail("Символ в нижнем регистре должен быть отклонен");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidMetadataSymbol");
+      }
+    });
+  });
 }); 
\ No newline at end of file
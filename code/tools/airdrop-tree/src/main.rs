@@ -0,0 +1,214 @@
+//! Офчейн-утилита раздач tech-hy-distributor: строит merkle-дерево из CSV
+//! `address,amount` (сумма в минимальных единицах, строка заголовка допускается)
+//! и выдает корень и доказательства для получателей.
+//!
+//! airdrop-tree build <airdrop.csv> <out.json>  - корень, параметры create_distributor и доказательства всех получателей
+//! airdrop-tree proof <airdrop.csv> <address>   - номер листа, сумма и доказательство одного получателя
+//!
+//! Номер листа - номер строки данных в CSV (с нуля), поэтому порядок строк
+//! после создания раздачи менять нельзя.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::{env, fs, process};
+
+use anchor_lang::prelude::Pubkey;
+use serde_json::{json, Value};
+use tech_hy_common::{airdrop_leaf, verify_proof, MerkleTree, AIRDROP_MAX_CLAIMANTS};
+
+const USAGE: &str = "использование:\n  airdrop-tree build <airdrop.csv> <out.json>\n  airdrop-tree proof <airdrop.csv> <address>";
+
+struct Entry {
+    claimant: Pubkey,
+    amount: u64,
+}
+
+struct Airdrop {
+    entries: Vec<Entry>,
+    tree: MerkleTree,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["build", csv, out] => build(csv, out),
+        ["proof", csv, address] => proof(csv, address),
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+fn build(csv_path: &str, out_path: &str) -> Result<(), String> {
+    let airdrop = load(csv_path)?;
+    let max_total_claim = airdrop
+        .entries
+        .iter()
+        .try_fold(0u64, |total, entry| total.checked_add(entry.amount))
+        .ok_or("сумма раздачи превышает u64")?;
+
+    let claims: Vec<Value> = airdrop
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| claim_json(&airdrop, index, entry))
+        .collect::<Result<_, _>>()?;
+
+    let output = json!({
+        "merkle_root": to_hex(&airdrop.tree.root()),
+        "max_total_claim": max_total_claim.to_string(),
+        "num_nodes": airdrop.entries.len(),
+        "claims": claims,
+    });
+    let text = serde_json::to_string_pretty(&output).map_err(|e| e.to_string())?;
+    fs::write(out_path, text + "\n").map_err(|e| format!("{}: {}", out_path, e))?;
+
+    println!("корень: {}", to_hex(&airdrop.tree.root()));
+    println!("получателей: {}, всего: {}", airdrop.entries.len(), max_total_claim);
+    Ok(())
+}
+
+fn proof(csv_path: &str, address: &str) -> Result<(), String> {
+    let claimant = Pubkey::from_str(address).map_err(|_| format!("неверный адрес: {}", address))?;
+    let airdrop = load(csv_path)?;
+    let (index, entry) = airdrop
+        .entries
+        .iter()
+        .enumerate()
+        .find(|(_, entry)| entry.claimant == claimant)
+        .ok_or(format!("{} нет в раздаче", address))?;
+
+    let claim = claim_json(&airdrop, index, entry)?;
+    println!("{}", serde_json::to_string_pretty(&claim).map_err(|e| e.to_string())?);
+    Ok(())
+}
+
+/// Данные инструкции claim для одного получателя; доказательство сверяется с корнем
+fn claim_json(airdrop: &Airdrop, index: usize, entry: &Entry) -> Result<Value, String> {
+    let proof = airdrop.tree.proof(index).ok_or("лист вне дерева")?;
+    let leaf = airdrop_leaf(index as u64, &entry.claimant, entry.amount);
+    if !verify_proof(&proof, &airdrop.tree.root(), leaf) {
+        return Err(format!("доказательство листа {} не сходится с корнем", index));
+    }
+    Ok(json!({
+        "index": index,
+        "claimant": entry.claimant.to_string(),
+        "amount": entry.amount.to_string(),
+        "proof": proof.iter().map(to_hex).collect::<Vec<_>>(),
+    }))
+}
+
+fn load(csv_path: &str) -> Result<Airdrop, String> {
+    let text = fs::read_to_string(csv_path).map_err(|e| format!("{}: {}", csv_path, e))?;
+    let entries = parse_csv(&text)?;
+    let leaves = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| airdrop_leaf(index as u64, &entry.claimant, entry.amount))
+        .collect();
+    let tree = MerkleTree::new(leaves).ok_or("в CSV нет получателей")?;
+    Ok(Airdrop { entries, tree })
+}
+
+/// Разбирает строки `address,amount`. Первая строка пропускается, если это заголовок;
+/// пустые строки игнорируются, повторный адрес - ошибка (получатель сводится в одну строку).
+/// Получателей не больше AIRDROP_MAX_CLAIMANTS, иначе create_distributor отклонит раздачу.
+fn parse_csv(text: &str) -> Result<Vec<Entry>, String> {
+    let mut entries = Vec::new();
+    let mut seen = HashSet::new();
+    for (line_number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let [address, amount] = fields.as_slice() else {
+            return Err(format!("строка {}: ожидается address,amount", line_number + 1));
+        };
+        let Ok(claimant) = Pubkey::from_str(address) else {
+            if line_number == 0 {
+                continue;
+            }
+            return Err(format!("строка {}: неверный адрес {}", line_number + 1, address));
+        };
+        let amount: u64 = amount
+            .parse()
+            .map_err(|_| format!("строка {}: неверная сумма {}", line_number + 1, amount))?;
+        if amount == 0 {
+            return Err(format!("строка {}: нулевая сумма", line_number + 1));
+        }
+        if !seen.insert(claimant) {
+            return Err(format!("строка {}: адрес {} уже есть в раздаче", line_number + 1, address));
+        }
+        if entries.len() as u64 == AIRDROP_MAX_CLAIMANTS {
+            return Err(format!("строка {}: получателей больше {}", line_number + 1, AIRDROP_MAX_CLAIMANTS));
+        }
+        entries.push(Entry { claimant, amount });
+    }
+    Ok(entries)
+}
+
+fn to_hex(bytes: &[u8; 32]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(index: u64) -> Pubkey {
+        let mut bytes = [0u8; 32];
+        bytes[..8].copy_from_slice(&(index + 1).to_le_bytes());
+        Pubkey::new_from_array(bytes)
+    }
+
+    fn csv(count: u64) -> String {
+        (0..count).map(|index| format!("{},{}\n", address(index), index + 1)).collect()
+    }
+
+    #[test]
+    fn parses_rows_and_skips_header() {
+        let text = format!("address,amount\n\n{}", csv(3));
+        let entries = parse_csv(&text).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[2].claimant, address(2));
+        assert_eq!(entries[2].amount, 3);
+    }
+
+    #[test]
+    fn rejects_bad_rows() {
+        let first = address(0);
+        assert!(parse_csv(&format!("{},0", first)).is_err());
+        assert!(parse_csv(&format!("{},1,2", first)).is_err());
+        assert!(parse_csv(&format!("{},x", first)).is_err());
+        assert!(parse_csv(&format!("{},1\n{},2", first, first)).is_err());
+        assert!(parse_csv(&format!("{},1\nнеадрес,2", first)).is_err());
+    }
+
+    #[test]
+    fn caps_claimants_at_distributor_limit() {
+        assert_eq!(parse_csv(&csv(AIRDROP_MAX_CLAIMANTS)).unwrap().len() as u64, AIRDROP_MAX_CLAIMANTS);
+        let error = parse_csv(&csv(AIRDROP_MAX_CLAIMANTS + 1)).err().unwrap();
+        assert!(error.contains(&AIRDROP_MAX_CLAIMANTS.to_string()));
+    }
+
+    #[test]
+    fn claims_verify_against_root() {
+        for count in [1, 2, 5] {
+            let entries = parse_csv(&csv(count)).unwrap();
+            let leaves = entries
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| airdrop_leaf(index as u64, &entry.claimant, entry.amount))
+                .collect();
+            let airdrop = Airdrop { tree: MerkleTree::new(leaves).unwrap(), entries };
+            for (index, entry) in airdrop.entries.iter().enumerate() {
+                let claim = claim_json(&airdrop, index, entry).unwrap();
+                assert_eq!(claim["index"], index);
+                assert_eq!(claim["amount"], entry.amount.to_string());
+            }
+        }
+    }
+}